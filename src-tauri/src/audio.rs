@@ -1,190 +1,1177 @@
+use crate::ring_buffer;
+use crate::vad::{Vad, VadConfig, VadEvent, VadStatus};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use parking_lot::Mutex;
 use rubato::{SincFixedIn, SincInterpolationParameters, SincInterpolationType, WindowFunction, Resampler};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
 
-#[derive(Clone, serde::Serialize)]
+/// Dosyadan transkripsiyon icin desteklenen ses formatlari
+pub const SUPPORTED_AUDIO_EXTENSIONS: &[&str] = &["wav", "mp3", "flac", "ogg", "oga", "m4a", "mp4", "aac"];
+
+/// VAD olaylarini kayit thread'inden disari ileten geri cagirim
+pub type VadEventSink = Box<dyn Fn(VadEvent) + Send + 'static>;
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct AudioDevice {
     pub name: String,
+    /// Kararli kimlik: Windows'ta WASAPI uc nokta kimligi (ad degisse de ayni kalir),
+    /// diger platformlarda cihaz adi. Ayni adli cihazlar `#2`, `#3` ekini alir.
     pub id: String,
+    pub is_default: bool,
+}
+
+/// Secilen mikrofon bulunamadigi ya da kayit sirasinda kayboldugu icin
+/// varsayilan mikrofona gecildi
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DeviceFallback {
+    /// Kaybolan cihazin adi (baslangicta bulunamadiysa kayitli kimligi)
+    pub lost: String,
+    /// Yerine gecilen mikrofonun adi
+    pub fallback: String,
+}
+
+/// Varsayilan mikrofona gecisleri kayit thread'inden disari ileten geri cagirim
+pub type FallbackSink = Box<dyn Fn(DeviceFallback) + Send + 'static>;
+
+/// Kullanicinin cihaz icin sectigi giris yapilandirmasi
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct InputConfig {
+    pub sample_rate: u32,
+    pub channels: u16,
 }
 
+/// Cihazin destekledigi, ayarlardan secilebilir yapilandirma
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct InputConfigOption {
+    pub sample_rate: u32,
+    pub channels: u16,
+    /// Bu hiz/kanal icin kullanilacak ornek formati (f32, i16...)
+    pub sample_format: String,
+    pub is_default: bool,
+}
+
+/// Tuketici thread'inin yayinladigi cerceve: 16kHz'de 30ms (VAD cercevesi)
+pub const FRAME_SAMPLES: usize = 480;
+/// Cercevelerin ornekleme hizi
+pub const FRAME_RATE: u32 = 16000;
+/// Ses thread'i ile tuketici arasindaki halka — cihaz hizinda 2 saniye
+const RING_SECONDS: usize = 2;
+/// Halka bosken tuketicinin bekleme suresi
+const CONSUMER_POLL: Duration = Duration::from_millis(5);
+/// Tuketicinin halkadan tek seferde okudugu en fazla ornek
+const CONSUMER_CHUNK: usize = 4096;
+/// RNNoise yalnizca 48kHz'te calisir
+const DENOISE_RATE: u32 = 48000;
+/// Wet/dry mix: %60 denoised + %40 orijinal — Turkce fricatifleri korumak icin
+/// (RNNoise Ingilizce ses uzerinde egitilmis, Turkce ş/ç/ğ seslerini bosabilir)
+const DENOISE_WET: f32 = 0.6;
+
+/// Kaydedicinin 16kHz cercevelerini alan abone (VAD, canli STT, seviye olcer...).
+/// Tuketici thread'inde cagrilir; ses thread'ini bekletmez.
+pub trait FrameSubscriber: Send {
+    /// `false` donerse abonelik kaldirilir
+    fn on_frame(&mut self, frame: &[f32]) -> bool;
+    /// Kayit bitti; son cerceve yayinlandiktan sonra cagrilir
+    fn on_stop(&mut self) {}
+}
+
+/// Kanal aboneligi — alici birakilinca abonelik kendiliginden kalkar
+impl FrameSubscriber for mpsc::Sender<Vec<f32>> {
+    fn on_frame(&mut self, frame: &[f32]) -> bool {
+        self.send(frame.to_vec()).is_ok()
+    }
+}
+
+type Subscribers = Arc<Mutex<Vec<Box<dyn FrameSubscriber>>>>;
+
 pub struct SharedAudioState {
     pub is_recording: Arc<AtomicBool>,
-    pub audio_buffer: Arc<Mutex<Vec<f32>>>,
+    /// Kayit dosyasina yazma hatasi (`stop_recording` kullaniciya dondurur)
+    recorder_error: Arc<Mutex<Option<String>>>,
     pub level: Arc<Mutex<f32>>,
-    pub sample_rate: Mutex<u32>,
+    pub vad_status: Arc<Mutex<VadStatus>>,
+    /// Cihazin kendi ornekleme hizi (varsayilan mikrofona gecilince degisebilir)
+    pub sample_rate: Arc<Mutex<u32>>,
+    subscribers: Subscribers,
+    /// Akisi acan ve cihaz kaybini izleyen thread
+    capture: Mutex<Option<std::thread::JoinHandle<()>>>,
+    consumer: Mutex<Option<std::thread::JoinHandle<()>>>,
 }
 
 impl SharedAudioState {
     pub fn new() -> Self {
         Self {
             is_recording: Arc::new(AtomicBool::new(false)),
-            audio_buffer: Arc::new(Mutex::new(Vec::new())),
+            recorder_error: Arc::new(Mutex::new(None)),
             level: Arc::new(Mutex::new(0.0)),
-            sample_rate: Mutex::new(0),
+            vad_status: Arc::new(Mutex::new(VadStatus::default())),
+            sample_rate: Arc::new(Mutex::new(0)),
+            subscribers: Arc::new(Mutex::new(Vec::new())),
+            capture: Mutex::new(None),
+            consumer: Mutex::new(None),
         }
     }
+
+    /// Suren kayda abone ol; kayit bitince kanal kapanir
+    pub fn subscribe(&self) -> mpsc::Receiver<Vec<f32>> {
+        let (tx, rx) = mpsc::channel();
+        self.subscribers.lock().push(Box::new(tx));
+        rx
+    }
 }
 
-pub fn list_devices() -> Vec<AudioDevice> {
-    let host = cpal::default_host();
-    let mut devices = Vec::new();
+/// Cerceve RMS'i → seviye olcer
+struct LevelMeter(Arc<Mutex<f32>>);
+
+impl FrameSubscriber for LevelMeter {
+    fn on_frame(&mut self, frame: &[f32]) -> bool {
+        *self.0.lock() = (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt();
+        true
+    }
+
+    fn on_stop(&mut self) {
+        *self.0.lock() = 0.0;
+    }
+}
+
+/// VAD olaylarini `sink`'e, durumu paylasilan alana yazar
+struct VadSubscriber {
+    vad: Vad,
+    sink: VadEventSink,
+    status: Arc<Mutex<VadStatus>>,
+}
 
-    if let Ok(input_devices) = host.input_devices() {
-        for device in input_devices {
-            if let Ok(name) = device.name() {
-                devices.push(AudioDevice {
-                    id: name.clone(),
-                    name,
-                });
+impl FrameSubscriber for VadSubscriber {
+    fn on_frame(&mut self, frame: &[f32]) -> bool {
+        for event in self.vad.process(frame) {
+            (self.sink)(event);
+        }
+        *self.status.lock() = self.vad.status();
+        true
+    }
+}
+
+/// Kaydin yazildigi gecici dosya — uzun kayitlar bellekte birikmez
+fn recording_path() -> PathBuf {
+    std::env::temp_dir().join(format!("kayit-{}.wav", std::process::id()))
+}
+
+/// Kaydi 16kHz mono float WAV olarak diske yazar (transkripsiyon icin).
+/// Uzunluk siniri yok; WAV'in 4 GB siniri bu formatta ~18 saate denk gelir.
+struct Recorder {
+    writer: Option<hound::WavWriter<std::io::BufWriter<std::fs::File>>>,
+    error: Arc<Mutex<Option<String>>>,
+}
+
+impl Recorder {
+    fn create(path: &Path, error: Arc<Mutex<Option<String>>>) -> Result<Self, String> {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: FRAME_RATE,
+            bits_per_sample: 32,
+            sample_format: hound::SampleFormat::Float,
+        };
+        let writer = hound::WavWriter::create(path, spec)
+            .map_err(|e| format!("Kayit dosyasi olusturulamadi: {}", e))?;
+        Ok(Self { writer: Some(writer), error })
+    }
+
+    fn fail(&mut self, e: hound::Error) {
+        let message = format!("Kayit dosyasina yazilamadi: {}", e);
+        log::error!("{}", message);
+        *self.error.lock() = Some(message);
+        self.writer = None;
+    }
+}
+
+impl FrameSubscriber for Recorder {
+    fn on_frame(&mut self, frame: &[f32]) -> bool {
+        let Some(writer) = self.writer.as_mut() else { return false };
+        for &sample in frame {
+            if let Err(e) = writer.write_sample(sample) {
+                // Seviye olcer ve VAD calismaya devam eder; yalnizca bu abonelik kalkar
+                self.fail(e);
+                return false;
             }
         }
+        true
     }
 
-    devices
+    fn on_stop(&mut self) {
+        if let Some(writer) = self.writer.take() {
+            if let Err(e) = writer.finalize() {
+                self.fail(e);
+            }
+        }
+    }
 }
 
-pub fn start_recording(
-    state: &SharedAudioState,
-    device_name: Option<String>,
-) -> Result<cpal::Stream, String> {
+/// `Recorder`'in yazdigi kaydi oku
+fn read_recording(path: &Path) -> Result<Vec<f32>, String> {
+    let mut reader = hound::WavReader::open(path).map_err(|e| format!("Kayit dosyasi okunamadi: {}", e))?;
+    reader
+        .samples::<f32>()
+        .collect::<Result<Vec<f32>, _>>()
+        .map_err(|e| format!("Kayit dosyasi okunamadi: {}", e))
+}
+
+/// Akan sesi 480 orneklik RNNoise cercevelerinde temizler (48kHz)
+struct Denoiser {
+    state: Box<nnnoiseless::DenoiseState<'static>>,
+    pending: Vec<f32>,
+}
+
+impl Denoiser {
+    fn new() -> Self {
+        Self { state: nnnoiseless::DenoiseState::new(), pending: Vec::new() }
+    }
+
+    fn process(&mut self, samples: &[f32]) -> Vec<f32> {
+        use nnnoiseless::DenoiseState;
+        self.pending.extend_from_slice(samples);
+        let full = self.pending.len() / DenoiseState::FRAME_SIZE * DenoiseState::FRAME_SIZE;
+        let mut clean = Vec::with_capacity(full);
+        let mut out_buf = [0.0f32; DenoiseState::FRAME_SIZE];
+        for frame in self.pending[..full].chunks_exact(DenoiseState::FRAME_SIZE) {
+            self.state.process_frame(&mut out_buf, frame);
+            clean.extend(out_buf.iter().zip(frame).map(|(d, o)| d * DENOISE_WET + o * (1.0 - DENOISE_WET)));
+        }
+        self.pending.drain(..full);
+        clean
+    }
+
+    /// Yarim kalan son cerceveyi sessizlikle tamamlayip temizle
+    fn flush(&mut self) -> Vec<f32> {
+        let len = self.pending.len();
+        if len == 0 {
+            return Vec::new();
+        }
+        let mut clean = self.process(&vec![0.0; nnnoiseless::DenoiseState::FRAME_SIZE - len]);
+        clean.truncate(len);
+        clean
+    }
+}
+
+/// Cihaz hizindaki sesi 16kHz cercevelere ceviren zincir. Gurultu bastirma
+/// acikken: cihaz → 48kHz (gerekirse) → RNNoise → 16kHz. Boylece zaten 16kHz'e
+/// inmis ses bir daha 48kHz'e cikarilmaz.
+struct CapturePipeline {
+    denoise: Option<(StreamResampler, Denoiser)>,
+    to_frames: StreamResampler,
+}
+
+impl CapturePipeline {
+    fn new(rate: u32, denoise: bool) -> Result<Self, String> {
+        if !denoise {
+            return Ok(Self { denoise: None, to_frames: StreamResampler::new(rate, FRAME_RATE)? });
+        }
+        Ok(Self {
+            denoise: Some((StreamResampler::new(rate, DENOISE_RATE)?, Denoiser::new())),
+            to_frames: StreamResampler::new(DENOISE_RATE, FRAME_RATE)?,
+        })
+    }
+
+    fn process(&mut self, samples: &[f32]) -> Result<Vec<f32>, String> {
+        match self.denoise.as_mut() {
+            Some((to_denoise, denoiser)) => {
+                let clean = denoiser.process(&to_denoise.process(samples)?);
+                self.to_frames.process(&clean)
+            }
+            None => self.to_frames.process(samples),
+        }
+    }
+
+    /// Zincirde bekleyen son ornekler (kaynak bitti ya da degisti)
+    fn flush(&mut self) -> Result<Vec<f32>, String> {
+        let mut tail = Vec::new();
+        if let Some((to_denoise, denoiser)) = self.denoise.as_mut() {
+            let mut clean = denoiser.process(&to_denoise.flush()?);
+            clean.extend(denoiser.flush());
+            tail = self.to_frames.process(&clean)?;
+        }
+        tail.extend(self.to_frames.flush()?);
+        Ok(tail)
+    }
+}
+
+/// Degisken uzunluklu parcalari sabit boyutlu cercevelere boler
+struct FrameAssembler {
+    pending: Vec<f32>,
+}
+
+impl FrameAssembler {
+    fn new() -> Self {
+        Self { pending: Vec::with_capacity(FRAME_SAMPLES) }
+    }
+
+    fn push(&mut self, mut samples: &[f32], mut emit: impl FnMut(&[f32])) {
+        while !samples.is_empty() {
+            let take = (FRAME_SAMPLES - self.pending.len()).min(samples.len());
+            self.pending.extend_from_slice(&samples[..take]);
+            samples = &samples[take..];
+            if self.pending.len() == FRAME_SAMPLES {
+                emit(&self.pending);
+                self.pending.clear();
+            }
+        }
+    }
+
+    /// Yarim kalan son cerceveyi sessizlikle tamamlayip yayinla
+    fn finish(&mut self, emit: impl FnMut(&[f32])) {
+        if !self.pending.is_empty() {
+            let missing = FRAME_SAMPLES - self.pending.len();
+            self.push(&vec![0.0; missing], emit);
+        }
+    }
+}
+
+fn publish(subscribers: &Mutex<Vec<Box<dyn FrameSubscriber>>>, frame: &[f32]) {
+    subscribers.lock().retain_mut(|s| s.on_frame(frame));
+}
+
+/// Zincirde bekleyen son ornekleri cercevelere ekle (kaynak bitti ya da degisti)
+fn flush_pipeline(pipeline: &mut CapturePipeline, frames: &mut FrameAssembler, subscribers: &Subscribers) {
+    match pipeline.flush() {
+        Ok(tail) => frames.push(&tail, |f| publish(subscribers, f)),
+        Err(e) => log::error!("{}", e),
+    }
+}
+
+/// Halkayi bosaltip (`denoise` ise gurultusunu bastirip) 16kHz'e ceviren ve
+/// cerceveleri abonelere dagitan dongu. Her cihaz acilisinda `sources` uzerinden
+/// yeni bir halka (ve hizi) gelir; eski halka bosalinca yenisine gecilir.
+/// Kayit durdurulup halka bosalinca biter.
+fn run_consumer(
+    sources: mpsc::Receiver<(ring_buffer::Consumer, u32)>,
+    is_recording: Arc<AtomicBool>,
+    subscribers: Subscribers,
+    denoise: bool,
+) {
+    let mut chunk = vec![0.0f32; CONSUMER_CHUNK];
+    let mut frames = FrameAssembler::new();
+    let mut current: Option<(ring_buffer::Consumer, CapturePipeline)> = None;
+    let mut reported_drops = 0;
+
+    loop {
+        if let Some((consumer, pipeline)) = current.as_mut() {
+            let n = consumer.pop(&mut chunk);
+            if n > 0 {
+                match pipeline.process(&chunk[..n]) {
+                    Ok(resampled) => frames.push(&resampled, |f| publish(&subscribers, f)),
+                    Err(e) => log::error!("{}", e),
+                }
+
+                let dropped = consumer.dropped();
+                if dropped != reported_drops {
+                    log::warn!("Ses halkasi doldu, {} ornek atildi", dropped - reported_drops);
+                    reported_drops = dropped;
+                }
+                continue;
+            }
+        }
+
+        // Halka bos — cihaz degistiyse eski zincirin kuyrugunu yayinlayip yeni halkaya gec
+        if let Ok((consumer, rate)) = sources.try_recv() {
+            if let Some((_, mut pipeline)) = current.take() {
+                flush_pipeline(&mut pipeline, &mut frames, &subscribers);
+            }
+            current = CapturePipeline::new(rate, denoise)
+                .inspect_err(|e| log::error!("{}", e))
+                .ok()
+                .map(|pipeline| (consumer, pipeline));
+            reported_drops = 0;
+            continue;
+        }
+        if !is_recording.load(Ordering::SeqCst) {
+            break;
+        }
+        std::thread::sleep(CONSUMER_POLL);
+    }
+
+    if let Some((_, mut pipeline)) = current {
+        flush_pipeline(&mut pipeline, &mut frames, &subscribers);
+    }
+    frames.finish(|f| publish(&subscribers, f));
+    for mut subscriber in subscribers.lock().drain(..) {
+        subscriber.on_stop();
+    }
+}
+
+// ─── Cihaz Kimlikleri ───
+
+/// Cihaz listesi yoklama araligi
+const DEVICE_POLL: Duration = Duration::from_secs(2);
+
+/// Cihaz adlarina kararli kimlik ata. `endpoints` (kimlik, ad) listesinde ayni adli
+/// uc nokta varsa onun kimligi, yoksa ad kullanilir; tekrar eden adlar `#n` alir.
+fn assign_device_ids(names: &[String], endpoints: &[(String, String)]) -> Vec<String> {
+    let mut used = vec![false; endpoints.len()];
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    names
+        .iter()
+        .map(|name| {
+            if let Some(i) = (0..endpoints.len()).find(|&i| !used[i] && endpoints[i].1 == *name) {
+                used[i] = true;
+                return endpoints[i].0.clone();
+            }
+            let count = seen.entry(name.as_str()).or_insert(0);
+            *count += 1;
+            if *count == 1 {
+                name.clone()
+            } else {
+                format!("{}#{}", name, count)
+            }
+        })
+        .collect()
+}
+
+/// Giris uc noktalari (kimlik, ad) — cpal cihaz adlari bunlarla eslenir
+#[cfg(target_os = "windows")]
+fn input_endpoints(_names: &[String]) -> Vec<(String, String)> {
+    crate::wasapi_capture::list_capture_endpoints().unwrap_or_else(|e| {
+        log::warn!("Giris uc noktalari listelenemedi: {}", e);
+        Vec::new()
+    })
+}
+
+/// ALSA adlarindaki `CARD=<kart>` kart numarasi gibi takilma sirasina gore
+/// degisebilir; kimlikte kartin fiziksel yolu (USB portu / PCI yuvasi) kullanilir.
+#[cfg(target_os = "linux")]
+fn input_endpoints(names: &[String]) -> Vec<(String, String)> {
+    names
+        .iter()
+        .filter_map(|name| Some((replace_alsa_card(name, alsa_card_path)?, name.clone())))
+        .collect()
+}
+
+/// macOS'ta cpal yalnizca ad verir; ayni adli cihazlar siraya gore `#n` alir
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+fn input_endpoints(_names: &[String]) -> Vec<(String, String)> {
+    Vec::new()
+}
+
+/// `hw:CARD=PCH,DEV=0` → `hw:CARD@0000:00:1f.3,DEV=0`. Kart bulunamazsa `None`.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn replace_alsa_card(name: &str, lookup: impl Fn(&str) -> Option<String>) -> Option<String> {
+    let start = name.find("CARD=")? + "CARD=".len();
+    let end = name[start..].find(',').map_or(name.len(), |i| start + i);
+    let path = lookup(&name[start..end])?;
+    Some(format!("{}CARD@{}{}", &name[..start - "CARD=".len()], path, &name[end..]))
+}
+
+/// ALSA kart kimliginin sysfs'teki fiziksel aygit adi (ornek: "1-2:1.0", "0000:00:1f.3")
+#[cfg(target_os = "linux")]
+fn alsa_card_path(card_id: &str) -> Option<String> {
+    std::fs::read_dir("/sys/class/sound").ok()?.flatten().find_map(|entry| {
+        let dir = entry.path();
+        let id = std::fs::read_to_string(dir.join("id")).ok()?;
+        if id.trim() != card_id {
+            return None;
+        }
+        let device = std::fs::canonicalize(dir.join("device")).ok()?;
+        Some(device.file_name()?.to_string_lossy().into_owned())
+    })
+}
+
+/// Giris cihazlari ve kararli kimlikleri
+fn input_devices() -> Result<Vec<(AudioDevice, cpal::Device)>, String> {
     let host = cpal::default_host();
+    let default_name = host.default_input_device().and_then(|d| d.name().ok());
+    let devices: Vec<(String, cpal::Device)> = host
+        .input_devices()
+        .map_err(|e| format!("Cihaz listesi alinamadi: {}", e))?
+        .filter_map(|d| Some((d.name().ok()?, d)))
+        .collect();
 
-    let device = if let Some(name) = device_name {
-        host.input_devices()
-            .map_err(|e| format!("Cihaz listesi alinamadi: {}", e))?
-            .find(|d| d.name().map(|n| n == name).unwrap_or(false))
-            .ok_or_else(|| format!("Cihaz bulunamadi: {}", name))?
-    } else {
-        host.default_input_device()
-            .ok_or_else(|| "Varsayilan mikrofon bulunamadi".to_string())?
+    let names: Vec<String> = devices.iter().map(|(name, _)| name.clone()).collect();
+    let ids = assign_device_ids(&names, &input_endpoints(&names));
+    Ok(devices
+        .into_iter()
+        .zip(ids)
+        .map(|((name, device), id)| {
+            let is_default = default_name.as_deref() == Some(name.as_str());
+            (AudioDevice { name, id, is_default }, device)
+        })
+        .collect())
+}
+
+pub fn list_devices() -> Vec<AudioDevice> {
+    match input_devices() {
+        Ok(devices) => devices.into_iter().map(|(info, _)| info).collect(),
+        Err(e) => {
+            log::warn!("{}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Giris cihazlarini `active` acik kaldikca arka planda yoklar; liste degisince
+/// `on_change` cagrilir. Kayit surerken yoklanmaz: ALSA listelemesi her cihazi
+/// acmayi denedigi icin kullanimdaki cihaz listeden dusmus gibi gorunur.
+pub fn watch_devices(
+    active: Arc<AtomicBool>,
+    is_recording: Arc<AtomicBool>,
+    on_change: impl Fn(&[AudioDevice]) + Send + 'static,
+) -> Result<(), String> {
+    std::thread::Builder::new()
+        .name("audio-device-watcher".into())
+        .spawn(move || {
+            let mut known = list_devices();
+            loop {
+                std::thread::sleep(DEVICE_POLL);
+                if !active.load(Ordering::SeqCst) {
+                    break;
+                }
+                if is_recording.load(Ordering::SeqCst) {
+                    continue;
+                }
+                let devices = list_devices();
+                if devices != known {
+                    log::info!("Ses cihazlari degisti ({} giris cihazi)", devices.len());
+                    on_change(&devices);
+                    known = devices;
+                }
+            }
+        })
+        .map(|_| ())
+        .map_err(|e| format!("Cihaz izleme thread'i baslatilamadi: {}", e))
+}
+
+/// Kimligi verilen giris cihazi. Eski ayarlar cihaz adini sakladigi icin ada da bakilir.
+fn find_input_device(device_id: &str) -> Result<Option<(AudioDevice, cpal::Device)>, String> {
+    let mut devices = input_devices()?;
+    let index = devices
+        .iter()
+        .position(|(info, _)| info.id == device_id)
+        .or_else(|| devices.iter().position(|(info, _)| info.name == device_id));
+    Ok(index.map(|i| devices.swap_remove(i)))
+}
+
+fn default_input_device() -> Result<cpal::Device, String> {
+    cpal::default_host()
+        .default_input_device()
+        .ok_or_else(|| "Varsayilan mikrofon bulunamadi".to_string())
+}
+
+fn device_name(device: &cpal::Device) -> String {
+    device.name().unwrap_or_else(|_| "Bilinmeyen cihaz".to_string())
+}
+
+// ─── Giris Yapilandirmasi ───
+
+/// Aralik olarak bildirilen hizlardan listelenen yaygin degerler
+const COMMON_RATES: &[u32] = &[8000, 16000, 22050, 32000, 44100, 48000, 88200, 96000];
+
+/// Ayni hiz/kanal birden fazla formatta sunulursa tercih sirasi (kucuk olan secilir).
+/// `None`: f32'ye cevrilemeyen format.
+fn format_rank(format: cpal::SampleFormat) -> Option<u8> {
+    use cpal::SampleFormat::*;
+    let rank = match format {
+        F32 => 0,
+        I32 => 1,
+        I16 => 2,
+        F64 => 3,
+        I64 => 4,
+        U16 => 5,
+        U32 => 6,
+        U64 => 7,
+        I8 => 8,
+        U8 => 9,
+        _ => return None,
     };
+    Some(rank)
+}
 
-    let config = device
+/// Cihazin destekledigi hiz/kanal secenekleri; her biri icin en iyi ornek formati
+fn config_options(
+    ranges: &[cpal::SupportedStreamConfigRange],
+    default: Option<&cpal::SupportedStreamConfig>,
+) -> Vec<InputConfigOption> {
+    let mut best: BTreeMap<(u16, u32), (u8, cpal::SampleFormat)> = BTreeMap::new();
+    for range in ranges {
+        let Some(rank) = format_rank(range.sample_format()) else {
+            continue;
+        };
+        let (min, max) = (range.min_sample_rate().0, range.max_sample_rate().0);
+        let mut rates: Vec<u32> = COMMON_RATES.iter().copied().filter(|r| (min..=max).contains(r)).collect();
+        if rates.is_empty() {
+            rates.push(max);
+        }
+        for rate in rates {
+            let entry = best.entry((range.channels(), rate)).or_insert((rank, range.sample_format()));
+            if rank < entry.0 {
+                *entry = (rank, range.sample_format());
+            }
+        }
+    }
+
+    best.into_iter()
+        .map(|((channels, sample_rate), (_, format))| InputConfigOption {
+            sample_rate,
+            channels,
+            sample_format: format.to_string(),
+            is_default: default.is_some_and(|d| d.channels() == channels && d.sample_rate().0 == sample_rate),
+        })
+        .collect()
+}
+
+/// Tercihe uyan yapilandirmayi sec; uymuyorsa cihazin varsayilanina dus.
+/// Varsayilanin formati cevrilemiyorsa desteklenen en yakin aralik kullanilir.
+fn choose_config(
+    ranges: &[cpal::SupportedStreamConfigRange],
+    default: cpal::SupportedStreamConfig,
+    preference: Option<InputConfig>,
+) -> Result<cpal::SupportedStreamConfig, String> {
+    let supports = |range: &cpal::SupportedStreamConfigRange, rate: cpal::SampleRate| {
+        range.min_sample_rate() <= rate && rate <= range.max_sample_rate()
+    };
+
+    if let Some(pref) = preference {
+        let rate = cpal::SampleRate(pref.sample_rate);
+        let matched = ranges
+            .iter()
+            .filter(|r| r.channels() == pref.channels && supports(r, rate))
+            .filter_map(|r| format_rank(r.sample_format()).map(|rank| (rank, r)))
+            .min_by_key(|(rank, _)| *rank);
+        match matched {
+            Some((_, range)) => return Ok(range.with_sample_rate(rate)),
+            None => log::warn!(
+                "Secilen yapilandirma desteklenmiyor ({}Hz, {} kanal), varsayilan kullaniliyor",
+                pref.sample_rate,
+                pref.channels
+            ),
+        }
+    }
+
+    if format_rank(default.sample_format()).is_some() {
+        return Ok(default);
+    }
+
+    let rate = default.sample_rate();
+    ranges
+        .iter()
+        .filter_map(|r| format_rank(r.sample_format()).map(|rank| (rank, r)))
+        .min_by_key(|(rank, r)| (r.channels() != default.channels(), !supports(r, rate), *rank))
+        .map(|(_, r)| r.try_with_sample_rate(rate).unwrap_or_else(|| r.with_max_sample_rate()))
+        .ok_or_else(|| format!("Desteklenmeyen ornek formati: {}", default.sample_format()))
+}
+
+fn input_config(
+    device: &cpal::Device,
+    preference: Option<InputConfig>,
+) -> Result<cpal::SupportedStreamConfig, String> {
+    let default = device
         .default_input_config()
         .map_err(|e| format!("Mikrofon yapilandirmasi alinamadi: {}", e))?;
+    let ranges: Vec<_> = match device.supported_input_configs() {
+        Ok(ranges) => ranges.collect(),
+        Err(e) => {
+            log::warn!("Desteklenen yapilandirmalar alinamadi: {}", e);
+            Vec::new()
+        }
+    };
+    choose_config(&ranges, default, preference)
+}
 
-    let sample_rate = config.sample_rate().0;
-    *state.sample_rate.lock() = sample_rate;
-    let channels = config.channels() as usize;
+/// Giris cihazinin secilebilir yapilandirmalari (ayarlar paneli icin)
+pub fn list_input_configs(device_id: Option<&str>) -> Result<Vec<InputConfigOption>, String> {
+    let device = match device_id {
+        Some(id) => find_input_device(id)?
+            .map(|(_, device)| device)
+            .ok_or_else(|| format!("Cihaz bulunamadi: {}", id))?,
+        None => default_input_device()?,
+    };
+    let ranges: Vec<_> = device
+        .supported_input_configs()
+        .map_err(|e| format!("Desteklenen yapilandirmalar alinamadi: {}", e))?
+        .collect();
+    let default = device.default_input_config().ok();
+    Ok(config_options(&ranges, default.as_ref()))
+}
 
-    state.audio_buffer.lock().clear();
-    state.is_recording.store(true, Ordering::SeqCst);
+/// Ic ice (interleaved) cerceveleri f32'ye cevirip kanal ortalamasiyla mono'ya indir
+fn downmix<T>(interleaved: &[T], channels: usize) -> impl ExactSizeIterator<Item = f32> + '_
+where
+    T: cpal::Sample,
+    f32: cpal::FromSample<T>,
+{
+    let channels = channels.max(1);
+    interleaved
+        .chunks_exact(channels)
+        .map(move |frame| frame.iter().map(|&s| s.to_sample::<f32>()).sum::<f32>() / channels as f32)
+}
 
-    let is_recording = state.is_recording.clone();
-    let audio_buffer = state.audio_buffer.clone();
-    let level = state.level.clone();
+/// cpal geri cagiriminda mono'ya indirilmis ornekleri alan hedef
+trait SampleSink: Send + 'static {
+    fn write(&mut self, samples: impl ExactSizeIterator<Item = f32>);
+}
 
-    let stream_config: cpal::StreamConfig = config.into();
+/// Kayit surerken ornekleri kilitsiz halkaya yazar — kilit ve bellek ayirma yok
+struct RingSink {
+    producer: ring_buffer::Producer,
+    is_recording: Arc<AtomicBool>,
+}
 
-    let stream = device
-        .build_input_stream(
-            &stream_config,
-            move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                if !is_recording.load(Ordering::SeqCst) {
-                    return;
-                }
+impl SampleSink for RingSink {
+    fn write(&mut self, samples: impl ExactSizeIterator<Item = f32>) {
+        if self.is_recording.load(Ordering::SeqCst) {
+            self.producer.push(samples);
+        }
+    }
+}
 
-                // Mono'ya donustur (tum kanallarin ortalamasini al)
-                let mono: Vec<f32> = data
-                    .chunks(channels)
-                    .map(|chunk| {
-                        let sum: f32 = chunk.iter().sum();
-                        sum / channels as f32
-                    })
-                    .collect();
+impl SampleSink for Arc<Mutex<Vec<f32>>> {
+    fn write(&mut self, samples: impl ExactSizeIterator<Item = f32>) {
+        self.lock().extend(samples);
+    }
+}
 
-                // Seviye hesapla (RMS)
-                let rms = (mono.iter().map(|s| s * s).sum::<f32>() / mono.len() as f32).sqrt();
-                *level.lock() = rms;
+/// Akis hatalarini ses thread'inden alan geri cagirim
+type ErrorSink = Box<dyn FnMut(cpal::StreamError) + Send + 'static>;
 
-                audio_buffer.lock().extend_from_slice(&mono);
-            },
-            move |err| {
-                log::error!("Ses yakalama hatasi: {}", err);
-            },
-            None,
-        )
-        .map_err(|e| format!("Ses akisi baslatilamadi: {}", e))?;
+fn build_input<T, S>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    mut sink: S,
+    on_error: ErrorSink,
+) -> Result<cpal::Stream, cpal::BuildStreamError>
+where
+    T: cpal::SizedSample,
+    f32: cpal::FromSample<T>,
+    S: SampleSink,
+{
+    let channels = config.channels as usize;
+    device.build_input_stream(
+        config,
+        move |data: &[T], _: &cpal::InputCallbackInfo| sink.write(downmix(data, channels)),
+        on_error,
+        None,
+    )
+}
 
-    stream
-        .play()
-        .map_err(|e| format!("Ses akisi oynatma hatasi: {}", e))?;
+/// Yapilandirmanin ornek formatina gore akisi kur; her format f32'ye cevrilir
+fn open_input_stream<S: SampleSink>(
+    device: &cpal::Device,
+    config: &cpal::SupportedStreamConfig,
+    sink: S,
+    on_error: ErrorSink,
+) -> Result<cpal::Stream, String> {
+    use cpal::SampleFormat;
 
-    log::info!("Kayit basladi - {}Hz, {} kanal", sample_rate, channels);
+    let stream_config = config.config();
+    let stream = match config.sample_format() {
+        SampleFormat::I8 => build_input::<i8, _>(device, &stream_config, sink, on_error),
+        SampleFormat::I16 => build_input::<i16, _>(device, &stream_config, sink, on_error),
+        SampleFormat::I32 => build_input::<i32, _>(device, &stream_config, sink, on_error),
+        SampleFormat::I64 => build_input::<i64, _>(device, &stream_config, sink, on_error),
+        SampleFormat::U8 => build_input::<u8, _>(device, &stream_config, sink, on_error),
+        SampleFormat::U16 => build_input::<u16, _>(device, &stream_config, sink, on_error),
+        SampleFormat::U32 => build_input::<u32, _>(device, &stream_config, sink, on_error),
+        SampleFormat::U64 => build_input::<u64, _>(device, &stream_config, sink, on_error),
+        SampleFormat::F32 => build_input::<f32, _>(device, &stream_config, sink, on_error),
+        SampleFormat::F64 => build_input::<f64, _>(device, &stream_config, sink, on_error),
+        other => return Err(format!("Desteklenmeyen ornek formati: {}", other)),
+    };
+    stream.map_err(|e| format!("Ses akisi baslatilamadi: {}", e))
+}
+
+// ─── Kayit ───
+
+/// Kayit thread'inin akis hatalarini bekleme araligi
+const CAPTURE_POLL: Duration = Duration::from_millis(250);
+/// Ilk hatadan sonra ayni patlamaya ait hatalarin toplandigi sure
+const ERROR_WINDOW: Duration = Duration::from_millis(100);
+/// Bu kadar hata art arda gelirse cihaz kaybolmus sayilir
+const LOST_ERROR_BURST: usize = 10;
 
-    Ok(stream)
+/// Akis hatalarindan cihazin kayboldugunu cikar. WASAPI bunu acikca bildirir;
+/// ALSA ise cikarilan cihaz icin ayni arka uc hatasini durmadan tekrarlar.
+fn is_device_lost(errors: &[cpal::StreamError]) -> bool {
+    errors.iter().any(|e| matches!(e, cpal::StreamError::DeviceNotAvailable)) || errors.len() >= LOST_ERROR_BURST
 }
 
-pub fn stop_recording(state: &SharedAudioState) -> Result<Vec<f32>, String> {
+/// Ilk hatayla ayni anda gelen hatalari topla
+fn collect_errors(errors: &mpsc::Receiver<cpal::StreamError>, first: cpal::StreamError) -> Vec<cpal::StreamError> {
+    let mut batch = vec![first];
+    let deadline = Instant::now() + ERROR_WINDOW;
+    while !is_device_lost(&batch) {
+        let Some(left) = deadline.checked_duration_since(Instant::now()) else {
+            break;
+        };
+        match errors.recv_timeout(left) {
+            Ok(err) => batch.push(err),
+            Err(_) => break,
+        }
+    }
+    batch
+}
+
+/// Mikrofon akisini acan ve kaybolursa varsayilan mikrofona geciren kayit thread'i.
+/// cpal akisi Send olmadigi icin akis bu thread'de acilir ve kapatilir.
+struct Capture {
+    is_recording: Arc<AtomicBool>,
+    /// Her acilista yeni halka tuketiciye buradan verilir
+    sources: mpsc::Sender<(ring_buffer::Consumer, u32)>,
+    sample_rate: Arc<Mutex<u32>>,
+    on_fallback: Option<FallbackSink>,
+    error_tx: mpsc::Sender<cpal::StreamError>,
+}
+
+impl Capture {
+    /// Cihazi yeni bir halkayla ac; akis calisinca halka tuketiciye gecer
+    fn open(&self, device: &cpal::Device, preference: Option<InputConfig>) -> Result<cpal::Stream, String> {
+        let config = input_config(device, preference)?;
+        let sample_rate = config.sample_rate().0;
+        let (producer, consumer) = ring_buffer::channel(sample_rate as usize * RING_SECONDS);
+        let sink = RingSink { producer, is_recording: self.is_recording.clone() };
+        let error_tx = self.error_tx.clone();
+        let stream = open_input_stream(device, &config, sink, Box::new(move |err| {
+            error_tx.send(err).ok();
+        }))?;
+        stream
+            .play()
+            .map_err(|e| format!("Ses akisi oynatma hatasi: {}", e))?;
+
+        *self.sample_rate.lock() = sample_rate;
+        self.sources.send((consumer, sample_rate)).ok();
+        log::info!(
+            "Kayit basladi - {} - {}Hz, {} kanal, {}",
+            device_name(device),
+            sample_rate,
+            config.channels(),
+            config.sample_format()
+        );
+        Ok(stream)
+    }
+
+    /// Varsayilan mikrofonu ac ve gecisi bildir
+    fn fall_back(&self, lost: &str) -> Result<(cpal::Stream, String), String> {
+        let device = default_input_device()?;
+        let stream = self.open(&device, None)?;
+        let name = device_name(&device);
+        log::warn!("Mikrofon bulunamadi ({}), varsayilan mikrofona gecildi: {}", lost, name);
+        if let Some(sink) = &self.on_fallback {
+            sink(DeviceFallback { lost: lost.to_string(), fallback: name.clone() });
+        }
+        Ok((stream, name))
+    }
+
+    fn run(
+        self,
+        errors: mpsc::Receiver<cpal::StreamError>,
+        device_id: Option<String>,
+        preference: Option<InputConfig>,
+        ready: mpsc::Sender<Result<(), String>>,
+    ) {
+        // Secilen cihaz cikarilmis ya da yeniden adlandirilmis olabilir
+        let opened = match device_id.as_deref() {
+            Some(id) => match find_input_device(id) {
+                Ok(Some((info, device))) => self.open(&device, preference).map(|stream| (stream, info.name)),
+                Ok(None) => self.fall_back(id),
+                Err(e) => Err(e),
+            },
+            None => default_input_device()
+                .and_then(|device| Ok((self.open(&device, preference)?, device_name(&device)))),
+        };
+        let (stream, mut current) = match opened {
+            Ok(opened) => opened,
+            Err(e) => {
+                ready.send(Err(e)).ok();
+                return;
+            }
+        };
+        ready.send(Ok(())).ok();
+
+        let mut stream = Some(stream);
+        let mut retry_logged = false;
+        while self.is_recording.load(Ordering::SeqCst) {
+            if stream.is_some() {
+                let Ok(first) = errors.recv_timeout(CAPTURE_POLL) else {
+                    continue;
+                };
+                let batch = collect_errors(&errors, first);
+                if !is_device_lost(&batch) {
+                    log::error!("Ses yakalama hatasi: {} ({} hata)", batch[0], batch.len());
+                    continue;
+                }
+                log::warn!("Mikrofon kayboldu: {} — {}", current, batch[0]);
+                // Olu akisi kapat; ondan kalan hatalar yeni akisi tetiklemesin
+                stream = None;
+                errors.try_iter().for_each(drop);
+            }
+
+            match self.fall_back(&current) {
+                Ok((fallback, name)) => {
+                    stream = Some(fallback);
+                    current = name;
+                    retry_logged = false;
+                }
+                Err(e) => {
+                    if !retry_logged {
+                        log::warn!("Varsayilan mikrofon acilamadi, tekrar denenecek: {}", e);
+                        retry_logged = true;
+                    }
+                    std::thread::sleep(CAPTURE_POLL);
+                }
+            }
+        }
+    }
+}
+
+/// Kayit ve tuketici thread'lerinin bitmesini bekle
+fn join_recording_threads(state: &SharedAudioState) {
     state.is_recording.store(false, Ordering::SeqCst);
+    if let Some(capture) = state.capture.lock().take() {
+        capture.join().ok();
+    }
+    if let Some(consumer) = state.consumer.lock().take() {
+        consumer.join().ok();
+    }
+}
 
-    let raw_audio = {
-        let mut buf = state.audio_buffer.lock();
-        std::mem::take(&mut *buf)
+/// Kaydi baslat. Kayit thread'i ornekleri kilitsiz halkaya yazan akisi acar; tuketici
+/// thread'i gurultuyu bastirip 16kHz'e cevirir ve sabit cerceveleri abonelere (seviye, kayit, `vad` verilirse VAD) dagitir.
+/// `preference` verilirse cihaz o hiz/kanalda acilir; desteklenmiyorsa varsayilana dusulur.
+/// Secilen cihaz yoksa ya da kayit sirasinda kaybolursa varsayilan mikrofona gecilir
+/// ve `on_fallback` cagrilir.
+pub fn start_recording(
+    state: &SharedAudioState,
+    device_id: Option<String>,
+    preference: Option<InputConfig>,
+    vad: Option<(VadConfig, VadEventSink)>,
+    on_fallback: Option<FallbackSink>,
+) -> Result<(), String> {
+    // Onceki kaydin thread'leri bitmeden yeni abonelikler eklenmesin
+    join_recording_threads(state);
+    *state.recorder_error.lock() = None;
+    *state.vad_status.lock() = VadStatus::default();
+    let recorder = Recorder::create(&recording_path(), state.recorder_error.clone())?;
+    {
+        let mut subscribers = state.subscribers.lock();
+        subscribers.clear();
+        subscribers.push(Box::new(LevelMeter(state.level.clone())));
+        subscribers.push(Box::new(recorder));
+        if let Some((config, sink)) = vad {
+            subscribers.push(Box::new(VadSubscriber {
+                vad: Vad::new(config, FRAME_RATE),
+                sink,
+                status: state.vad_status.clone(),
+            }));
+        }
+    }
+
+    state.is_recording.store(true, Ordering::SeqCst);
+    let (sources_tx, sources) = mpsc::channel();
+    let (consumer_flag, subscribers) = (state.is_recording.clone(), state.subscribers.clone());
+    let consumer = std::thread::Builder::new()
+        .name("audio-consumer".into())
+        .spawn(move || run_consumer(sources, consumer_flag, subscribers, true))
+        .map_err(|e| {
+            state.is_recording.store(false, Ordering::SeqCst);
+            format!("Ses tuketici thread'i baslatilamadi: {}", e)
+        })?;
+    *state.consumer.lock() = Some(consumer);
+
+    let (error_tx, errors) = mpsc::channel();
+    let capture = Capture {
+        is_recording: state.is_recording.clone(),
+        sources: sources_tx,
+        sample_rate: state.sample_rate.clone(),
+        on_fallback,
+        error_tx,
     };
+    // Akis kayit thread'inde acilir; acilis sonucu kanal ile doner
+    let (ready_tx, ready) = mpsc::channel();
+    let spawned = std::thread::Builder::new()
+        .name("audio-capture".into())
+        .spawn(move || capture.run(errors, device_id, preference, ready_tx));
+    match spawned {
+        Ok(handle) => *state.capture.lock() = Some(handle),
+        Err(e) => {
+            join_recording_threads(state);
+            return Err(format!("Kayit thread'i baslatilamadi: {}", e));
+        }
+    }
 
-    if raw_audio.is_empty() {
-        return Err("Ses verisi bos".to_string());
+    match ready.recv() {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(e)) => {
+            join_recording_threads(state);
+            Err(e)
+        }
+        Err(_) => {
+            join_recording_threads(state);
+            Err("Kayit thread'i beklenmedik sekilde sonlandi".to_string())
+        }
     }
+}
 
-    let sample_rate = *state.sample_rate.lock();
+/// Kaydi durdur, akisi kapatip tuketicinin halkayi bosaltmasini bekle ve
+/// diske yazilan sesi dondur
+pub fn stop_recording(state: &SharedAudioState) -> Result<Vec<f32>, String> {
+    join_recording_threads(state);
 
-    // Gurultu bastirma: 48kHz'e resample → nnnoiseless denoise → 16kHz'e resample
-    let resampled = denoise_and_resample(&raw_audio, sample_rate)?;
+    // Gurultu bastirma tuketicide, cihaz hizinda uygulandi
+    let path = recording_path();
+    let recorded = read_recording(&path);
+    std::fs::remove_file(&path).ok();
+    if let Some(e) = state.recorder_error.lock().take() {
+        return Err(e);
+    }
+    let recorded = recorded?;
+    if recorded.is_empty() {
+        return Err("Ses verisi bos".to_string());
+    }
 
     log::info!(
         "Kayit durduruldu - {} ornek ({:.1}s)",
-        resampled.len(),
-        resampled.len() as f64 / 16000.0
+        recorded.len(),
+        recorded.len() as f64 / 16000.0
     );
 
-    Ok(resampled)
+    Ok(recorded)
 }
 
 pub fn get_level(state: &SharedAudioState) -> f32 {
     *state.level.lock()
 }
 
-/// Ses verisini nnnoiseless (RNNoise) ile denoise edip 16kHz'e resample et.
-/// RNNoise 48kHz gerektirir: orijinal→48kHz→denoise→16kHz
-fn denoise_and_resample(audio: &[f32], sample_rate: u32) -> Result<Vec<f32>, String> {
-    // Adim 1: 48kHz'e resample (nnnoiseless icin)
-    let audio_48k = if sample_rate == 48000 {
-        audio.to_vec()
+pub fn get_vad_status(state: &SharedAudioState) -> VadStatus {
+    *state.vad_status.lock()
+}
+
+/// Ses dosyasini coz, mono'ya indir ve Whisper icin 16kHz'e resample et.
+/// Gurultu bastirma uygulanmaz — dosyalar genelde mikrofondan daha temiz.
+pub fn load_audio_file(path: &Path) -> Result<Vec<f32>, String> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    if !SUPPORTED_AUDIO_EXTENSIONS.contains(&ext.as_str()) {
+        return Err(format!(
+            "Desteklenmeyen ses formati: {}. Desteklenenler: {}",
+            ext,
+            SUPPORTED_AUDIO_EXTENSIONS.join(", ")
+        ));
+    }
+
+    let (mono, sample_rate) = decode_audio_file(path, &ext)?;
+    if mono.is_empty() {
+        return Err("Ses dosyasi bos".to_string());
+    }
+
+    log::info!(
+        "Ses dosyasi cozuldu: {:?} ({}Hz, {:.1}s)",
+        path,
+        sample_rate,
+        mono.len() as f64 / sample_rate as f64
+    );
+
+    if sample_rate == 16000 {
+        Ok(mono)
     } else {
-        resample_audio(audio, sample_rate, 48000)?
-    };
+        resample_audio(&mono, sample_rate, 16000)
+    }
+}
 
-    // Adim 2: nnnoiseless denoise (48kHz, frame_size=480)
-    // Wet/dry mix: %60 denoised + %40 orijinal — Turkce fricatifleri korumak icin
-    // (RNNoise Ingilizce ses uzerinde egitilmis, Turkce ş/ç/ğ seslerini bosabilir)
-    let denoised = {
-        use nnnoiseless::DenoiseState;
-        let mut state = Box::new(DenoiseState::new());
-        let mut clean = Vec::with_capacity(audio_48k.len());
-        let mut out_buf = [0.0f32; DenoiseState::FRAME_SIZE];
+/// Symphonia ile dosyayi coz — (mono ornekler, orijinal ornekleme hizi)
+fn decode_audio_file(path: &Path, ext: &str) -> Result<(Vec<f32>, u32), String> {
+    use symphonia::core::audio::SampleBuffer;
+    use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+    use symphonia::core::errors::Error as SymphoniaError;
+    use symphonia::core::formats::FormatOptions;
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::meta::MetadataOptions;
+    use symphonia::core::probe::Hint;
 
-        for chunk in audio_48k.chunks(DenoiseState::FRAME_SIZE) {
-            let mut frame = [0.0f32; DenoiseState::FRAME_SIZE];
-            frame[..chunk.len()].copy_from_slice(chunk);
-            state.process_frame(&mut out_buf, &frame);
-            // Wet/dry mix: orijinal sesi koruyarak denoise
-            for j in 0..chunk.len() {
-                clean.push(out_buf[j] * 0.6 + chunk[j] * 0.4);
+    let file = std::fs::File::open(path).map_err(|e| format!("Ses dosyasi acilamadi: {}", e))?;
+    let mss = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let mut hint = Hint::new();
+    hint.with_extension(ext);
+
+    let probed = symphonia::default::get_probe()
+        .format(&hint, mss, &FormatOptions::default(), &MetadataOptions::default())
+        .map_err(|e| format!("Ses formati taninamadi: {}", e))?;
+    let mut format = probed.format;
+
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| "Dosyada ses parcasi bulunamadi".to_string())?;
+    let track_id = track.id;
+    let mut sample_rate = track.codec_params.sample_rate.unwrap_or(0);
+
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| format!("Ses codec'i desteklenmiyor: {}", e))?;
+
+    let mut mono = Vec::new();
+    let mut sample_buf: Option<SampleBuffer<f32>> = None;
+
+    loop {
+        let packet = match format.next_packet() {
+            Ok(p) => p,
+            // Dosya sonu
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+            Err(SymphoniaError::ResetRequired) => break,
+            Err(e) => return Err(format!("Ses dosyasi okunamadi: {}", e)),
+        };
+
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = match decoder.decode(&packet) {
+            Ok(d) => d,
+            // Bozuk paketleri atla, kalan sesi cozmeye devam et
+            Err(SymphoniaError::DecodeError(e)) => {
+                log::warn!("Bozuk ses paketi atlandi: {}", e);
+                continue;
             }
+            Err(e) => return Err(format!("Ses cozme hatasi: {}", e)),
+        };
+
+        let spec = *decoded.spec();
+        if sample_rate == 0 {
+            sample_rate = spec.rate;
         }
-        clean
-    };
-    log::info!("Gurultu bastirma uygulandi ({} sample @ 48kHz, mix 60/40)", denoised.len());
+        let channels = spec.channels.count().max(1);
+
+        // Tampon yetersizse (ilk paket veya daha buyuk paket) yeniden olustur
+        let needs_alloc = sample_buf
+            .as_ref()
+            .is_none_or(|b| b.capacity() < decoded.capacity() * channels);
+        if needs_alloc {
+            sample_buf = Some(SampleBuffer::<f32>::new(decoded.capacity() as u64, spec));
+        }
+        let buf = sample_buf.as_mut().unwrap();
+        buf.copy_interleaved_ref(decoded);
+
+        // Mono'ya donustur (tum kanallarin ortalamasini al)
+        mono.extend(
+            buf.samples()
+                .chunks(channels)
+                .map(|frame| frame.iter().sum::<f32>() / channels as f32),
+        );
+    }
+
+    if sample_rate == 0 {
+        return Err("Ses dosyasinin ornekleme hizi okunamadi".to_string());
+    }
 
-    // Adim 3: 16kHz'e resample (Whisper icin)
-    resample_audio(&denoised, 48000, 16000)
+    Ok((mono, sample_rate))
 }
 
-fn resample_audio(input: &[f32], from_rate: u32, to_rate: u32) -> Result<Vec<f32>, String> {
+const RESAMPLE_CHUNK_SIZE: usize = 1024;
+
+fn new_resampler(from_rate: u32, to_rate: u32) -> Result<SincFixedIn<f32>, String> {
     let params = SincInterpolationParameters {
         sinc_len: 256,
         f_cutoff: 0.90,
@@ -194,16 +1181,71 @@ fn resample_audio(input: &[f32], from_rate: u32, to_rate: u32) -> Result<Vec<f32
     };
 
     let ratio = to_rate as f64 / from_rate as f64;
-    let chunk_size = 1024;
 
-    let mut resampler = SincFixedIn::<f32>::new(
+    SincFixedIn::<f32>::new(
         ratio,
         2.0,
         params,
-        chunk_size,
+        RESAMPLE_CHUNK_SIZE,
         1, // mono
     )
-    .map_err(|e| format!("Resampler olusturulamadi: {}", e))?;
+    .map_err(|e| format!("Resampler olusturulamadi: {}", e))
+}
+
+/// Parca parca gelen sesi kesintisiz resample eder (canli transkripsiyon icin).
+/// Filtre durumu cagrilar arasinda korunur; dolmayan parca sonraki cagriya kalir.
+pub struct StreamResampler {
+    resampler: Option<SincFixedIn<f32>>,
+    pending: Vec<f32>,
+    ratio: f64,
+}
+
+impl StreamResampler {
+    pub fn new(from_rate: u32, to_rate: u32) -> Result<Self, String> {
+        let resampler = if from_rate == to_rate {
+            None
+        } else {
+            Some(new_resampler(from_rate, to_rate)?)
+        };
+        Ok(Self { resampler, pending: Vec::new(), ratio: to_rate as f64 / from_rate as f64 })
+    }
+
+    /// Bekleyen son parcayi sessizlikle tamamlayip cevir (akis sonu)
+    pub fn flush(&mut self) -> Result<Vec<f32>, String> {
+        if self.pending.is_empty() || self.resampler.is_none() {
+            return Ok(std::mem::take(&mut self.pending));
+        }
+        let expected = (self.pending.len() as f64 * self.ratio).round() as usize;
+        let padding = RESAMPLE_CHUNK_SIZE - self.pending.len();
+        let mut output = self.process(&vec![0.0; padding])?;
+        output.truncate(expected);
+        Ok(output)
+    }
+
+    pub fn process(&mut self, input: &[f32]) -> Result<Vec<f32>, String> {
+        let Some(resampler) = self.resampler.as_mut() else {
+            return Ok(input.to_vec());
+        };
+
+        self.pending.extend_from_slice(input);
+        let full = self.pending.len() / RESAMPLE_CHUNK_SIZE * RESAMPLE_CHUNK_SIZE;
+        let mut output = Vec::new();
+        for chunk in self.pending[..full].chunks(RESAMPLE_CHUNK_SIZE) {
+            let result = resampler
+                .process(&[chunk], None)
+                .map_err(|e| format!("Resample hatasi: {}", e))?;
+            if !result.is_empty() {
+                output.extend_from_slice(&result[0]);
+            }
+        }
+        self.pending.drain(..full);
+        Ok(output)
+    }
+}
+
+fn resample_audio(input: &[f32], from_rate: u32, to_rate: u32) -> Result<Vec<f32>, String> {
+    let chunk_size = RESAMPLE_CHUNK_SIZE;
+    let mut resampler = new_resampler(from_rate, to_rate)?;
 
     let mut output = Vec::new();
 
@@ -225,5 +1267,534 @@ fn resample_audio(input: &[f32], from_rate: u32, to_rate: u32) -> Result<Vec<f32
         }
     }
 
+    // Son parcanin sifir dolgusu ciktiya sure eklemesin
+    output.truncate((input.len() as u64 * to_rate as u64 / from_rate as u64) as usize);
     Ok(output)
 }
+
+// ─── Yakalama Kaynaklari ───
+
+/// Dosya kaynagindan okunan parca (100ms @ 16kHz)
+const FILE_CHUNK_SAMPLES: usize = 1600;
+
+/// Mono ses ureten yakalama kaynagi — mikrofon, sistem sesi (loopback) veya dosya.
+/// Ornekler kaynagin kendi hizindadir; tuketici gerekirse `StreamResampler` ile ceker.
+pub trait CaptureSource {
+    fn sample_rate(&self) -> u32;
+    fn name(&self) -> String;
+    /// Biriken ornekler (bos olabilir); kaynak bittiyse `None`
+    fn read(&mut self) -> Result<Option<Vec<f32>>, String>;
+}
+
+/// Sistem sesi kaynagi: Windows'ta cikis cihazi, Linux'ta monitor kaynagi
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LoopbackDevice {
+    pub id: String,
+    pub name: String,
+    pub is_default: bool,
+}
+
+/// cpal mikrofon akisi
+pub struct CpalSource {
+    _stream: cpal::Stream,
+    buffer: Arc<Mutex<Vec<f32>>>,
+    sample_rate: u32,
+    name: String,
+}
+
+impl CpalSource {
+    /// Kimligi (ya da adi) verilen giris cihazini ac; bulunamazsa varsayilan mikrofona duser
+    pub fn open_input(device_id: Option<&str>, preference: Option<InputConfig>) -> Result<Self, String> {
+        let selected = match device_id {
+            Some(id) => find_input_device(id)?.map(|(_, device)| device),
+            None => None,
+        };
+        match selected {
+            Some(device) => Self::open(device, preference),
+            None => Self::open(default_input_device()?, None),
+        }
+    }
+
+    fn open(device: cpal::Device, preference: Option<InputConfig>) -> Result<Self, String> {
+        let name = device_name(&device);
+        let config = input_config(&device, preference)?;
+
+        let sample_rate = config.sample_rate().0;
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let on_error: ErrorSink = Box::new(|err| log::error!("Ses yakalama hatasi: {}", err));
+        let stream = open_input_stream(&device, &config, buffer.clone(), on_error)?;
+        stream
+            .play()
+            .map_err(|e| format!("Ses akisi oynatma hatasi: {}", e))?;
+
+        Ok(Self { _stream: stream, buffer, sample_rate, name })
+    }
+}
+
+impl CaptureSource for CpalSource {
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn read(&mut self) -> Result<Option<Vec<f32>>, String> {
+        Ok(Some(std::mem::take(&mut *self.buffer.lock())))
+    }
+}
+
+/// PulseAudio/PipeWire monitor kaynagi — `parec` ham float32 akisini okur
+#[cfg(target_os = "linux")]
+pub struct MonitorSource {
+    child: std::process::Child,
+    buffer: Arc<Mutex<Vec<f32>>>,
+    ended: Arc<AtomicBool>,
+    name: String,
+}
+
+#[cfg(target_os = "linux")]
+impl MonitorSource {
+    pub fn open(monitor: &str) -> Result<Self, String> {
+        use std::io::Read;
+
+        let mut child = std::process::Command::new("parec")
+            .args([
+                "--device",
+                monitor,
+                "--format=float32le",
+                "--rate=16000",
+                "--channels=1",
+                "--raw",
+                "--latency-msec=100",
+            ])
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::null())
+            .spawn()
+            .map_err(|e| format!("parec baslatilamadi (pulseaudio-utils kurulu mu?): {}", e))?;
+        let mut stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| "parec cikisi okunamadi".to_string())?;
+
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let ended = Arc::new(AtomicBool::new(false));
+        let (sink, done) = (buffer.clone(), ended.clone());
+        std::thread::spawn(move || {
+            let mut bytes = [0u8; 4096];
+            let mut carry = Vec::new();
+            while let Ok(n) = stdout.read(&mut bytes) {
+                if n == 0 {
+                    break;
+                }
+                carry.extend_from_slice(&bytes[..n]);
+                let whole = carry.len() / 4 * 4;
+                sink.lock().extend(
+                    carry[..whole]
+                        .chunks_exact(4)
+                        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+                );
+                carry.drain(..whole);
+            }
+            done.store(true, Ordering::SeqCst);
+        });
+
+        Ok(Self { child, buffer, ended, name: monitor.to_string() })
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl CaptureSource for MonitorSource {
+    fn sample_rate(&self) -> u32 {
+        16000
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn read(&mut self) -> Result<Option<Vec<f32>>, String> {
+        let samples = std::mem::take(&mut *self.buffer.lock());
+        if samples.is_empty() && self.ended.load(Ordering::SeqCst) {
+            return Err("Sistem sesi akisi kesildi".to_string());
+        }
+        Ok(Some(samples))
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for MonitorSource {
+    fn drop(&mut self) {
+        self.child.kill().ok();
+        self.child.wait().ok();
+    }
+}
+
+/// Kayitli dosyayi (WAV ya da desteklenen diger formatlar) tekrar oynatan kaynak.
+/// 16kHz'de 100ms'lik parcalarla okunur; `realtime` acikken parcalar gercek
+/// zamanli hizda verilir, kapaliyken testler icin bekleme yapilmaz.
+pub struct FileSource {
+    samples: Vec<f32>,
+    position: usize,
+    realtime: bool,
+    name: String,
+}
+
+impl FileSource {
+    pub fn open(path: &Path, realtime: bool) -> Result<Self, String> {
+        Ok(Self::from_samples(load_audio_file(path)?, realtime, path.display().to_string()))
+    }
+
+    /// Bellekteki 16kHz ornekleri kaynak olarak kullan
+    pub fn from_samples(samples: Vec<f32>, realtime: bool, name: String) -> Self {
+        Self { samples, position: 0, realtime, name }
+    }
+}
+
+impl CaptureSource for FileSource {
+    fn sample_rate(&self) -> u32 {
+        16000
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn read(&mut self) -> Result<Option<Vec<f32>>, String> {
+        if self.position >= self.samples.len() {
+            return Ok(None);
+        }
+        if self.realtime {
+            std::thread::sleep(std::time::Duration::from_millis((FILE_CHUNK_SAMPLES * 1000 / 16000) as u64));
+        }
+        let end = (self.position + FILE_CHUNK_SAMPLES).min(self.samples.len());
+        let chunk = self.samples[self.position..end].to_vec();
+        self.position = end;
+        Ok(Some(chunk))
+    }
+}
+
+/// Kimligi verilen sistem sesi kaynagini ac (`loopback_devices` kimlikleri)
+#[cfg(target_os = "linux")]
+pub fn open_loopback(id: &str) -> Result<Box<dyn CaptureSource>, String> {
+    Ok(Box::new(MonitorSource::open(id)?))
+}
+
+#[cfg(target_os = "windows")]
+pub fn open_loopback(id: &str) -> Result<Box<dyn CaptureSource>, String> {
+    Ok(Box::new(crate::wasapi_capture::WasapiLoopback::open(Some(id))?))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+pub fn open_loopback(_id: &str) -> Result<Box<dyn CaptureSource>, String> {
+    Err("Sistem sesi yakalama bu platformda desteklenmiyor".to_string())
+}
+
+/// `pactl list short sources` ciktisindan monitor kaynaklari
+fn parse_pactl_sources(output: &str, default_sink: &str) -> Vec<LoopbackDevice> {
+    let default_monitor = format!("{}.monitor", default_sink.trim());
+    output
+        .lines()
+        .filter_map(|line| line.split('\t').nth(1))
+        .filter(|name| name.ends_with(".monitor"))
+        .map(|name| LoopbackDevice {
+            id: name.to_string(),
+            name: format!("{} (monitor)", name.trim_end_matches(".monitor")),
+            is_default: name == default_monitor,
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+pub fn loopback_devices() -> Vec<LoopbackDevice> {
+    let run = |args: &[&str]| {
+        std::process::Command::new("pactl")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+    };
+    let Some(sources) = run(&["list", "short", "sources"]) else {
+        log::warn!("pactl calistirilamadi — monitor kaynaklari listelenemedi");
+        return Vec::new();
+    };
+    // Eski pactl surumlerinde get-default-sink yok; `pactl info` satirina dus
+    let default_sink = run(&["get-default-sink"])
+        .or_else(|| {
+            run(&["info"]).and_then(|info| {
+                info.lines()
+                    .find_map(|l| l.strip_prefix("Default Sink:").map(|s| s.trim().to_string()))
+            })
+        })
+        .unwrap_or_default();
+    parse_pactl_sources(&sources, &default_sink)
+}
+
+#[cfg(target_os = "windows")]
+pub fn loopback_devices() -> Vec<LoopbackDevice> {
+    crate::wasapi_capture::list_render_devices().unwrap_or_else(|e| {
+        log::warn!("Cikis cihazlari listelenemedi: {}", e);
+        Vec::new()
+    })
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+pub fn loopback_devices() -> Vec<LoopbackDevice> {
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pactl_sources() {
+        let output = "0\talsa_output.pci.analog-stereo.monitor\tPipeWire\tfloat32le 2ch 48000Hz\tSUSPENDED\n\
+                      1\talsa_input.pci.analog-stereo\tPipeWire\ts16le 2ch 48000Hz\tRUNNING\n\
+                      2\tbluez_output.headset.monitor\tPipeWire\tfloat32le 2ch 48000Hz\tIDLE\n";
+        let devices = parse_pactl_sources(output, "bluez_output.headset\n");
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].id, "alsa_output.pci.analog-stereo.monitor");
+        assert!(!devices[0].is_default);
+        assert!(devices[1].is_default);
+        assert_eq!(devices[1].name, "bluez_output.headset (monitor)");
+    }
+
+    #[test]
+    fn test_frame_assembler() {
+        let mut frames = FrameAssembler::new();
+        let mut emitted = Vec::new();
+        frames.push(&[0.5; 700], |f| emitted.push(f.to_vec()));
+        frames.push(&[0.5; 300], |f| emitted.push(f.to_vec()));
+        assert_eq!(emitted.len(), 2);
+        frames.finish(|f| emitted.push(f.to_vec()));
+        assert_eq!(emitted.len(), 3);
+        assert!(emitted.iter().all(|f| f.len() == FRAME_SAMPLES));
+        // 1000 ornek + 440 sessizlik
+        assert_eq!(emitted[2][39], 0.5);
+        assert_eq!(emitted[2][40], 0.0);
+    }
+
+    #[test]
+    fn test_consumer_publishes_16k_frames() {
+        let (mut producer, consumer) = ring_buffer::channel(48000);
+        let recording = Arc::new(AtomicBool::new(true));
+        let subscribers: Subscribers = Arc::new(Mutex::new(Vec::new()));
+        let (tx, frames) = mpsc::channel();
+        subscribers.lock().push(Box::new(tx));
+
+        let (sources_tx, sources) = mpsc::channel();
+        sources_tx.send((consumer, 48000)).unwrap();
+        let (flag, subs) = (recording.clone(), subscribers.clone());
+        let worker = std::thread::spawn(move || run_consumer(sources, flag, subs, false));
+
+        // 1 saniye 48kHz stereo, 10ms'lik geri cagirimlar gibi
+        let stereo: Vec<f32> = (0..960).map(|i| if i % 2 == 0 { 0.25 } else { 0.75 }).collect();
+        for _ in 0..100 {
+            while producer.push(downmix(&stereo, 2)) == 0 {
+                std::thread::yield_now();
+            }
+        }
+        recording.store(false, Ordering::SeqCst);
+        worker.join().unwrap();
+
+        let received: Vec<Vec<f32>> = frames.iter().collect();
+        assert!(received.iter().all(|f| f.len() == FRAME_SAMPLES));
+        let total = received.len() * FRAME_SAMPLES;
+        assert!((16000..16000 + FRAME_SAMPLES).contains(&total), "{}", total);
+        // Kanal ortalamasi 0.5; resampler gecikmesinden sonra sabit seviye
+        assert!((received[10][0] - 0.5).abs() < 0.01);
+        assert!(subscribers.lock().is_empty());
+    }
+
+    #[test]
+    fn test_consumer_switches_to_fallback_source() {
+        let recording = Arc::new(AtomicBool::new(true));
+        let subscribers: Subscribers = Arc::new(Mutex::new(Vec::new()));
+        let (tx, frames) = mpsc::channel();
+        subscribers.lock().push(Box::new(tx));
+
+        // 0.5 s 48kHz cihaz kaybolur, yerine 0.5 s 16kHz varsayilan mikrofon gelir
+        let (mut lost, lost_consumer) = ring_buffer::channel(48000);
+        let (mut fallback, fallback_consumer) = ring_buffer::channel(16000);
+        lost.push(vec![0.25; 24000].into_iter());
+        fallback.push(vec![0.75; 8000].into_iter());
+        let (sources_tx, sources) = mpsc::channel();
+        sources_tx.send((lost_consumer, 48000)).unwrap();
+        sources_tx.send((fallback_consumer, 16000)).unwrap();
+
+        recording.store(false, Ordering::SeqCst);
+        run_consumer(sources, recording, subscribers, false);
+
+        let samples: Vec<f32> = frames.iter().flatten().collect();
+        assert!((16000..16000 + FRAME_SAMPLES).contains(&samples.len()), "{}", samples.len());
+        // Eski kaynagin kuyrugu yenisinden once yayinlanir
+        assert!((samples[4000] - 0.25).abs() < 0.01);
+        assert_eq!(samples[12000], 0.75);
+    }
+
+    #[test]
+    fn test_denoised_pipeline_keeps_duration() {
+        // 44.1kHz cihaz: once 48kHz'e cikar, RNNoise, sonra 16kHz
+        let mut pipeline = CapturePipeline::new(44100, true).unwrap();
+        let mut out = Vec::new();
+        for _ in 0..10 {
+            out.extend(pipeline.process(&[0.1; 4410]).unwrap());
+        }
+        out.extend(pipeline.flush().unwrap());
+        assert!((15900..=16100).contains(&out.len()), "{}", out.len());
+        assert!(out.iter().all(|s| s.is_finite()));
+    }
+
+    #[test]
+    fn test_recorder_streams_to_disk() {
+        let path = std::env::temp_dir().join(format!("kayit-test-{}.wav", std::process::id()));
+        let error = Arc::new(Mutex::new(None));
+        let mut recorder = Recorder::create(&path, error.clone()).unwrap();
+        // Bellekte tutulmadigi icin uzunluk siniri yok — tum cerceveler geri okunur
+        for i in 0..100 {
+            assert!(recorder.on_frame(&[i as f32 / 100.0; FRAME_SAMPLES]));
+        }
+        recorder.on_stop();
+
+        let recorded = read_recording(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert!(error.lock().is_none());
+        assert_eq!(recorded.len(), FRAME_SAMPLES * 100);
+        assert_eq!(recorded[FRAME_SAMPLES * 42], 0.42);
+    }
+
+    #[test]
+    fn test_load_audio_file_downmixes_and_resamples() {
+        let path = std::env::temp_dir().join(format!("dosya-test-{}.wav", std::process::id()));
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: 44100,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+        for i in 0..44100 {
+            let phase = 2.0 * std::f32::consts::PI * 440.0 * i as f32 / 44100.0;
+            let sample = (0.3 * phase.sin() * i16::MAX as f32) as i16;
+            writer.write_sample(sample).unwrap();
+            writer.write_sample(sample).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let audio = load_audio_file(&path);
+        std::fs::remove_file(&path).ok();
+        // 1 saniye stereo 44.1kHz → 1 saniye mono 16kHz
+        let audio = audio.unwrap();
+        assert!((15900..=16100).contains(&audio.len()), "{}", audio.len());
+        assert!(audio.iter().all(|s| s.is_finite() && s.abs() <= 1.0));
+    }
+
+    #[test]
+    fn test_load_audio_file_rejects_unsupported_extension() {
+        let err = load_audio_file(Path::new("kayit.txt")).unwrap_err();
+        assert!(err.contains("Desteklenmeyen ses formati: txt"), "{}", err);
+    }
+
+    #[test]
+    fn test_assign_device_ids() {
+        let names: Vec<String> = ["Mikrofon (USB)", "Mikrofon (USB)", "pipewire", "pipewire"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let endpoints = vec![("{0.0.1}.{a}".to_string(), "Mikrofon (USB)".to_string())];
+        assert_eq!(
+            assign_device_ids(&names, &endpoints),
+            vec!["{0.0.1}.{a}", "Mikrofon (USB)", "pipewire", "pipewire#2"]
+        );
+    }
+
+    #[test]
+    fn test_alsa_card_replaced_with_physical_path() {
+        let lookup = |card: &str| (card == "Device").then(|| "1-2:1.0".to_string());
+        assert_eq!(
+            replace_alsa_card("hw:CARD=Device,DEV=0", lookup).as_deref(),
+            Some("hw:CARD@1-2:1.0,DEV=0")
+        );
+        assert_eq!(
+            replace_alsa_card("sysdefault:CARD=Device", lookup).as_deref(),
+            Some("sysdefault:CARD@1-2:1.0")
+        );
+        assert_eq!(replace_alsa_card("hw:CARD=PCH,DEV=0", lookup), None, "bilinmeyen kart");
+        assert_eq!(replace_alsa_card("pipewire", lookup), None);
+    }
+
+    #[test]
+    fn test_device_lost_detection() {
+        let backend = || cpal::StreamError::BackendSpecific {
+            err: cpal::BackendSpecificError { description: "No such device".to_string() },
+        };
+        assert!(is_device_lost(&[cpal::StreamError::DeviceNotAvailable]));
+        assert!(!is_device_lost(&[backend(), backend()]));
+        let burst: Vec<_> = (0..LOST_ERROR_BURST).map(|_| backend()).collect();
+        assert!(is_device_lost(&burst));
+    }
+
+    fn range(channels: u16, min: u32, max: u32, format: cpal::SampleFormat) -> cpal::SupportedStreamConfigRange {
+        cpal::SupportedStreamConfigRange::new(
+            channels,
+            cpal::SampleRate(min),
+            cpal::SampleRate(max),
+            cpal::SupportedBufferSize::Unknown,
+            format,
+        )
+    }
+
+    #[test]
+    fn test_downmix_converts_sample_formats() {
+        let i16s: Vec<f32> = downmix(&[i16::MAX, i16::MAX, i16::MIN, 0], 2).collect();
+        assert!((i16s[0] - 1.0).abs() < 1e-4 && (i16s[1] + 0.5).abs() < 1e-4);
+        // Isaretsiz formatlarda orta nokta sessizliktir
+        let u16s: Vec<f32> = downmix(&[32768u16, 32768, u16::MAX, u16::MAX], 2).collect();
+        assert_eq!(u16s[0], 0.0);
+        assert!((u16s[1] - 1.0).abs() < 1e-4);
+        let u8s: Vec<f32> = downmix(&[128u8, 0], 1).collect();
+        assert_eq!(u8s, vec![0.0, -1.0]);
+        // Yarim kalan cerceve atilir
+        assert_eq!(downmix(&[0.5f32, 0.5, 0.5], 2).len(), 1);
+    }
+
+    #[test]
+    fn test_choose_config_for_i16_only_device() {
+        use cpal::SampleFormat::{F32, I16};
+        let ranges = vec![range(2, 8000, 48000, I16), range(1, 44100, 44100, I16), range(2, 48000, 48000, F32)];
+        let default = ranges[0].with_sample_rate(cpal::SampleRate(48000));
+
+        let options = config_options(&ranges, Some(&default));
+        let mono: Vec<u32> = options.iter().filter(|o| o.channels == 1).map(|o| o.sample_rate).collect();
+        assert_eq!(mono, vec![44100]);
+        let stereo_48k = options.iter().find(|o| o.channels == 2 && o.sample_rate == 48000).unwrap();
+        assert_eq!(stereo_48k.sample_format, F32.to_string());
+        assert!(stereo_48k.is_default);
+
+        // Tercih edilen hiz/kanal, ayni araliktaki en iyi formatla acilir
+        let chosen = choose_config(&ranges, default.clone(), Some(InputConfig { sample_rate: 16000, channels: 2 })).unwrap();
+        assert_eq!((chosen.sample_rate().0, chosen.channels(), chosen.sample_format()), (16000, 2, I16));
+        let chosen = choose_config(&ranges, default.clone(), Some(InputConfig { sample_rate: 48000, channels: 2 })).unwrap();
+        assert_eq!(chosen.sample_format(), F32);
+
+        // Desteklenmeyen tercih varsayilana duser
+        let chosen = choose_config(&ranges, default.clone(), Some(InputConfig { sample_rate: 16000, channels: 1 })).unwrap();
+        assert_eq!(chosen, default);
+    }
+
+    #[test]
+    fn test_file_source_replays_in_chunks() {
+        let samples: Vec<f32> = (0..4000).map(|i| i as f32 / 4000.0).collect();
+        let mut source = FileSource::from_samples(samples.clone(), false, "test".to_string());
+        let mut replayed = Vec::new();
+        while let Some(chunk) = source.read().unwrap() {
+            assert!(chunk.len() <= FILE_CHUNK_SAMPLES);
+            replayed.extend(chunk);
+        }
+        assert_eq!(replayed, samples);
+        assert_eq!(source.read().unwrap(), None);
+    }
+}
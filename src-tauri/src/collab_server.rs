@@ -21,7 +21,7 @@ pub struct CollabServerInfo {
 }
 
 #[tauri::command]
-pub async fn start_collab_server(peer_id: String) -> Result<CollabServerInfo, String> {
+pub async fn start_collab_server(app: tauri::AppHandle, peer_id: String) -> Result<CollabServerInfo, String> {
     if SERVER_RUNNING.load(Ordering::SeqCst) {
         // Zaten calisiyorsa mevcut bilgiyi dondur
         if let Some(info) = SERVER_INFO.lock().clone() {
@@ -36,7 +36,7 @@ pub async fn start_collab_server(peer_id: String) -> Result<CollabServerInfo, St
     let pid = peer_id.clone();
     let html_content = COLLAB_HTML.replace("{{PEER_ID}}", &pid);
 
-    let app = Router::new().route(
+    let router = Router::new().route(
         "/",
         get(move || {
             let html = html_content.clone();
@@ -69,7 +69,7 @@ pub async fn start_collab_server(peer_id: String) -> Result<CollabServerInfo, St
 
     // Sunucuyu arka planda calistir
     tokio::spawn(async move {
-        let server = axum::serve(listener, app);
+        let server = axum::serve(listener, router);
         let graceful = server.with_graceful_shutdown(async {
             rx.await.ok();
         });
@@ -81,6 +81,13 @@ pub async fn start_collab_server(peer_id: String) -> Result<CollabServerInfo, St
         log::info!("Collab HTTP sunucu durduruldu");
     });
 
+    // Yerel agdaki diger Fisilti orneklerine duyur (isbirligi acikken)
+    if crate::commands::settings::get_settings(app.clone()).features.collaboration {
+        if let Err(e) = crate::peer_discovery::advertise(app, &peer_id, port) {
+            log::warn!("mDNS duyurusu yapilamadi: {}", e);
+        }
+    }
+
     log::info!("Collab HTTP sunucu baslatildi: {}", url);
     Ok(info)
 }
@@ -90,6 +97,7 @@ pub async fn stop_collab_server() -> Result<(), String> {
     if let Some(tx) = SHUTDOWN_TX.lock().take() {
         tx.send(()).ok();
     }
+    crate::peer_discovery::unadvertise();
     SERVER_RUNNING.store(false, Ordering::SeqCst);
     *SERVER_INFO.lock() = None;
     Ok(())
@@ -1,15 +1,41 @@
-use crate::audio::{self, AudioDevice, SharedAudioState};
-use std::sync::OnceLock;
+use crate::audio::{self, AudioDevice, DeviceFallback, InputConfig, InputConfigOption, SharedAudioState};
+use crate::vad::{VadConfig, VadEvent, VadStatus};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use tauri::Emitter;
+use tauri_plugin_store::StoreExt;
+
+/// Cihaz basina secilen giris yapilandirmalari ("" anahtari varsayilan mikrofon)
+const DEVICE_STORE_PATH: &str = "audio_devices.json";
 
 static AUDIO_STATE: OnceLock<SharedAudioState> = OnceLock::new();
 
-fn get_state() -> &'static SharedAudioState {
+pub(crate) fn get_state() -> &'static SharedAudioState {
     AUDIO_STATE.get_or_init(SharedAudioState::new)
 }
 
-// Stream'i thread-local olarak yonet (Send degil)
-std::thread_local! {
-    static STREAM: std::cell::RefCell<Option<cpal::Stream>> = const { std::cell::RefCell::new(None) };
+/// Calisan cihaz izleyicisinin durdurma bayragi
+static DEVICE_WATCH: Mutex<Option<Arc<AtomicBool>>> = Mutex::new(None);
+
+/// Giris cihazi listesini izlemeyi ac/kapat. Yalnizca ayarlar paneli gorunurken
+/// acilir; degisiklikte tum pencerelere "audio-devices-changed" gonderilir.
+#[tauri::command]
+pub fn watch_audio_devices(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    let mut watch = DEVICE_WATCH.lock();
+    if let Some(active) = watch.take() {
+        active.store(false, Ordering::SeqCst);
+    }
+    if !enabled {
+        return Ok(());
+    }
+    let active = Arc::new(AtomicBool::new(true));
+    audio::watch_devices(active.clone(), get_state().is_recording.clone(), move |devices: &[AudioDevice]| {
+        app.emit("audio-devices-changed", devices).ok();
+    })?;
+    *watch = Some(active);
+    Ok(())
 }
 
 #[tauri::command]
@@ -17,21 +43,69 @@ pub fn list_audio_devices() -> Result<Vec<AudioDevice>, String> {
     Ok(audio::list_devices())
 }
 
+fn load_input_configs(app: &tauri::AppHandle) -> HashMap<String, InputConfig> {
+    app.store(DEVICE_STORE_PATH)
+        .ok()
+        .and_then(|store| store.get("input_configs"))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+/// Cihaz icin kaydedilmis yapilandirma (yoksa cihaz varsayilani kullanilir)
+pub(crate) fn input_config_for(app: &tauri::AppHandle, device_id: Option<&str>) -> Option<InputConfig> {
+    load_input_configs(app).get(device_id.unwrap_or_default()).copied()
+}
+
+#[tauri::command]
+pub fn list_audio_input_configs(device_name: Option<String>) -> Result<Vec<InputConfigOption>, String> {
+    audio::list_input_configs(device_name.as_deref())
+}
+
+#[tauri::command]
+pub fn get_audio_input_config(app: tauri::AppHandle, device_name: Option<String>) -> Option<InputConfig> {
+    input_config_for(&app, device_name.as_deref())
+}
+
+/// `config` bos ise cihaz varsayilanina donulur
+#[tauri::command]
+pub fn set_audio_input_config(
+    app: tauri::AppHandle,
+    device_name: Option<String>,
+    config: Option<InputConfig>,
+) -> Result<(), String> {
+    let store = app.store(DEVICE_STORE_PATH)
+        .map_err(|e| format!("Cihaz ayarlari acilamadi: {}", e))?;
+    let mut configs = load_input_configs(&app);
+    let key = device_name.unwrap_or_default();
+    match config {
+        Some(config) => configs.insert(key, config),
+        None => configs.remove(&key),
+    };
+    store.set("input_configs", serde_json::json!(configs));
+    store.save().map_err(|e| format!("Cihaz ayarlari kaydedilemedi: {}", e))
+}
+
+/// `device_name`: `list_audio_devices` kimligi (eski ayarlarda cihaz adi)
 #[tauri::command]
-pub fn start_recording(device_name: Option<String>) -> Result<(), String> {
+pub fn start_recording(app: tauri::AppHandle, device_name: Option<String>) -> Result<(), String> {
     let state = get_state();
-    let stream = audio::start_recording(state, device_name)?;
-    STREAM.with(|s| {
-        *s.borrow_mut() = Some(stream);
+    let settings = crate::commands::settings::get_settings(app.clone());
+    let config = VadConfig::from_settings(&settings);
+    let input_config = input_config_for(&app, device_name.as_deref());
+    // VAD olaylari frontend'e "vad-event" olarak iletilir (otomatik durdurma icin)
+    let vad_app = app.clone();
+    let sink: audio::VadEventSink = Box::new(move |event: VadEvent| {
+        let _ = vad_app.emit("vad-event", event);
     });
-    Ok(())
+    // Mikrofon cikarilirsa kayit varsayilan mikrofonla surer; kullaniciya bildirilir
+    let on_fallback: audio::FallbackSink = Box::new(move |fallback: DeviceFallback| {
+        let _ = app.emit("audio-device-fallback", fallback);
+    });
+    audio::start_recording(state, device_name, input_config, Some((config, sink)), Some(on_fallback))
 }
 
 #[tauri::command]
 pub fn stop_recording() -> Result<Vec<f32>, String> {
-    STREAM.with(|s| {
-        *s.borrow_mut() = None;
-    });
     let state = get_state();
     audio::stop_recording(state)
 }
@@ -41,3 +115,9 @@ pub fn get_audio_levels() -> f32 {
     let state = get_state();
     audio::get_level(state)
 }
+
+#[tauri::command]
+pub fn get_vad_status() -> VadStatus {
+    let state = get_state();
+    audio::get_vad_status(state)
+}
@@ -0,0 +1,70 @@
+use crate::commands::history::get_history;
+use crate::export::{self, ExportFormat, ExportOptions, TranscriptDocument};
+use crate::meeting;
+
+#[tauri::command]
+pub fn export_transcript(
+    document: TranscriptDocument,
+    format: String,
+    options: Option<ExportOptions>,
+) -> Result<String, String> {
+    let format = ExportFormat::parse(&format)?;
+    export::render(&document, format, &options.unwrap_or_default())
+}
+
+#[tauri::command]
+pub fn export_transcript_to_file(
+    document: TranscriptDocument,
+    format: String,
+    options: Option<ExportOptions>,
+    path: String,
+) -> Result<(), String> {
+    let format = ExportFormat::parse(&format)?;
+    let content = export::render(&document, format, &options.unwrap_or_default())?;
+
+    let mut path = std::path::PathBuf::from(path);
+    if path.extension().is_none() {
+        path.set_extension(format.extension());
+    }
+    std::fs::write(&path, content).map_err(|e| format!("Dosya yazilamadi: {}", e))?;
+
+    log::info!("Transkript disa aktarildi: {:?}", path);
+    Ok(())
+}
+
+/// Gecmis kaydini disa aktar. Metin kullanicinin gordugu islenmis metindir; segmentler
+/// yalnizca zamanlama icin kullanilir. Segmenti olmayan eski kayitlar tek parca yazilir.
+#[tauri::command]
+pub fn export_history_entry(
+    app_handle: tauri::AppHandle,
+    id: String,
+    format: String,
+    options: Option<ExportOptions>,
+) -> Result<String, String> {
+    let format = ExportFormat::parse(&format)?;
+    let entry = get_history(app_handle)
+        .into_iter()
+        .find(|e| e.id == id)
+        .ok_or_else(|| format!("Gecmis kaydi bulunamadi: {}", id))?;
+
+    let document = if entry.segments.is_empty() {
+        TranscriptDocument::from_plain_text(None, &entry.language, &entry.text, entry.duration_ms)
+    } else {
+        TranscriptDocument::from_processed_text(None, &entry.language, &entry.text, &entry.segments)
+    };
+
+    export::render(&document, format, &options.unwrap_or_default())
+}
+
+/// Toplantiyi disa aktar (`meeting_id` yoksa aktif/son toplanti). Konusmacilar korunur.
+#[tauri::command]
+pub fn export_meeting(
+    app_handle: tauri::AppHandle,
+    meeting_id: Option<String>,
+    format: String,
+    options: Option<ExportOptions>,
+) -> Result<String, String> {
+    let format = ExportFormat::parse(&format)?;
+    let meeting = meeting::find_meeting(&app_handle, meeting_id.as_deref())?;
+    export::render(&meeting.to_document(), format, &options.unwrap_or_default())
+}
@@ -1,3 +1,4 @@
+use crate::transcription::TranscriptionSegment;
 use serde::{Deserialize, Serialize};
 use tauri::Emitter;
 use tauri_plugin_store::StoreExt;
@@ -14,6 +15,9 @@ pub struct HistoryEntry {
     pub engine: String,
     pub language: String,
     pub model_id: String,
+    /// Zaman damgali segmentler (yerel Whisper motoru; eski kayitlarda bos)
+    #[serde(default)]
+    pub segments: Vec<TranscriptionSegment>,
 }
 
 #[tauri::command]
@@ -1,9 +1,14 @@
+use crate::voice_commands::{self, VoiceCommand};
 use enigo::{Enigo, Keyboard, Settings};
+use parking_lot::Mutex;
 use std::sync::atomic::{AtomicIsize, Ordering};
 
 /// Son aktif (bizim olmayan) pencere HWND'si (yedek)
 static LAST_FOREGROUND_HWND: AtomicIsize = AtomicIsize::new(0);
 
+/// Son yapistirilan metin — "son cumleyi sil" yalnizca bunun son cumlesini secer
+static LAST_PASTED: Mutex<String> = Mutex::new(String::new());
+
 #[cfg(target_os = "windows")]
 extern "system" {
     fn GetForegroundWindow() -> isize;
@@ -209,5 +214,99 @@ pub fn paste_to_active_app(text: String) -> Result<(), String> {
     std::thread::sleep(std::time::Duration::from_millis(50));
 
     log::info!("Metin yapistrildi: {} karakter", text.len());
+    *LAST_PASTED.lock() = text;
+    Ok(())
+}
+
+/// Metne uygulanamayan ses komutlarini tus vuruslarina cevir
+/// (bkz. `voice_commands::extract`). Aktif pencereye gonderilir.
+#[tauri::command]
+pub fn execute_voice_commands(commands: Vec<VoiceCommand>) -> Result<(), String> {
+    use enigo::{Direction, Key};
+
+    if commands.is_empty() {
+        return Ok(());
+    }
+
+    let mut enigo = Enigo::new(&Settings::default())
+        .map_err(|e| format!("Enigo baslatilamadi: {}", e))?;
+
+    enigo.key(Key::Shift, Direction::Release).ok();
+    enigo.key(Key::Control, Direction::Release).ok();
+    enigo.key(Key::Alt, Direction::Release).ok();
+
+    let chord = |enigo: &mut Enigo, modifier: Key, key: Key| -> Result<(), String> {
+        enigo.key(modifier, Direction::Press)
+            .map_err(|e| format!("Tus basilamadi: {}", e))?;
+        let result = enigo.key(key, Direction::Click);
+        enigo.key(modifier, Direction::Release).ok();
+        result.map_err(|e| format!("Tus basilamadi: {}", e))
+    };
+
+    for command in commands {
+        match command {
+            VoiceCommand::Send | VoiceCommand::NewLine => {
+                enigo.key(Key::Return, Direction::Click)
+                    .map_err(|e| format!("Enter basilamadi: {}", e))?;
+            }
+            VoiceCommand::NewParagraph => {
+                for _ in 0..2 {
+                    enigo.key(Key::Return, Direction::Click)
+                        .map_err(|e| format!("Enter basilamadi: {}", e))?;
+                }
+            }
+            VoiceCommand::DeleteLastWord => {
+                chord(&mut enigo, Key::Control, Key::Backspace)?;
+                voice_commands::delete_last_word(&mut LAST_PASTED.lock());
+            }
+            VoiceCommand::DeleteLastSentence => {
+                // Cumle sinirini dis uygulamada bilemeyiz — son yapistirilan cumlenin
+                // kelimeleri kadar Ctrl+Shift+Left ile secilip silinir
+                let mut pasted = LAST_PASTED.lock();
+                let before = pasted.clone();
+                voice_commands::delete_last_sentence(&mut pasted);
+                let words = before[pasted.len()..].split_whitespace().count();
+                if words == 0 {
+                    log::warn!("Silinecek yapistirilmis cumle yok: {:?}", command);
+                    continue;
+                }
+                select_words_left(&mut enigo, words)?;
+                enigo.key(Key::Backspace, Direction::Click)
+                    .map_err(|e| format!("Backspace basilamadi: {}", e))?;
+            }
+            VoiceCommand::Undo => chord(&mut enigo, Key::Control, Key::Unicode('z'))?,
+            VoiceCommand::SelectAll => chord(&mut enigo, Key::Control, Key::Unicode('a'))?,
+            VoiceCommand::UpperCaseAll | VoiceCommand::LowerCaseAll => {
+                log::warn!("Harf donusumu yapistirilmis metne uygulanamaz: {:?}", command);
+                continue;
+            }
+        }
+        // Diger komutlardan sonra imlecin yapistirilan metnin sonunda oldugu bilinemez
+        if !command.is_destructive() {
+            LAST_PASTED.lock().clear();
+        }
+        std::thread::sleep(std::time::Duration::from_millis(30));
+        log::info!("Ses komutu calistirildi: {:?}", command);
+    }
     Ok(())
 }
+
+/// Ctrl+Shift+Left ile imlecin solundaki `words` kelimeyi sec
+fn select_words_left(enigo: &mut Enigo, words: usize) -> Result<(), String> {
+    use enigo::{Direction, Key};
+
+    enigo.key(Key::Control, Direction::Press)
+        .map_err(|e| format!("Ctrl basilamadi: {}", e))?;
+    enigo.key(Key::Shift, Direction::Press)
+        .map_err(|e| format!("Shift basilamadi: {}", e))?;
+    let mut result = Ok(());
+    for _ in 0..words {
+        result = enigo.key(Key::LeftArrow, Direction::Click);
+        if result.is_err() {
+            break;
+        }
+    }
+    enigo.key(Key::Shift, Direction::Release).ok();
+    enigo.key(Key::Control, Direction::Release).ok();
+    result.map_err(|e| format!("Sol ok basilamadi: {}", e))
+}
@@ -1,5 +1,6 @@
 pub mod audio;
 pub mod corrections;
+pub mod export;
 pub mod history;
 pub mod input;
 pub mod model;
@@ -1,7 +1,13 @@
+use crate::commands::settings::get_settings;
 use crate::model::{self, DownloadProgress, WhisperModel};
+use crate::transcription;
 use parking_lot::Mutex;
 use std::collections::HashMap;
 use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+/// Bosta kalan model kontrol araligi
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
 
 static DOWNLOAD_PROGRESS: OnceLock<Mutex<HashMap<String, Arc<Mutex<DownloadProgress>>>>> =
     OnceLock::new();
@@ -58,6 +64,8 @@ pub fn delete_model(app_handle: tauri::AppHandle, model_id: String) -> Result<()
     let model_path = model::get_model_path(&models_dir, &model_id)
         .ok_or_else(|| format!("Model bulunamadi: {}", model_id))?;
 
+    transcription::unload_model(&model_path);
+
     if model_path.exists() {
         std::fs::remove_file(&model_path)
             .map_err(|e| format!("Model silinemedi: {}", e))?;
@@ -65,3 +73,55 @@ pub fn delete_model(app_handle: tauri::AppHandle, model_id: String) -> Result<()
 
     Ok(())
 }
+
+/// Modeli arka planda bellege yukle (model degistirildiginde ilk dikte beklemesin)
+#[tauri::command]
+pub fn preload_model(app_handle: tauri::AppHandle, model_id: String) -> Result<(), String> {
+    let models_dir = model::get_models_dir(&app_handle);
+    let model_path = model::get_model_path(&models_dir, &model_id)
+        .ok_or_else(|| format!("Model bulunamadi: {}", model_id))?;
+
+    std::thread::spawn(move || {
+        if let Err(e) = transcription::preload_model(&model_path) {
+            log::warn!("Model on yuklemesi basarisiz ({}): {}", model_id, e);
+        }
+    });
+    Ok(())
+}
+
+/// Bellekteki Whisper modellerinin dosya yollari
+#[tauri::command]
+pub fn get_loaded_models() -> Vec<String> {
+    transcription::loaded_models()
+}
+
+#[tauri::command]
+pub fn unload_models() -> usize {
+    transcription::unload_all_models()
+}
+
+/// Acilista secili modeli isit ve bosta kalan modelleri periyodik olarak bosalt
+pub fn start_model_lifecycle(app_handle: tauri::AppHandle) {
+    std::thread::spawn(move || {
+        let settings = get_settings(app_handle.clone());
+        if settings.preload_model {
+            let models_dir = model::get_models_dir(&app_handle);
+            match model::get_model_path(&models_dir, &settings.selected_model) {
+                Some(path) if path.exists() => match transcription::preload_model(&path) {
+                    Ok(()) => log::info!("Model on yuklendi: {}", settings.selected_model),
+                    Err(e) => log::warn!("Model on yuklemesi basarisiz: {}", e),
+                },
+                _ => log::info!("Secili model indirilmemis, on yukleme atlandi"),
+            }
+        }
+
+        loop {
+            std::thread::sleep(IDLE_CHECK_INTERVAL);
+            // Zaman asimi her turda okunur — ayar degisikligi yeniden baslatma gerektirmez
+            let timeout_mins = get_settings(app_handle.clone()).model_idle_timeout_mins;
+            if timeout_mins > 0 {
+                transcription::unload_idle_models(Duration::from_secs(timeout_mins as u64 * 60));
+            }
+        }
+    });
+}
@@ -1,44 +1,181 @@
+use crate::audio;
 use crate::commands::settings::get_settings;
 use crate::corrections;
 use crate::model;
 use crate::text;
-use crate::transcription;
+use crate::settings::AppSettings;
+use crate::transcription::{
+    self, ProgressCallback, TranscriptionOptions, TranscriptionResult, TranscriptionSegment,
+};
+use parking_lot::Mutex;
 use serde::Serialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::Emitter;
 
-#[derive(Serialize)]
+const JOB_RUNNING: &str = "calisiyor";
+const JOB_DONE: &str = "tamamlandi";
+const JOB_CANCELLED: &str = "iptal_edildi";
+const JOB_FAILED: &str = "hata";
+/// Biten is okunmazsa bu sure sonunda silinir
+const FINISHED_JOB_TTL: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Clone, Serialize)]
 pub struct TranscriptionResponse {
     pub text: String,
     pub duration_ms: u64,
     pub status: String,
     pub original_text: String,
+    /// Ham Whisper segmentleri (zaman damgali, islenmemis metin)
+    pub segments: Vec<TranscriptionSegment>,
+    /// Transkripsiyon dili ("auto" modunda tespit edilen dil)
+    pub language: String,
+    pub language_probability: Option<f32>,
+    /// Whisper ceviri modu kullanildi (metin Ingilizce)
+    pub translated: bool,
 }
 
-#[tauri::command]
-pub fn transcribe_audio(
-    app_handle: tauri::AppHandle,
-    audio_data: Vec<f32>,
+impl TranscriptionResponse {
+    fn new(text: String, result: TranscriptionResult, request: &TranscribeRequest) -> Self {
+        Self {
+            text,
+            duration_ms: result.duration_ms,
+            status: "tamamlandi".to_string(),
+            original_text: result.text,
+            segments: result.segments,
+            language: result.language,
+            language_probability: result.language_probability,
+            translated: request.translate,
+        }
+    }
+}
+
+/// Arka plan isinin ilerleme ve iptal kancalari (senkron komutlarda bos)
+#[derive(Clone, Default)]
+struct JobHooks {
+    progress: Option<ProgressCallback>,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+/// Komut parametrelerinden derlenen transkripsiyon istegi
+struct TranscribeRequest {
     model_id: String,
-) -> Result<TranscriptionResponse, String> {
-    let settings = get_settings(app_handle.clone());
+    word_timestamps: bool,
+    decoding_profile: Option<String>,
+    /// Whisper'in yerlesik Ingilizce cevirisi (konusma dili ne olursa olsun cikti Ingilizce)
+    translate: bool,
+}
+
+impl TranscribeRequest {
+    /// `translate` verilmezse ayarlardaki ceviri modu kullanilir. Ceviri
+    /// desteklemeyen modelde (turbo) sessizce Turkce metin donmek yerine hata verir.
+    fn new(
+        app_handle: &tauri::AppHandle,
+        model_id: String,
+        word_timestamps: Option<bool>,
+        decoding_profile: Option<String>,
+        translate: Option<bool>,
+    ) -> Result<Self, String> {
+        let translate = translate.unwrap_or_else(|| get_settings(app_handle.clone()).whisper_translate);
+        if translate && !model::supports_translate(&model_id) {
+            return Err(format!(
+                "{} modeli ceviri yapamaz; ceviri icin Small veya Large V3 modelini secin",
+                model_id
+            ));
+        }
+        Ok(Self {
+            model_id,
+            word_timestamps: word_timestamps.unwrap_or(false),
+            decoding_profile,
+            translate,
+        })
+    }
 
-    let models_dir = model::get_models_dir(&app_handle);
-    let model_path = model::get_model_path(&models_dir, &model_id)
+    fn options<'a>(&self, settings: &'a AppSettings, hooks: JobHooks) -> TranscriptionOptions<'a> {
+        let mut options = TranscriptionOptions::new(&settings.language);
+        options.word_timestamps = self.word_timestamps;
+        // 60 saniyeyi asan kayit kirpilmaz, sessizlik sinirlarindan parcalanir
+        options.long_form = true;
+        options.translate = self.translate;
+        options.drop_hallucinations = settings.hallucination_filter;
+        options.decoding = settings.decoding_profile(self.decoding_profile.as_deref());
+        options.progress = hooks.progress;
+        options.cancel = hooks.cancel;
+        options
+    }
+}
+
+/// Metin isleme dili — ceviri modunda cikti her zaman Ingilizce
+fn output_language<'a>(result: &'a TranscriptionResult, request: &TranscribeRequest) -> &'a str {
+    if request.translate { "en" } else { &result.language }
+}
+
+fn resolve_model_path(app_handle: &tauri::AppHandle, model_id: &str) -> Result<PathBuf, String> {
+    let models_dir = model::get_models_dir(app_handle);
+    let model_path = model::get_model_path(&models_dir, model_id)
         .ok_or_else(|| format!("Model bulunamadi: {}", model_id))?;
 
     if !model_path.exists() {
         return Err("Model dosyasi bulunamadi. Lutfen once modeli indirin.".to_string());
     }
+    Ok(model_path)
+}
+
+#[tauri::command]
+pub fn transcribe_audio(
+    app_handle: tauri::AppHandle,
+    audio_data: Vec<f32>,
+    model_id: String,
+    word_timestamps: Option<bool>,
+    decoding_profile: Option<String>,
+    translate: Option<bool>,
+) -> Result<TranscriptionResponse, String> {
+    let request = TranscribeRequest::new(&app_handle, model_id, word_timestamps, decoding_profile, translate)?;
+    transcribe_recording(&app_handle, &audio_data, &request, JobHooks::default())
+}
+
+/// Mikrofon kaydi transkripsiyonu + metin isleme + ogrenme
+fn transcribe_recording(
+    app_handle: &tauri::AppHandle,
+    audio_data: &[f32],
+    request: &TranscribeRequest,
+    hooks: JobHooks,
+) -> Result<TranscriptionResponse, String> {
+    let settings = get_settings(app_handle.clone());
+    let model_path = resolve_model_path(app_handle, &request.model_id)?;
 
     // Dinamik prompt ile transkripsiyon
-    let result = transcription::transcribe_audio_data(&audio_data, &model_path, &settings.language)?;
+    let options = request.options(&settings, hooks);
+    let result = transcription::transcribe_audio_data(audio_data, &model_path, &options)?;
+    let language = output_language(&result, request);
 
     // Kullanici duzeltme haritasini al
     let user_corrections = corrections::get_corrections_map();
     let corrections_opt = if user_corrections.is_empty() { None } else { Some(&user_corrections) };
 
+    // Ceviri ciktisi Ingilizce — Turkce ogrenme sozlugunu ve n-gram'lari kirletmesin
+    if request.translate {
+        let processed_text = text::process_text_full(
+            &result.text,
+            language,
+            settings.turkish_corrections,
+            settings.hallucination_filter,
+            corrections_opt,
+            settings.auto_punctuation,
+            settings.auto_capitalization,
+            settings.preserve_english_words,
+            settings.auto_comma,
+            settings.paragraph_break,
+        );
+        return Ok(TranscriptionResponse::new(processed_text, result, request));
+    }
+
     let (processed_text, learned_pairs) = text::process_text_and_learn(
         &result.text,
-        &settings.language,
+        language,
         settings.turkish_corrections,
         settings.hallucination_filter,
         corrections_opt,
@@ -54,26 +191,88 @@ pub fn transcribe_audio(
         corrections::add_correction(wrong, right);
     }
     if !learned_pairs.is_empty() {
-        corrections::save_corrections(&app_handle);
+        corrections::save_corrections(app_handle);
         log::info!("Pipeline'dan {} duzeltme ogrendi", learned_pairs.len());
     }
 
-    // N-gram ve sik kelime istatistiklerini guncelle
+    // N-gram ve sik kelime istatistiklerini guncelle; kelime guvenleri belirsiz
+    // kelime listesini gunceller (tekrar dusuk guven: dislanir, yuksek guven: affedilir)
     if !processed_text.is_empty() {
         corrections::update_ngrams(&processed_text);
+        let confidences: Vec<(String, f32)> = result
+            .segments
+            .iter()
+            .flat_map(|seg| &seg.words)
+            .map(|w| (w.text.clone(), w.confidence))
+            .collect();
+        corrections::record_word_confidences(&confidences);
         corrections::update_frequent_words(&processed_text);
-        corrections::save_profile(&app_handle);
+        corrections::save_profile(app_handle);
     }
 
     // Periyodik bakim (her 100 transkripsiyonda)
     corrections::periodic_maintenance();
 
-    Ok(TranscriptionResponse {
-        text: processed_text,
-        duration_ms: result.duration_ms,
-        status: "tamamlandi".to_string(),
-        original_text: result.text,
+    Ok(TranscriptionResponse::new(processed_text, result, request))
+}
+
+/// Diskteki ses dosyasini (WAV/MP3/FLAC/OGG/M4A) transkribe et.
+/// Mikrofon yolundaki Whisper + metin isleme hattini kullanir; ogrenme sozlugunu guncellemez.
+#[tauri::command]
+pub async fn transcribe_file(
+    app_handle: tauri::AppHandle,
+    path: String,
+    model_id: String,
+    word_timestamps: Option<bool>,
+    decoding_profile: Option<String>,
+    translate: Option<bool>,
+) -> Result<TranscriptionResponse, String> {
+    let request = TranscribeRequest::new(&app_handle, model_id, word_timestamps, decoding_profile, translate)?;
+    tokio::task::spawn_blocking(move || {
+        transcribe_file_blocking(&app_handle, &path, &request, JobHooks::default())
     })
+    .await
+    .map_err(|e| format!("Dosya transkripsiyonu basarisiz: {}", e))?
+}
+
+fn transcribe_file_blocking(
+    app_handle: &tauri::AppHandle,
+    path: &str,
+    request: &TranscribeRequest,
+    hooks: JobHooks,
+) -> Result<TranscriptionResponse, String> {
+    let settings = get_settings(app_handle.clone());
+    let model_path = resolve_model_path(app_handle, &request.model_id)?;
+
+    let file_path = PathBuf::from(path);
+    if !file_path.exists() {
+        return Err(format!("Ses dosyasi bulunamadi: {}", path));
+    }
+
+    let audio_data = audio::load_audio_file(&file_path)?;
+
+    let options = request.options(&settings, hooks);
+    let result = transcription::transcribe_audio_data(&audio_data, &model_path, &options)?;
+
+    let user_corrections = corrections::get_corrections_map();
+    let corrections_opt = if user_corrections.is_empty() { None } else { Some(&user_corrections) };
+
+    let processed_text = text::process_text_full(
+        &result.text,
+        output_language(&result, request),
+        settings.turkish_corrections,
+        settings.hallucination_filter,
+        corrections_opt,
+        settings.auto_punctuation,
+        settings.auto_capitalization,
+        settings.preserve_english_words,
+        settings.auto_comma,
+        settings.paragraph_break,
+    );
+
+    log::info!("Dosya transkripsiyonu tamamlandi: {} ({} segment)", path, result.segments.len());
+
+    Ok(TranscriptionResponse::new(processed_text, result, request))
 }
 
 #[tauri::command]
@@ -89,7 +288,7 @@ pub fn process_text_command(
 
     let (processed, learned_pairs) = text::process_text_and_learn(
         &text,
-        &settings.language,
+        settings.spoken_language(),
         settings.turkish_corrections,
         settings.hallucination_filter,
         corrections_opt,
@@ -121,5 +320,213 @@ pub fn process_text_command(
 
 #[tauri::command]
 pub fn get_transcription_status() -> String {
-    "hazir".to_string()
+    let running = get_jobs()
+        .lock()
+        .values()
+        .any(|job| job.status.lock().status == JOB_RUNNING);
+    if running { JOB_RUNNING.to_string() } else { "hazir".to_string() }
+}
+
+// ─── Arka Plan Transkripsiyon Isleri ───
+
+#[derive(Clone, Serialize)]
+pub struct TranscriptionJobStatus {
+    pub job_id: String,
+    pub status: String,
+    pub percent: i32,
+    pub error: Option<String>,
+    /// Biten isin sonucu — "transcription-finished" kacirildiysa buradan okunur
+    pub result: Option<TranscriptionResponse>,
+    #[serde(skip)]
+    finished_at: Option<Instant>,
+}
+
+#[derive(Clone, Serialize)]
+struct TranscriptionProgressEvent {
+    job_id: String,
+    percent: i32,
+}
+
+#[derive(Clone, Serialize)]
+struct TranscriptionFinishedEvent {
+    job_id: String,
+    status: String,
+    result: Option<TranscriptionResponse>,
+    error: Option<String>,
+}
+
+struct TranscriptionJob {
+    status: Arc<Mutex<TranscriptionJobStatus>>,
+    cancel: Arc<AtomicBool>,
+}
+
+static TRANSCRIPTION_JOBS: OnceLock<Mutex<HashMap<String, TranscriptionJob>>> = OnceLock::new();
+static NEXT_JOB_ID: AtomicU64 = AtomicU64::new(1);
+
+fn get_jobs() -> &'static Mutex<HashMap<String, TranscriptionJob>> {
+    TRANSCRIPTION_JOBS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Suresi dolan biten isleri at; calisanlar ve henuz okunmamis sonuclar kalir
+fn prune_jobs(jobs: &mut HashMap<String, TranscriptionJob>, now: Instant) {
+    jobs.retain(|_, job| {
+        job.status
+            .lock()
+            .finished_at
+            .is_none_or(|at| now.duration_since(at) < FINISHED_JOB_TTL)
+    });
+}
+
+/// Isi arka plan thread'inde baslat; ilerleme "transcription-progress",
+/// sonuc "transcription-finished" event'i ile bildirilir.
+fn spawn_job<F>(app_handle: tauri::AppHandle, work: F) -> Result<String, String>
+where
+    F: FnOnce(JobHooks) -> Result<TranscriptionResponse, String> + Send + 'static,
+{
+    let job_id = format!("tj-{}", NEXT_JOB_ID.fetch_add(1, Ordering::Relaxed));
+    let cancel = Arc::new(AtomicBool::new(false));
+    let status = Arc::new(Mutex::new(TranscriptionJobStatus {
+        job_id: job_id.clone(),
+        status: JOB_RUNNING.to_string(),
+        percent: 0,
+        error: None,
+        result: None,
+        finished_at: None,
+    }));
+
+    {
+        let mut jobs = get_jobs().lock();
+        prune_jobs(&mut jobs, Instant::now());
+        jobs.insert(
+            job_id.clone(),
+            TranscriptionJob {
+                status: status.clone(),
+                cancel: cancel.clone(),
+            },
+        );
+    }
+
+    let progress: ProgressCallback = {
+        let app = app_handle.clone();
+        let status = status.clone();
+        let id = job_id.clone();
+        let last = AtomicI32::new(-1);
+        Arc::new(move |percent: i32| {
+            let percent = percent.clamp(0, 100);
+            // Ayni yuzdeyi tekrar gonderme
+            if last.swap(percent, Ordering::Relaxed) == percent {
+                return;
+            }
+            status.lock().percent = percent;
+            app.emit(
+                "transcription-progress",
+                TranscriptionProgressEvent { job_id: id.clone(), percent },
+            )
+            .ok();
+        })
+    };
+
+    let hooks = JobHooks {
+        progress: Some(progress),
+        cancel: Some(cancel),
+    };
+
+    let id = job_id.clone();
+    std::thread::Builder::new()
+        .name(format!("transcription-{}", id))
+        .spawn(move || {
+            let outcome = work(hooks);
+
+            let (state, result, error) = match outcome {
+                Ok(response) => (JOB_DONE, Some(response), None),
+                Err(e) if e == transcription::CANCELLED_ERROR => (JOB_CANCELLED, None, Some(e)),
+                Err(e) => (JOB_FAILED, None, Some(e)),
+            };
+
+            {
+                let mut s = status.lock();
+                s.status = state.to_string();
+                s.error = error.clone();
+                s.result = result.clone();
+                s.finished_at = Some(Instant::now());
+                if result.is_some() {
+                    s.percent = 100;
+                }
+            }
+            log::info!("Transkripsiyon isi {} bitti: {}", id, state);
+
+            app_handle
+                .emit(
+                    "transcription-finished",
+                    TranscriptionFinishedEvent {
+                        job_id: id,
+                        status: state.to_string(),
+                        result,
+                        error,
+                    },
+                )
+                .ok();
+        })
+        .map_err(|e| format!("Transkripsiyon isi baslatilamadi: {}", e))?;
+
+    Ok(job_id)
+}
+
+/// Kaydi arka planda transkribe et — is kimligini hemen dondurur
+#[tauri::command]
+pub fn start_transcription_job(
+    app_handle: tauri::AppHandle,
+    audio_data: Vec<f32>,
+    model_id: String,
+    word_timestamps: Option<bool>,
+    decoding_profile: Option<String>,
+    translate: Option<bool>,
+) -> Result<String, String> {
+    let request = TranscribeRequest::new(&app_handle, model_id, word_timestamps, decoding_profile, translate)?;
+    let app = app_handle.clone();
+    spawn_job(app_handle, move |hooks| {
+        transcribe_recording(&app, &audio_data, &request, hooks)
+    })
+}
+
+/// Ses dosyasini arka planda transkribe et — is kimligini hemen dondurur
+#[tauri::command]
+pub fn start_file_transcription_job(
+    app_handle: tauri::AppHandle,
+    path: String,
+    model_id: String,
+    word_timestamps: Option<bool>,
+    decoding_profile: Option<String>,
+    translate: Option<bool>,
+) -> Result<String, String> {
+    let request = TranscribeRequest::new(&app_handle, model_id, word_timestamps, decoding_profile, translate)?;
+    let app = app_handle.clone();
+    spawn_job(app_handle, move |hooks| {
+        transcribe_file_blocking(&app, &path, &request, hooks)
+    })
+}
+
+/// Calisan isi iptal et (Whisper abort callback'i bir sonraki kontrolde durur)
+#[tauri::command]
+pub fn cancel_transcription_job(job_id: String) -> bool {
+    match get_jobs().lock().get(&job_id) {
+        Some(job) => {
+            job.cancel.store(true, Ordering::Relaxed);
+            log::info!("Transkripsiyon isi iptal istendi: {}", job_id);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Is durumu. Biten is okununca listeden cikar (sonuc bir kez teslim edilir).
+#[tauri::command]
+pub fn get_transcription_job(job_id: String) -> Option<TranscriptionJobStatus> {
+    let mut jobs = get_jobs().lock();
+    prune_jobs(&mut jobs, Instant::now());
+    let status = jobs.get(&job_id)?.status.lock().clone();
+    if status.finished_at.is_some() {
+        jobs.remove(&job_id);
+    }
+    Some(status)
 }
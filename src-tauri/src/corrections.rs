@@ -5,7 +5,7 @@
 //! 2. Dinamik Initial Prompt (Whisper baglam)
 //! 3. Alan Tespiti (Domain Detection)
 //! 4. N-gram Istatistikleri
-//! 5. Confidence Takibi (frontend tarafinda)
+//! 5. Confidence Takibi (Whisper token olasiliklari + frontend)
 
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
@@ -87,6 +87,13 @@ pub struct NgramEntry {
     pub count: u32,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UncertainWord {
+    pub word: String,
+    pub count: u32,
+    pub avg_confidence: f32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct UserProfile {
     pub domain: Domain,
@@ -94,6 +101,9 @@ pub struct UserProfile {
     pub ngrams: Vec<NgramEntry>,
     pub total_transcriptions: u32,
     pub total_corrections: u32,
+    /// Whisper'in sik dusuk guvenle tanidigi kelimeler (duzeltme adaylari)
+    #[serde(default)]
+    pub uncertain_words: Vec<UncertainWord>,
 }
 
 // ─── Yeni API Yapilari ───
@@ -754,19 +764,25 @@ pub fn update_ngrams(text: &str) {
 
 /// Sik kelimeleri profilde guncelle (stop-word ve kisa kelimeler filtrelenir)
 pub fn update_frequent_words(text: &str) {
+    add_frequent_words(&mut get_user_profile().write(), text);
+}
+
+/// Kelimeler noktalamasiz anahtar olarak saklanir. Tekrar tekrar dusuk guvenle
+/// taninan kelimeler sik kelime listesine alinmaz — aksi halde
+/// `record_word_confidences`'in cikardigi kelime bir sonraki kayitta geri gelir.
+fn add_frequent_words(profile: &mut UserProfile, text: &str) {
     let words: Vec<String> = text
         .split_whitespace()
+        .map(word_key)
         .filter(|w| w.len() > 3)
-        .map(|w| w.to_lowercase())
         .filter(|w| !is_turkish_stopword(w))
+        .filter(|w| !is_excluded_uncertain(profile, w))
         .collect();
 
-    let mut profile = get_user_profile().write();
-
-    // Kelime frekanslarini say
+    // Kelime frekanslarini say (eski profillerdeki noktalamali kayitlar birlesir)
     let mut word_counts: HashMap<String, u32> = HashMap::new();
     for word in &profile.frequent_words {
-        *word_counts.entry(word.clone()).or_insert(0) += 1;
+        *word_counts.entry(word_key(word)).or_insert(0) += 1;
     }
     for word in &words {
         *word_counts.entry(word.clone()).or_insert(0) += 1;
@@ -780,6 +796,75 @@ pub fn update_frequent_words(text: &str) {
     profile.frequent_words = sorted.into_iter().map(|(w, _)| w).collect();
 }
 
+// ─── Katman 5: Confidence Takibi ───
+
+/// Belirsiz kelime bu kadar kez dusuk guvenle taninmadan sik kelimelerden dislanmaz
+const UNCERTAIN_EXCLUDE_COUNT: u32 = 2;
+
+/// Whisper'in kelime guvenlerini kaydet. Tekrar tekrar dusuk guvenle taninan kelime
+/// sik kelime listesinden cikarilir — yanlis taninmis olabilecek bir kelime dinamik
+/// prompt'a girip hatayi pekistirmesin. Yuksek guvenli her taninma sayaci bir azaltir;
+/// sifirlanan kelime belirsiz listesinden duser ve yeniden sik kelime olabilir.
+pub fn record_word_confidences(words: &[(String, f32)]) {
+    if words.is_empty() {
+        return;
+    }
+    apply_word_confidences(&mut get_user_profile().write(), words);
+}
+
+/// Kelime anahtari — noktalama atilmis, kucuk harf
+fn word_key(word: &str) -> String {
+    word.chars()
+        .filter(|c| c.is_alphanumeric() || *c == '\'')
+        .collect::<String>()
+        .to_lowercase()
+}
+
+fn is_excluded_uncertain(profile: &UserProfile, key: &str) -> bool {
+    profile
+        .uncertain_words
+        .iter()
+        .any(|u| u.word == key && u.count >= UNCERTAIN_EXCLUDE_COUNT)
+}
+
+fn apply_word_confidences(profile: &mut UserProfile, words: &[(String, f32)]) {
+    for (word, confidence) in words {
+        let key = word_key(word);
+        if key.chars().count() < 2 {
+            continue;
+        }
+
+        if *confidence >= crate::transcription::LOW_CONFIDENCE_THRESHOLD {
+            if let Some(i) = profile.uncertain_words.iter().position(|u| u.word == key) {
+                profile.uncertain_words[i].count -= 1;
+                if profile.uncertain_words[i].count == 0 {
+                    profile.uncertain_words.remove(i);
+                }
+            }
+            continue;
+        }
+
+        if let Some(existing) = profile.uncertain_words.iter_mut().find(|u| u.word == key) {
+            let total = existing.avg_confidence * existing.count as f32 + confidence;
+            existing.count += 1;
+            existing.avg_confidence = total / existing.count as f32;
+        } else {
+            profile.uncertain_words.push(UncertainWord {
+                word: key.clone(),
+                count: 1,
+                avg_confidence: *confidence,
+            });
+        }
+        if is_excluded_uncertain(profile, &key) {
+            profile.frequent_words.retain(|w| word_key(w) != key);
+        }
+    }
+
+    // En sik 100 belirsiz kelimeyi sakla
+    profile.uncertain_words.sort_by_key(|u| std::cmp::Reverse(u.count));
+    profile.uncertain_words.truncate(100);
+}
+
 // ─── Katman 2: Dinamik Prompt Olusturucu ───
 
 /// 3 katmanli dinamik prompt olustur:
@@ -1081,6 +1166,45 @@ mod tests {
         assert_eq!(detect_domain(&general), Domain::General);
     }
 
+    #[test]
+    fn test_uncertain_words_stay_out_of_frequent_words() {
+        let low = |w: &str| (w.to_string(), 0.3);
+        let mut profile = UserProfile::default();
+        add_frequent_words(&mut profile, "Kubernetes, kubernetes deploy");
+        assert_eq!(profile.frequent_words.iter().filter(|w| *w == "kubernetes").count(), 1);
+
+        // Tek dusuk guvenli taninma dislamaz, ikincisi dislar
+        apply_word_confidences(&mut profile, &[low("Kubernetes,")]);
+        assert!(profile.frequent_words.contains(&"kubernetes".to_string()));
+        apply_word_confidences(&mut profile, &[low("kubernetes")]);
+        assert!(!profile.frequent_words.contains(&"kubernetes".to_string()));
+
+        // Sonraki kayit belirsiz kelimeyi sik kelimelere geri eklemez
+        add_frequent_words(&mut profile, "kubernetes deploy");
+        assert!(!profile.frequent_words.contains(&"kubernetes".to_string()));
+        assert!(profile.frequent_words.contains(&"deploy".to_string()));
+        assert_eq!(profile.uncertain_words[0].word, "kubernetes");
+
+        // Guvenli taninmalar dislamayi kaldirir
+        let high = ("kubernetes".to_string(), 0.9);
+        apply_word_confidences(&mut profile, &[high.clone(), high]);
+        assert!(profile.uncertain_words.is_empty());
+        add_frequent_words(&mut profile, "kubernetes");
+        assert!(profile.frequent_words.contains(&"kubernetes".to_string()));
+    }
+
+    #[test]
+    fn test_punctuated_frequent_word_is_removed() {
+        // Eski profil kelimeyi noktalamasiyla saklamis
+        let mut profile = UserProfile {
+            frequent_words: vec!["kubernetes,".to_string()],
+            ..Default::default()
+        };
+        let low = ("Kubernetes".to_string(), 0.2);
+        apply_word_confidences(&mut profile, &[low.clone(), low]);
+        assert!(profile.frequent_words.is_empty());
+    }
+
     #[test]
     fn test_apply_user_corrections() {
         let mut map = HashMap::new();
@@ -0,0 +1,467 @@
+//! Transkript disa aktarma — SRT, WebVTT, JSON ve duz metin.
+//!
+//! Dosya transkripsiyonu, gecmis kayitlari ve toplanti transkriptleri
+//! ayni `TranscriptDocument` yapisina donusturulup buradan render edilir.
+//! Satir uzunlugu karakter (byte degil) bazinda olculur — Turkce
+//! karakterler (ç, ğ, ı, ö, ş, ü) tek karakter sayilir.
+
+use crate::transcription::TranscriptionSegment;
+use serde::{Deserialize, Serialize};
+
+/// Altyazi satiri icin varsayilan maksimum karakter (Netflix/BBC onerisi)
+const DEFAULT_MAX_LINE_CHARS: usize = 42;
+/// Bir altyazi karesinde maksimum satir sayisi
+const DEFAULT_MAX_LINES: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Srt,
+    Vtt,
+    Json,
+    Txt,
+}
+
+impl ExportFormat {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.trim().to_lowercase().as_str() {
+            "srt" => Ok(ExportFormat::Srt),
+            "vtt" | "webvtt" => Ok(ExportFormat::Vtt),
+            "json" => Ok(ExportFormat::Json),
+            "txt" | "text" => Ok(ExportFormat::Txt),
+            other => Err(format!("Bilinmeyen disa aktarma formati: {}", other)),
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Srt => "srt",
+            ExportFormat::Vtt => "vtt",
+            ExportFormat::Json => "json",
+            ExportFormat::Txt => "txt",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportOptions {
+    /// TXT formatinda her satirin basina [SS:DD:ss] ekle
+    #[serde(default)]
+    pub include_timestamps: bool,
+    #[serde(default = "default_max_line_chars")]
+    pub max_line_chars: usize,
+    #[serde(default = "default_max_lines")]
+    pub max_lines: usize,
+}
+
+fn default_max_line_chars() -> usize {
+    DEFAULT_MAX_LINE_CHARS
+}
+
+fn default_max_lines() -> usize {
+    DEFAULT_MAX_LINES
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            include_timestamps: false,
+            max_line_chars: DEFAULT_MAX_LINE_CHARS,
+            max_lines: DEFAULT_MAX_LINES,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportSegment {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+    #[serde(default)]
+    pub speaker: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TranscriptDocument {
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub language: String,
+    pub segments: Vec<ExportSegment>,
+}
+
+impl TranscriptDocument {
+    /// Islenmis metni (duzeltme, noktalama, sesli komut temizligi) Whisper segmentlerinin
+    /// zamanlarina dagit. Kelimeler segmentlere ham kelime sayilari oraninda paylastirilir.
+    pub fn from_processed_text(
+        title: Option<String>,
+        language: &str,
+        text: &str,
+        segments: &[TranscriptionSegment],
+    ) -> Self {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let counts: Vec<usize> = segments.iter().map(|s| s.text.split_whitespace().count()).collect();
+        let total: usize = counts.iter().sum();
+        if total == 0 {
+            let duration_ms = segments.last().map_or(0, |s| s.end_ms);
+            return Self::from_plain_text(title, language, text, duration_ms);
+        }
+
+        let mut seen = 0;
+        let mut taken = 0;
+        let mut out = Vec::new();
+        for (segment, count) in segments.iter().zip(counts) {
+            seen += count;
+            let end = (seen * words.len() + total / 2) / total;
+            if end > taken {
+                out.push(ExportSegment {
+                    start_ms: segment.start_ms,
+                    end_ms: segment.end_ms,
+                    text: words[taken..end].join(" "),
+                    speaker: None,
+                });
+                taken = end;
+            }
+        }
+        Self {
+            title,
+            language: language.to_string(),
+            segments: out,
+        }
+    }
+
+    /// Zaman bilgisi olmayan tek parca metinden belge olustur (eski gecmis kayitlari)
+    pub fn from_plain_text(title: Option<String>, language: &str, text: &str, duration_ms: u64) -> Self {
+        let segments = if text.trim().is_empty() {
+            Vec::new()
+        } else {
+            vec![ExportSegment {
+                start_ms: 0,
+                end_ms: duration_ms,
+                text: text.trim().to_string(),
+                speaker: None,
+            }]
+        };
+        Self {
+            title,
+            language: language.to_string(),
+            segments,
+        }
+    }
+}
+
+/// Belgeyi istenen formatta render et
+pub fn render(doc: &TranscriptDocument, format: ExportFormat, options: &ExportOptions) -> Result<String, String> {
+    match format {
+        ExportFormat::Srt => Ok(render_srt(doc, options)),
+        ExportFormat::Vtt => Ok(render_vtt(doc, options)),
+        ExportFormat::Json => serde_json::to_string_pretty(doc)
+            .map_err(|e| format!("JSON olusturulamadi: {}", e)),
+        ExportFormat::Txt => Ok(render_txt(doc, options)),
+    }
+}
+
+// ─── Altyazi Formatlari ───
+
+/// Altyazi karesi — zaman araligi + sarilmis satirlar
+struct Cue {
+    start_ms: u64,
+    end_ms: u64,
+    lines: Vec<String>,
+    speaker: Option<String>,
+}
+
+/// Segmentleri satir uzunluguna gore sar; sigmayan segmentleri
+/// sureyi karakter oraninda paylastirarak birden fazla kareye bol.
+fn build_cues(doc: &TranscriptDocument, options: &ExportOptions) -> Vec<Cue> {
+    let max_chars = options.max_line_chars.max(10);
+    let max_lines = options.max_lines.max(1);
+    let mut cues = Vec::new();
+
+    for seg in &doc.segments {
+        let text = seg.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+
+        let lines = wrap_text(text, max_chars);
+        let groups: Vec<&[String]> = lines.chunks(max_lines).collect();
+        let total_chars: usize = lines.iter().map(|l| l.chars().count()).sum::<usize>().max(1);
+        let span = seg.end_ms.saturating_sub(seg.start_ms);
+
+        let mut cursor = seg.start_ms;
+        let mut consumed = 0usize;
+        for (i, group) in groups.iter().enumerate() {
+            consumed += group.iter().map(|l| l.chars().count()).sum::<usize>();
+            let end = if i + 1 == groups.len() {
+                seg.end_ms
+            } else {
+                seg.start_ms + span * consumed as u64 / total_chars as u64
+            };
+            cues.push(Cue {
+                start_ms: cursor,
+                end_ms: end.max(cursor),
+                lines: group.to_vec(),
+                speaker: seg.speaker.clone(),
+            });
+            cursor = end;
+        }
+    }
+
+    cues
+}
+
+fn render_srt(doc: &TranscriptDocument, options: &ExportOptions) -> String {
+    let mut out = String::new();
+    for (i, cue) in build_cues(doc, options).iter().enumerate() {
+        out.push_str(&format!(
+            "{}\n{} --> {}\n",
+            i + 1,
+            format_timestamp(cue.start_ms, ','),
+            format_timestamp(cue.end_ms, ',')
+        ));
+        for (j, line) in cue.lines.iter().enumerate() {
+            // SRT'de konusmaci etiketi ilk satirin basina yazilir
+            match (&cue.speaker, j) {
+                (Some(speaker), 0) => out.push_str(&format!("{}: {}\n", speaker, line)),
+                _ => {
+                    out.push_str(line);
+                    out.push('\n');
+                }
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn render_vtt(doc: &TranscriptDocument, options: &ExportOptions) -> String {
+    let mut out = String::from("WEBVTT\n");
+    if let Some(title) = &doc.title {
+        out.push_str(&format!("NOTE {}\n", escape_vtt(title)));
+    }
+    out.push('\n');
+
+    for cue in build_cues(doc, options) {
+        out.push_str(&format!(
+            "{} --> {}\n",
+            format_timestamp(cue.start_ms, '.'),
+            format_timestamp(cue.end_ms, '.')
+        ));
+        let body: Vec<String> = cue.lines.iter().map(|l| escape_vtt(l)).collect();
+        match &cue.speaker {
+            Some(speaker) => out.push_str(&format!("<v {}>{}\n", escape_vtt(speaker), body.join("\n"))),
+            None => {
+                out.push_str(&body.join("\n"));
+                out.push('\n');
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn render_txt(doc: &TranscriptDocument, options: &ExportOptions) -> String {
+    let mut out = String::new();
+    if let Some(title) = &doc.title {
+        out.push_str(title);
+        out.push_str("\n\n");
+    }
+
+    for seg in &doc.segments {
+        let text = seg.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        if options.include_timestamps {
+            out.push_str(&format!("[{}] ", format_clock(seg.start_ms)));
+        }
+        if let Some(speaker) = &seg.speaker {
+            out.push_str(&format!("{}: ", speaker));
+        }
+        out.push_str(text);
+        out.push('\n');
+    }
+    out
+}
+
+// ─── Yardimcilar ───
+
+/// SS:DD:ss{sep}mmm — SRT ',' WebVTT '.' ayirici kullanir
+fn format_timestamp(ms: u64, sep: char) -> String {
+    let hours = ms / 3_600_000;
+    let minutes = (ms / 60_000) % 60;
+    let seconds = (ms / 1000) % 60;
+    let millis = ms % 1000;
+    format!("{:02}:{:02}:{:02}{}{:03}", hours, minutes, seconds, sep, millis)
+}
+
+/// Duz metin icin SS:DD:ss
+fn format_clock(ms: u64) -> String {
+    let secs = ms / 1000;
+    format!("{:02}:{:02}:{:02}", secs / 3600, (secs / 60) % 60, secs % 60)
+}
+
+/// Kelimeleri bolmeden satirlara sar (uzunluk karakter bazli).
+/// Tek basina satira sigmayan cok uzun kelimeler karakter sinirindan bolunur.
+fn wrap_text(text: &str, max_chars: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut current_len = 0usize;
+
+    for word in text.split_whitespace() {
+        let word_len = word.chars().count();
+
+        if word_len > max_chars {
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+                current_len = 0;
+            }
+            let chars: Vec<char> = word.chars().collect();
+            for piece in chars.chunks(max_chars) {
+                lines.push(piece.iter().collect());
+            }
+            continue;
+        }
+
+        let needed = if current.is_empty() { word_len } else { current_len + 1 + word_len };
+        if needed > max_chars {
+            lines.push(std::mem::take(&mut current));
+            current.push_str(word);
+            current_len = word_len;
+        } else {
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(word);
+            current_len = needed;
+        }
+    }
+
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// WebVTT ozel karakterlerini kacir; "-->" kare ayiricisiyla karismasin
+fn escape_vtt(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(segments: Vec<(u64, u64, &str)>) -> TranscriptDocument {
+        TranscriptDocument {
+            title: None,
+            language: "tr".to_string(),
+            segments: segments
+                .into_iter()
+                .map(|(s, e, t)| ExportSegment {
+                    start_ms: s,
+                    end_ms: e,
+                    text: t.to_string(),
+                    speaker: None,
+                })
+                .collect(),
+        }
+    }
+
+    fn raw(start_ms: u64, end_ms: u64, text: &str) -> TranscriptionSegment {
+        TranscriptionSegment { text: text.to_string(), start_ms, end_ms, ..Default::default() }
+    }
+
+    #[test]
+    fn test_processed_text_keeps_segment_timings() {
+        let segments = vec![raw(0, 1500, "merhaba dunya"), raw(1500, 3000, "nasilsin")];
+        // Turkce duzeltme + noktalama uygulanmis metin
+        let d = TranscriptDocument::from_processed_text(None, "tr", "Merhaba dünya. Nasılsın?", &segments);
+        assert_eq!(d.segments.len(), 2);
+        assert_eq!(d.segments[0].text, "Merhaba dünya.");
+        assert_eq!((d.segments[0].start_ms, d.segments[0].end_ms), (0, 1500));
+        assert_eq!(d.segments[1].text, "Nasılsın?");
+        assert_eq!((d.segments[1].start_ms, d.segments[1].end_ms), (1500, 3000));
+
+        // Sesli komut cikarilinca kelimesi kalmayan segment atlanir, metin kaybolmaz
+        let d = TranscriptDocument::from_processed_text(None, "tr", "Tamam.", &segments);
+        let joined: Vec<&str> = d.segments.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(joined, vec!["Tamam."]);
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0, ','), "00:00:00,000");
+        assert_eq!(format_timestamp(3_723_456, ','), "01:02:03,456");
+        assert_eq!(format_timestamp(61_005, '.'), "00:01:01.005");
+    }
+
+    #[test]
+    fn test_wrap_text_counts_chars_not_bytes() {
+        // 10 karakter, 20+ byte
+        let lines = wrap_text("çğışöü çğış", 11);
+        assert_eq!(lines, vec!["çğışöü çğış".to_string()]);
+        let lines = wrap_text("çğışöü çğış", 10);
+        assert_eq!(lines, vec!["çğışöü".to_string(), "çğış".to_string()]);
+    }
+
+    #[test]
+    fn test_wrap_text_long_word() {
+        let lines = wrap_text("abcdefghijkl", 5);
+        assert_eq!(lines, vec!["abcde", "fghij", "kl"]);
+    }
+
+    #[test]
+    fn test_render_srt() {
+        let d = doc(vec![(0, 1500, "Merhaba dünya."), (1500, 3000, "Nasılsınız?")]);
+        let srt = render(&d, ExportFormat::Srt, &ExportOptions::default()).unwrap();
+        assert_eq!(
+            srt,
+            "1\n00:00:00,000 --> 00:00:01,500\nMerhaba dünya.\n\n2\n00:00:01,500 --> 00:00:03,000\nNasılsınız?\n\n"
+        );
+    }
+
+    #[test]
+    fn test_render_vtt_escapes() {
+        let d = doc(vec![(0, 1000, "a < b & c")]);
+        let vtt = render(&d, ExportFormat::Vtt, &ExportOptions::default()).unwrap();
+        assert!(vtt.starts_with("WEBVTT\n\n"));
+        assert!(vtt.contains("00:00:00.000 --> 00:00:01.000\na &lt; b &amp; c\n"));
+    }
+
+    #[test]
+    fn test_long_segment_split_into_cues() {
+        let text = "bu çok uzun bir cümle ve altyazı karesine sığmayacak kadar fazla kelime içeriyor, bu yüzden bölünmeli";
+        let d = doc(vec![(0, 10_000, text)]);
+        let options = ExportOptions { max_line_chars: 20, max_lines: 2, ..Default::default() };
+        let cues = build_cues(&d, &options);
+        assert!(cues.len() > 1);
+        assert_eq!(cues.first().unwrap().start_ms, 0);
+        assert_eq!(cues.last().unwrap().end_ms, 10_000);
+        for pair in cues.windows(2) {
+            assert_eq!(pair[0].end_ms, pair[1].start_ms);
+        }
+        for cue in &cues {
+            assert!(cue.lines.len() <= 2);
+            assert!(cue.lines.iter().all(|l| l.chars().count() <= 20));
+        }
+    }
+
+    #[test]
+    fn test_render_txt_with_timestamps() {
+        let mut d = doc(vec![(65_000, 70_000, "İkinci konu")]);
+        d.segments[0].speaker = Some("Ayşe".to_string());
+        let options = ExportOptions { include_timestamps: true, ..Default::default() };
+        let txt = render(&d, ExportFormat::Txt, &options).unwrap();
+        assert_eq!(txt, "[00:01:05] Ayşe: İkinci konu\n");
+    }
+
+    #[test]
+    fn test_render_json_keeps_turkish() {
+        let d = doc(vec![(0, 1000, "Güle güle")]);
+        let json = render(&d, ExportFormat::Json, &ExportOptions::default()).unwrap();
+        assert!(json.contains("Güle güle"));
+    }
+}
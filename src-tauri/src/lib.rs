@@ -4,6 +4,7 @@ mod collab_server;
 mod commands;
 mod corrections;
 mod edge_tts;
+mod export;
 mod keyboard_hook;
 mod live_translation;
 mod llm_client;
@@ -11,9 +12,12 @@ mod meeting;
 mod model;
 mod mouse_hook;
 mod peer_discovery;
+mod ring_buffer;
 mod sentiment;
 mod settings;
 mod streaming_stt;
+#[cfg(test)]
+mod test_util;
 mod text;
 mod translate;
 mod transcription;
@@ -76,6 +80,9 @@ pub fn run() {
             corrections::load_corrections(&app_handle);
             corrections::load_profile(&app_handle);
 
+            // Secili Whisper modelini arka planda isit, bosta kalan modelleri bosalt
+            commands::model::start_model_lifecycle(app_handle.clone());
+
             // Sistem tepsisi olustur
             let show_item = MenuItemBuilder::with_id("show", "Göster")
                 .build(app)?;
@@ -154,6 +161,11 @@ pub fn run() {
             // Pano izlemeyi baslat
             clipboard_watcher::start_clipboard_watcher(app_handle.clone());
 
+            // Yerel agdaki diger Fisilti orneklerini dinle (mDNS)
+            if commands::settings::get_settings(app_handle.clone()).features.collaboration {
+                peer_discovery::set_enabled(app_handle.clone(), true);
+            }
+
             // Captions penceresi — baslangiçta gizli, seffaf
             if let Some(window) = app.get_webview_window("captions") {
                 window.set_title("").ok();
@@ -251,24 +263,42 @@ pub fn run() {
         })
         .invoke_handler(tauri::generate_handler![
             commands::audio::list_audio_devices,
+            commands::audio::watch_audio_devices,
             commands::audio::start_recording,
             commands::audio::stop_recording,
             commands::audio::get_audio_levels,
+            commands::audio::get_vad_status,
+            commands::audio::list_audio_input_configs,
+            commands::audio::get_audio_input_config,
+            commands::audio::set_audio_input_config,
             commands::transcription::transcribe_audio,
+            commands::transcription::transcribe_file,
+            commands::transcription::start_transcription_job,
+            commands::transcription::start_file_transcription_job,
+            commands::transcription::cancel_transcription_job,
+            commands::transcription::get_transcription_job,
             commands::transcription::get_transcription_status,
             commands::transcription::process_text_command,
             commands::model::list_models,
             commands::model::download_model,
             commands::model::get_download_progress,
             commands::model::delete_model,
+            commands::model::preload_model,
+            commands::model::get_loaded_models,
+            commands::model::unload_models,
             commands::settings::get_settings,
             commands::settings::save_settings,
             commands::input::paste_to_active_app,
+            commands::input::execute_voice_commands,
             commands::input::save_foreground_window,
             commands::input::restore_foreground_window,
             commands::history::save_history_entry,
             commands::history::get_history,
             commands::history::clear_history,
+            commands::export::export_transcript,
+            commands::export::export_transcript_to_file,
+            commands::export::export_history_entry,
+            commands::export::export_meeting,
             commands::corrections::add_user_correction,
             commands::corrections::remove_user_correction,
             commands::corrections::get_user_corrections,
@@ -321,9 +351,17 @@ pub fn run() {
             meeting::update_chunk_speaker,
             meeting::set_meeting_notes,
             meeting::get_meeting_notes,
+            meeting::list_meetings,
+            meeting::get_saved_meeting,
+            meeting::delete_saved_meeting,
             llm_client::process_with_llm,
+            llm_client::get_llm_actions,
             peer_discovery::get_discovered_peers,
             peer_discovery::stop_peer_service,
+            // Canli transkripsiyon (Whisper)
+            streaming_stt::start_streaming_transcription,
+            streaming_stt::stop_streaming_transcription,
+            streaming_stt::get_streaming_status,
             // Canli ceviri
             live_translation::start_live_translation,
             live_translation::stop_live_translation,
@@ -1624,12 +1662,23 @@ fn setup_context_menu_win32(app_handle: tauri::AppHandle) {
 
 #[tauri::command]
 async fn translate_text(
+    app: tauri::AppHandle,
     text: String,
     source_lang: String,
     target_lang: String,
     engine: String,
     deepl_api_key: String,
 ) -> Result<translate::TranslateResponse, String> {
+    // Bos motor/anahtar gelirse kayitli ayarlar kullanilir
+    let (engine, deepl_api_key) = if engine.trim().is_empty() || deepl_api_key.trim().is_empty() {
+        let settings = commands::settings::get_settings(app);
+        (
+            if engine.trim().is_empty() { settings.translate_engine } else { engine },
+            if deepl_api_key.trim().is_empty() { settings.deepl_api_key } else { deepl_api_key },
+        )
+    } else {
+        (engine, deepl_api_key)
+    };
     eprintln!(
         "[tauri-cmd] translate_text: engine={}, src={}, tgt={}, len={}",
         engine, source_lang, target_lang, text.len()
@@ -0,0 +1,611 @@
+//! AI asistan — transkripti LLM ile duzenleme (dilbilgisi, ozet, resmilestirme).
+//!
+//! Her saglayici `LlmProvider` trait'ini uygular: Groq ve "openai" (OpenAI
+//! uyumlu herhangi bir sunucu) ayni chat/completions adaptorunu, Gemini ve
+//! Ollama kendi API'lerini kullanir. Adresler ayarlardan (`ollama_url`,
+//! `llm_base_url`) ya da istekteki `base_url` ile degistirilebilir; boylece
+//! yerel bir Ollama veya mock sunucu kullanilabilir.
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::future::Future;
+use std::time::Duration;
+
+const GROQ_BASE_URL: &str = "https://api.groq.com/openai/v1";
+const GEMINI_BASE_URL: &str = "https://generativelanguage.googleapis.com";
+
+const GROQ_DEFAULT_MODEL: &str = "llama-3.3-70b-versatile";
+const GEMINI_DEFAULT_MODEL: &str = "gemini-2.0-flash";
+const OPENAI_DEFAULT_MODEL: &str = "gpt-4o-mini";
+
+pub const PROVIDER_GROQ: &str = "groq";
+pub const PROVIDER_GEMINI: &str = "gemini";
+pub const PROVIDER_OLLAMA: &str = "ollama";
+pub const PROVIDER_OPENAI: &str = "openai";
+
+/// Yerel modeller ilk istekte diske yuklenir — uzun zaman asimi
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+/// Duzenleme gorevleri icin dusuk sicaklik (yaratici degil, sadik cikti)
+const TEMPERATURE: f32 = 0.2;
+/// "custom:" ile baslayan eylemde talimat dogrudan kullanilir
+const CUSTOM_ACTION_PREFIX: &str = "custom:";
+
+const SYSTEM_PROMPT: &str = "Sen bir metin duzenleme asistanisin. Sana verilen metni istenen \
+sekilde duzenle. Yalnizca duzenlenmis metni dondur; aciklama, baslik veya tirnak ekleme. \
+Aksi istenmedikce metnin dilini koru.";
+
+/// Eylem kimligi, arayuz etiketi ve talimat
+const ACTIONS: &[(&str, &str, &str)] = &[
+    (
+        "fix_grammar",
+        "Dilbilgisini duzelt",
+        "Yazim, noktalama ve dilbilgisi hatalarini duzelt. Anlami ve uslubu degistirme.",
+    ),
+    (
+        "summarize",
+        "Ozetle",
+        "Metni ana fikirleri koruyarak kisa bir paragrafta ozetle.",
+    ),
+    (
+        "make_formal",
+        "Resmilestir",
+        "Metni resmi ve profesyonel bir uslupla yeniden yaz.",
+    ),
+    (
+        "make_casual",
+        "Samimilestir",
+        "Metni samimi ve gunluk bir uslupla yeniden yaz.",
+    ),
+    (
+        "shorten",
+        "Kisalt",
+        "Metni anlamini kaybetmeden belirgin bicimde kisalt.",
+    ),
+    (
+        "expand",
+        "Genislet",
+        "Metni ayni uslupla daha ayrintili hale getir.",
+    ),
+    (
+        "bullet_points",
+        "Maddele",
+        "Metindeki fikirleri kisa madde isaretli bir listeye donustur.",
+    ),
+    (
+        "translate_english",
+        "Ingilizceye cevir",
+        "Metni dogal bir Ingilizceye cevir.",
+    ),
+];
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LLMRequest {
+    pub text: String,
+    /// `ACTIONS` kimliklerinden biri ya da "custom:<talimat>"
+    pub action: String,
+    /// Bos ise `AppSettings::ai_provider`
+    #[serde(default)]
+    pub provider: String,
+    #[serde(default)]
+    pub api_key: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Saglayici adresini gecersiz kil (yerel sunucu / test)
+    #[serde(default)]
+    pub base_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct LLMResponse {
+    pub text: String,
+    pub provider: String,
+    pub model: String,
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+    /// prompt + completion
+    pub tokens_used: u32,
+    /// Saglayici kullanim bilgisi dondurmediyse token sayilari tahmindir
+    pub tokens_estimated: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LLMAction {
+    pub id: String,
+    pub label: String,
+}
+
+/// Sistem + kullanici mesaji
+#[derive(Debug, Clone, PartialEq)]
+pub struct Prompt {
+    pub system: String,
+    pub user: String,
+}
+
+/// Saglayicinin ham cevabi; token sayilari bilinmiyorsa `None`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Completion {
+    pub text: String,
+    pub prompt_tokens: Option<u32>,
+    pub completion_tokens: Option<u32>,
+}
+
+pub trait LlmProvider {
+    fn name(&self) -> &'static str;
+
+    fn complete(
+        &self,
+        client: &reqwest::Client,
+        prompt: &Prompt,
+        model: &str,
+    ) -> impl Future<Output = Result<Completion, String>> + Send;
+}
+
+/// Eylemden prompt olustur
+pub fn build_prompt(action: &str, text: &str) -> Result<Prompt, String> {
+    let instruction = match action.strip_prefix(CUSTOM_ACTION_PREFIX) {
+        Some(custom) if !custom.trim().is_empty() => custom.trim().to_string(),
+        Some(_) => return Err("Ozel talimat bos".to_string()),
+        None => ACTIONS
+            .iter()
+            .find(|(id, _, _)| *id == action)
+            .map(|(_, _, instruction)| instruction.to_string())
+            .ok_or_else(|| format!("Bilinmeyen AI eylemi: {}", action))?,
+    };
+    Ok(Prompt {
+        system: SYSTEM_PROMPT.to_string(),
+        user: format!("{}\n\nMetin:\n{}", instruction, text.trim()),
+    })
+}
+
+/// Kaba token tahmini (~4 karakter/token)
+fn estimate_tokens(text: &str) -> u32 {
+    (text.chars().count() as u32).div_ceil(4)
+}
+
+fn http_error(provider: &str, status: reqwest::StatusCode, body: &str) -> String {
+    match status.as_u16() {
+        401 | 403 => format!("{} API anahtari gecersiz", provider),
+        429 => format!(
+            "{} istek limiti asildi, biraz sonra tekrar deneyin",
+            provider
+        ),
+        code => {
+            let detail: String = body.chars().take(200).collect();
+            format!("{} hatasi: HTTP {} {}", provider, code, detail.trim())
+        }
+    }
+}
+
+async fn post_json(
+    provider: &str,
+    request: reqwest::RequestBuilder,
+    body: serde_json::Value,
+) -> Result<serde_json::Value, String> {
+    let resp = request
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("{} istegi basarisiz: {}", provider, e))?;
+    let status = resp.status();
+    let text = resp
+        .text()
+        .await
+        .map_err(|e| format!("Yanit okunamadi: {}", e))?;
+    if !status.is_success() {
+        return Err(http_error(provider, status, &text));
+    }
+    serde_json::from_str(&text).map_err(|e| format!("{} yaniti cozulemedi: {}", provider, e))
+}
+
+fn as_tokens(value: &serde_json::Value) -> Option<u32> {
+    value.as_u64().map(|v| v as u32)
+}
+
+// ─── OpenAI Uyumlu (Groq, OpenAI, LM Studio, vLLM) ───
+
+pub struct OpenAiCompatible {
+    name: &'static str,
+    base_url: String,
+    api_key: String,
+}
+
+impl OpenAiCompatible {
+    pub fn new(name: &'static str, base_url: &str, api_key: &str) -> Self {
+        Self {
+            name,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.trim().to_string(),
+        }
+    }
+}
+
+fn parse_openai_response(json: &serde_json::Value) -> Result<Completion, String> {
+    let text = json["choices"][0]["message"]["content"]
+        .as_str()
+        .ok_or_else(|| "LLM yaniti bos".to_string())?;
+    Ok(Completion {
+        text: text.trim().to_string(),
+        prompt_tokens: as_tokens(&json["usage"]["prompt_tokens"]),
+        completion_tokens: as_tokens(&json["usage"]["completion_tokens"]),
+    })
+}
+
+impl LlmProvider for OpenAiCompatible {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    async fn complete(
+        &self,
+        client: &reqwest::Client,
+        prompt: &Prompt,
+        model: &str,
+    ) -> Result<Completion, String> {
+        let mut request = client.post(format!("{}/chat/completions", self.base_url));
+        // Yerel sunucular (LM Studio vb.) anahtar istemez
+        if !self.api_key.is_empty() {
+            request = request.bearer_auth(&self.api_key);
+        }
+        let body = json!({
+            "model": model,
+            "temperature": TEMPERATURE,
+            "messages": [
+                { "role": "system", "content": prompt.system },
+                { "role": "user", "content": prompt.user },
+            ],
+        });
+        parse_openai_response(&post_json(self.name, request, body).await?)
+    }
+}
+
+// ─── Gemini ───
+
+pub struct Gemini {
+    base_url: String,
+    api_key: String,
+}
+
+impl Gemini {
+    pub fn new(base_url: &str, api_key: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.trim().to_string(),
+        }
+    }
+}
+
+fn parse_gemini_response(json: &serde_json::Value) -> Result<Completion, String> {
+    let parts = json["candidates"][0]["content"]["parts"]
+        .as_array()
+        .ok_or_else(|| "Gemini yaniti bos (icerik filtresine takilmis olabilir)".to_string())?;
+    let text: String = parts.iter().filter_map(|p| p["text"].as_str()).collect();
+    Ok(Completion {
+        text: text.trim().to_string(),
+        prompt_tokens: as_tokens(&json["usageMetadata"]["promptTokenCount"]),
+        completion_tokens: as_tokens(&json["usageMetadata"]["candidatesTokenCount"]),
+    })
+}
+
+impl LlmProvider for Gemini {
+    fn name(&self) -> &'static str {
+        PROVIDER_GEMINI
+    }
+
+    async fn complete(
+        &self,
+        client: &reqwest::Client,
+        prompt: &Prompt,
+        model: &str,
+    ) -> Result<Completion, String> {
+        if self.api_key.is_empty() {
+            return Err("Gemini API anahtari gerekli".to_string());
+        }
+        let request = client
+            .post(format!(
+                "{}/v1beta/models/{}:generateContent",
+                self.base_url, model
+            ))
+            .header("x-goog-api-key", &self.api_key);
+        let body = json!({
+            "systemInstruction": { "parts": [{ "text": prompt.system }] },
+            "contents": [{ "role": "user", "parts": [{ "text": prompt.user }] }],
+            "generationConfig": { "temperature": TEMPERATURE },
+        });
+        parse_gemini_response(&post_json(PROVIDER_GEMINI, request, body).await?)
+    }
+}
+
+// ─── Ollama ───
+
+pub struct Ollama {
+    base_url: String,
+}
+
+impl Ollama {
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+fn parse_ollama_response(json: &serde_json::Value) -> Result<Completion, String> {
+    let text = json["message"]["content"]
+        .as_str()
+        .ok_or_else(|| "Ollama yaniti bos".to_string())?;
+    Ok(Completion {
+        text: text.trim().to_string(),
+        prompt_tokens: as_tokens(&json["prompt_eval_count"]),
+        completion_tokens: as_tokens(&json["eval_count"]),
+    })
+}
+
+impl LlmProvider for Ollama {
+    fn name(&self) -> &'static str {
+        PROVIDER_OLLAMA
+    }
+
+    async fn complete(
+        &self,
+        client: &reqwest::Client,
+        prompt: &Prompt,
+        model: &str,
+    ) -> Result<Completion, String> {
+        let request = client.post(format!("{}/api/chat", self.base_url));
+        let body = json!({
+            "model": model,
+            "stream": false,
+            "options": { "temperature": TEMPERATURE },
+            "messages": [
+                { "role": "system", "content": prompt.system },
+                { "role": "user", "content": prompt.user },
+            ],
+        });
+        post_json(PROVIDER_OLLAMA, request, body)
+            .await
+            .map_err(|e| format!("{} (Ollama calisiyor mu? `ollama serve`)", e))
+            .and_then(|json| parse_ollama_response(&json))
+    }
+}
+
+// ─── Ortak Giris Noktasi ───
+
+async fn run_provider<P: LlmProvider>(
+    provider: &P,
+    prompt: &Prompt,
+    model: &str,
+) -> Result<LLMResponse, String> {
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| format!("HTTP istemcisi olusturulamadi: {}", e))?;
+    let completion = provider.complete(&client, prompt, model).await?;
+    if completion.text.is_empty() {
+        return Err("LLM bos yanit dondu".to_string());
+    }
+
+    let tokens_estimated =
+        completion.prompt_tokens.is_none() || completion.completion_tokens.is_none();
+    let prompt_tokens = completion
+        .prompt_tokens
+        .unwrap_or_else(|| estimate_tokens(&prompt.system) + estimate_tokens(&prompt.user));
+    let completion_tokens = completion
+        .completion_tokens
+        .unwrap_or_else(|| estimate_tokens(&completion.text));
+
+    Ok(LLMResponse {
+        text: completion.text,
+        provider: provider.name().to_string(),
+        model: model.to_string(),
+        prompt_tokens,
+        completion_tokens,
+        tokens_used: prompt_tokens + completion_tokens,
+        tokens_estimated,
+    })
+}
+
+/// Istegi ayarlarla tamamlayip ilgili saglayiciya gonder
+pub async fn process(
+    request: &LLMRequest,
+    settings: &crate::settings::AppSettings,
+) -> Result<LLMResponse, String> {
+    if request.text.trim().is_empty() {
+        return Err("Islenecek metin bos".to_string());
+    }
+    let prompt = build_prompt(&request.action, &request.text)?;
+
+    let provider = if request.provider.trim().is_empty() {
+        settings.ai_provider.trim().to_lowercase()
+    } else {
+        request.provider.trim().to_lowercase()
+    };
+    let pick = |override_value: &Option<String>, fallback: &str| {
+        override_value
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .unwrap_or(fallback)
+            .to_string()
+    };
+    let base_url = |fallback: &str| pick(&request.base_url, fallback);
+
+    match provider.as_str() {
+        PROVIDER_GROQ => {
+            let api_key = pick(&request.api_key, &settings.groq_api_key);
+            if api_key.is_empty() {
+                return Err("Groq API anahtari gerekli".to_string());
+            }
+            let engine = OpenAiCompatible::new(PROVIDER_GROQ, &base_url(GROQ_BASE_URL), &api_key);
+            run_provider(&engine, &prompt, &pick(&request.model, GROQ_DEFAULT_MODEL)).await
+        }
+        PROVIDER_OPENAI => {
+            let engine = OpenAiCompatible::new(
+                PROVIDER_OPENAI,
+                &base_url(&settings.llm_base_url),
+                &pick(&request.api_key, ""),
+            );
+            run_provider(
+                &engine,
+                &prompt,
+                &pick(&request.model, OPENAI_DEFAULT_MODEL),
+            )
+            .await
+        }
+        PROVIDER_GEMINI => {
+            let engine = Gemini::new(
+                &base_url(GEMINI_BASE_URL),
+                &pick(&request.api_key, &settings.gemini_api_key),
+            );
+            run_provider(
+                &engine,
+                &prompt,
+                &pick(&request.model, GEMINI_DEFAULT_MODEL),
+            )
+            .await
+        }
+        PROVIDER_OLLAMA => {
+            let engine = Ollama::new(&base_url(&settings.ollama_url));
+            run_provider(
+                &engine,
+                &prompt,
+                &pick(&request.model, &settings.ollama_model),
+            )
+            .await
+        }
+        other => Err(format!("Bilinmeyen AI saglayicisi: {}", other)),
+    }
+}
+
+// ─── Tauri Komutlari ───
+
+#[tauri::command]
+pub async fn process_with_llm(
+    app: tauri::AppHandle,
+    request: LLMRequest,
+) -> Result<LLMResponse, String> {
+    let settings = crate::commands::settings::get_settings(app);
+    let response = process(&request, &settings).await?;
+    log::info!(
+        "AI eylemi '{}' tamamlandi: {} / {} ({} token)",
+        request.action,
+        response.provider,
+        response.model,
+        response.tokens_used
+    );
+    Ok(response)
+}
+
+#[tauri::command]
+pub fn get_llm_actions() -> Vec<LLMAction> {
+    ACTIONS
+        .iter()
+        .map(|(id, label, _)| LLMAction {
+            id: id.to_string(),
+            label: label.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::mock_server;
+
+    fn request(provider: &str, base_url: &str) -> LLMRequest {
+        LLMRequest {
+            text: "bugun toplanti yapildi".to_string(),
+            action: "fix_grammar".to_string(),
+            provider: provider.to_string(),
+            api_key: Some("test-key".to_string()),
+            model: None,
+            base_url: Some(base_url.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_build_prompt() {
+        let prompt = build_prompt("summarize", "  uzun metin  ").unwrap();
+        assert!(prompt.user.starts_with("Metni ana fikirleri"));
+        assert!(prompt.user.ends_with("Metin:\nuzun metin"));
+
+        let custom = build_prompt("custom: Emoji ekle", "selam").unwrap();
+        assert!(custom.user.starts_with("Emoji ekle"));
+        assert!(build_prompt("custom:", "selam").is_err());
+        assert!(build_prompt("bilinmeyen", "selam").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_groq_against_mock() {
+        let (url, req) = mock_server(
+            200,
+            r#"{"choices":[{"message":{"role":"assistant","content":" Bugün toplantı yapıldı. "}}],"usage":{"prompt_tokens":42,"completion_tokens":7}}"#,
+        )
+        .await;
+        let resp = process(
+            &request(PROVIDER_GROQ, &url),
+            &crate::settings::AppSettings::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(resp.text, "Bugün toplantı yapıldı.");
+        assert_eq!(
+            (resp.prompt_tokens, resp.completion_tokens, resp.tokens_used),
+            (42, 7, 49)
+        );
+        assert!(!resp.tokens_estimated);
+        assert_eq!(resp.model, GROQ_DEFAULT_MODEL);
+
+        let req = req.await.unwrap();
+        assert!(req.starts_with("POST /chat/completions"));
+        assert!(req
+            .to_lowercase()
+            .contains("authorization: bearer test-key"));
+    }
+
+    #[tokio::test]
+    async fn test_gemini_against_mock() {
+        let (url, req) = mock_server(
+            200,
+            r#"{"candidates":[{"content":{"parts":[{"text":"Özet"}]}}],"usageMetadata":{"promptTokenCount":30,"candidatesTokenCount":2}}"#,
+        )
+        .await;
+        let resp = process(
+            &request(PROVIDER_GEMINI, &url),
+            &crate::settings::AppSettings::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(resp.text, "Özet");
+        assert_eq!(resp.tokens_used, 32);
+
+        let req = req.await.unwrap();
+        assert!(req.starts_with(&format!(
+            "POST /v1beta/models/{}:generateContent",
+            GEMINI_DEFAULT_MODEL
+        )));
+        assert!(req.contains("x-goog-api-key: test-key"));
+    }
+
+    #[tokio::test]
+    async fn test_ollama_against_mock_estimates_missing_usage() {
+        let (url, req) = mock_server(
+            200,
+            r#"{"message":{"role":"assistant","content":"Bugün toplantı yapıldı."},"done":true}"#,
+        )
+        .await;
+        let settings = crate::settings::AppSettings {
+            ollama_url: url,
+            ..Default::default()
+        };
+        let mut llm_request = request(PROVIDER_OLLAMA, "");
+        llm_request.base_url = None;
+
+        let resp = process(&llm_request, &settings).await.unwrap();
+        assert_eq!(resp.model, settings.ollama_model);
+        assert!(resp.tokens_estimated && resp.completion_tokens > 0);
+
+        let req = req.await.unwrap();
+        assert!(req.starts_with("POST /api/chat"));
+        assert!(req.contains("\"stream\":false"));
+    }
+}
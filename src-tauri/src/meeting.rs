@@ -0,0 +1,794 @@
+//! Toplanti modu — zaman damgali transkript parcalari, otomatik bolumleme,
+//! konusmaci atama/istatistikleri, notlar ve biten toplantilarin diske kaydi.
+//!
+//! Bolum (chapter) sinirlari uc sinyalden cikarilir:
+//! 1. Uzun sessizlik (parcalar arasi bosluk)
+//! 2. Gecis ifadeleri ("sonraki konu", "next topic" ...)
+//! 3. Konu kaymasi — son parcalarin anahtar kelimeleri bolumle ortusmuyorsa
+//!
+//! Saf mantik `Meeting` yapisinda; Tauri komutlari bu yapiyi global state
+//! uzerinden kullanir, boylece bolumleme ve istatistikler test edilebilir.
+
+use crate::export::{ExportSegment, TranscriptDocument};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tauri::Emitter;
+
+/// Parcalar arasi bu kadar saniye sessizlik yeni bolum baslatir
+const CHAPTER_GAP_SECS: f64 = 60.0;
+/// Konu kaymasi ancak bu uzunluktaki bolumlerde aranir
+const CHAPTER_MIN_SECS: f64 = 180.0;
+/// Bu uzunlugu asan bolum bir sonraki parcada kapatilir
+const CHAPTER_MAX_SECS: f64 = 15.0 * 60.0;
+/// Konu kaymasi icin karsilastirilan son parca sayisi
+const TOPIC_WINDOW_CHUNKS: usize = 3;
+/// Pencere anahtar kelimelerinin bolumle ortusme orani bunun altindaysa konu degismistir
+const TOPIC_SHIFT_OVERLAP: f64 = 0.1;
+/// Konu kaymasi karari icin penceredeki minimum anahtar kelime
+const TOPIC_MIN_KEYWORDS: usize = 5;
+/// Bolum basliginda kullanilan anahtar kelime sayisi
+const TITLE_KEYWORDS: usize = 3;
+
+/// Yeni bolum baslatan gecis ifadeleri (kucuk harf)
+const CHAPTER_CUES: &[&str] = &[
+    "sonraki konu",
+    "bir sonraki konu",
+    "diğer konuya",
+    "gündemin sonraki maddesi",
+    "gündemdeki sonraki madde",
+    "konuya geçelim",
+    "next topic",
+    "next item",
+    "moving on",
+    "let's move on",
+];
+
+/// Anahtar kelime cikariminda atlanan Ingilizce kelimeler
+const ENGLISH_STOPWORDS: &[&str] = &[
+    "that", "this", "with", "have", "from", "they", "will", "would", "there", "their",
+    "what", "about", "which", "when", "were", "been", "then", "than", "them", "just",
+    "like", "also", "into", "some", "could", "should", "yeah", "okay",
+];
+
+const UNKNOWN_SPEAKER: &str = "Bilinmeyen";
+
+// ─── Veri Yapilari ───
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptChunk {
+    pub id: u32,
+    pub text: String,
+    /// Toplanti basindan itibaren saniye
+    pub start_time: f64,
+    pub end_time: f64,
+    pub chapter_id: u32,
+    #[serde(default)]
+    pub speaker: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Chapter {
+    pub id: u32,
+    pub title: String,
+    pub start_time: f64,
+    pub end_time: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MeetingState {
+    pub is_active: bool,
+    /// Unix zaman damgasi (ms)
+    pub start_time: u64,
+    /// Saniye
+    pub total_duration: f64,
+    pub chunks: Vec<TranscriptChunk>,
+    pub chapters: Vec<Chapter>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SpeakerStats {
+    pub speaker: String,
+    pub chunk_count: usize,
+    pub word_count: usize,
+    /// Saniye
+    pub talk_time: f64,
+    /// Toplam konusma suresi icindeki pay (0..1)
+    pub share: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MeetingSummary {
+    pub id: String,
+    pub start_time: u64,
+    pub total_duration: f64,
+    pub chunk_count: usize,
+    pub chapter_titles: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct MeetingChunkEvent {
+    chunk: TranscriptChunk,
+    chapter: Chapter,
+    new_chapter: bool,
+}
+
+/// Tek bir toplanti — aktif oturum ve diske kaydedilen kayit ayni yapi
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Meeting {
+    pub id: String,
+    /// Unix zaman damgasi (ms)
+    pub start_time: u64,
+    /// Bitis zamani (ms); `None` ise toplanti suruyor
+    #[serde(default)]
+    pub end_time: Option<u64>,
+    pub chunks: Vec<TranscriptChunk>,
+    pub chapters: Vec<Chapter>,
+    #[serde(default)]
+    pub notes: String,
+}
+
+impl Meeting {
+    pub fn new(start_time: u64) -> Self {
+        Self {
+            id: format!("meeting-{}", start_time),
+            start_time,
+            end_time: None,
+            chunks: Vec::new(),
+            chapters: Vec::new(),
+            notes: String::new(),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.end_time.is_none()
+    }
+
+    /// Toplam sure (saniye) — aktif toplantida `now_ms`'e kadar
+    pub fn total_duration(&self, now_ms: u64) -> f64 {
+        let end = self.end_time.unwrap_or(now_ms);
+        end.saturating_sub(self.start_time) as f64 / 1000.0
+    }
+
+    pub fn state(&self, now_ms: u64) -> MeetingState {
+        MeetingState {
+            is_active: self.is_active(),
+            start_time: self.start_time,
+            total_duration: self.total_duration(now_ms),
+            chunks: self.chunks.clone(),
+            chapters: self.chapters.clone(),
+        }
+    }
+
+    /// Parca ekle; gerekirse yeni bolum baslat. Eklenen parca ve yeni bolum
+    /// baslayip baslamadigi doner.
+    pub fn add_chunk(
+        &mut self,
+        text: &str,
+        start_time: f64,
+        end_time: f64,
+        speaker: Option<String>,
+    ) -> Result<(TranscriptChunk, bool), String> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err("Bos parca eklenemez".to_string());
+        }
+        let start_time = start_time.max(0.0);
+        let end_time = end_time.max(start_time);
+
+        let new_chapter = self.should_start_chapter(text, start_time, end_time);
+        if new_chapter {
+            let id = self.chapters.len() as u32 + 1;
+            self.chapters.push(Chapter {
+                id,
+                title: format!("Bölüm {}", id),
+                start_time,
+                end_time,
+            });
+        }
+
+        let chapter_id = self.chapters.last().map(|c| c.id).unwrap_or(1);
+        let chunk = TranscriptChunk {
+            id: self.chunks.last().map(|c| c.id + 1).unwrap_or(1),
+            text: text.to_string(),
+            start_time,
+            end_time,
+            chapter_id,
+            speaker: speaker.filter(|s| !s.trim().is_empty()),
+        };
+        self.chunks.push(chunk.clone());
+
+        if let Some(chapter) = self.chapters.last_mut() {
+            chapter.end_time = chapter.end_time.max(end_time);
+        }
+        self.retitle_chapter(chapter_id);
+
+        Ok((chunk, new_chapter))
+    }
+
+    fn should_start_chapter(&self, text: &str, start_time: f64, end_time: f64) -> bool {
+        let (Some(chapter), Some(last)) = (self.chapters.last(), self.chunks.last()) else {
+            return true;
+        };
+
+        // 1. Uzun sessizlik
+        if start_time - last.end_time >= CHAPTER_GAP_SECS {
+            return true;
+        }
+
+        // 2. Gecis ifadesi
+        let lower = text.to_lowercase();
+        if CHAPTER_CUES.iter().any(|cue| lower.contains(cue)) {
+            return true;
+        }
+
+        // Cok uzun bolumu kapat
+        let chapter_len = end_time - chapter.start_time;
+        if chapter_len >= CHAPTER_MAX_SECS {
+            return true;
+        }
+        if chapter_len < CHAPTER_MIN_SECS {
+            return false;
+        }
+
+        // 3. Konu kaymasi — son parcalar + yeni metin, bolumun geri kalaniyla karsilastirilir
+        let chapter_chunks: Vec<&TranscriptChunk> =
+            self.chunks.iter().filter(|c| c.chapter_id == chapter.id).collect();
+        if chapter_chunks.len() <= TOPIC_WINDOW_CHUNKS {
+            return false;
+        }
+        let split = chapter_chunks.len() + 1 - TOPIC_WINDOW_CHUNKS;
+        let earlier: String = chapter_chunks[..split].iter().map(|c| c.text.as_str()).collect::<Vec<_>>().join(" ");
+        let recent: String = chapter_chunks[split..]
+            .iter()
+            .map(|c| c.text.as_str())
+            .chain(std::iter::once(text))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        topic_overlap(&earlier, &recent).is_some_and(|overlap| overlap < TOPIC_SHIFT_OVERLAP)
+    }
+
+    fn retitle_chapter(&mut self, chapter_id: u32) {
+        let text: String = self
+            .chunks
+            .iter()
+            .filter(|c| c.chapter_id == chapter_id)
+            .map(|c| c.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        if let Some(chapter) = self.chapters.iter_mut().find(|c| c.id == chapter_id) {
+            chapter.title = chapter_title(&text).unwrap_or_else(|| format!("Bölüm {}", chapter_id));
+        }
+    }
+
+    pub fn set_speaker(&mut self, chunk_id: u32, speaker: &str) -> Result<(), String> {
+        let chunk = self
+            .chunks
+            .iter_mut()
+            .find(|c| c.id == chunk_id)
+            .ok_or_else(|| format!("Parca bulunamadi: {}", chunk_id))?;
+        let speaker = speaker.trim();
+        chunk.speaker = if speaker.is_empty() { None } else { Some(speaker.to_string()) };
+        Ok(())
+    }
+
+    /// Konusma suresine gore sirali konusmaci istatistikleri
+    pub fn speaker_stats(&self) -> Vec<SpeakerStats> {
+        let mut by_speaker: Vec<SpeakerStats> = Vec::new();
+        for chunk in &self.chunks {
+            let name = chunk.speaker.as_deref().unwrap_or(UNKNOWN_SPEAKER);
+            let idx = match by_speaker.iter().position(|s| s.speaker == name) {
+                Some(idx) => idx,
+                None => {
+                    by_speaker.push(SpeakerStats {
+                        speaker: name.to_string(),
+                        chunk_count: 0,
+                        word_count: 0,
+                        talk_time: 0.0,
+                        share: 0.0,
+                    });
+                    by_speaker.len() - 1
+                }
+            };
+            let stats = &mut by_speaker[idx];
+            stats.chunk_count += 1;
+            stats.word_count += chunk.text.split_whitespace().count();
+            stats.talk_time += chunk.end_time - chunk.start_time;
+        }
+
+        let total: f64 = by_speaker.iter().map(|s| s.talk_time).sum();
+        for stats in &mut by_speaker {
+            stats.share = if total > 0.0 { stats.talk_time / total } else { 0.0 };
+        }
+        by_speaker.sort_by(|a, b| b.talk_time.total_cmp(&a.talk_time));
+        by_speaker
+    }
+
+    /// Bolum basliklari, zaman damgalari ve konusmacilarla okunabilir transkript
+    pub fn transcript(&self) -> String {
+        let mut out = String::new();
+        for chapter in &self.chapters {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&format!(
+                "## {} ({} - {})\n",
+                chapter.title,
+                format_clock(chapter.start_time),
+                format_clock(chapter.end_time)
+            ));
+            for chunk in self.chunks.iter().filter(|c| c.chapter_id == chapter.id) {
+                match &chunk.speaker {
+                    Some(speaker) => out.push_str(&format!(
+                        "[{}] {}: {}\n",
+                        format_clock(chunk.start_time),
+                        speaker,
+                        chunk.text
+                    )),
+                    None => out.push_str(&format!("[{}] {}\n", format_clock(chunk.start_time), chunk.text)),
+                }
+            }
+        }
+        out
+    }
+
+    /// Disa aktarma belgesi (SRT/VTT/JSON/TXT)
+    pub fn to_document(&self) -> TranscriptDocument {
+        TranscriptDocument {
+            title: self.chapters.first().map(|_| format!("Toplantı {}", self.id)),
+            language: String::new(),
+            segments: self
+                .chunks
+                .iter()
+                .map(|c| ExportSegment {
+                    start_ms: (c.start_time * 1000.0) as u64,
+                    end_ms: (c.end_time * 1000.0) as u64,
+                    text: c.text.clone(),
+                    speaker: c.speaker.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    fn summary(&self, now_ms: u64) -> MeetingSummary {
+        MeetingSummary {
+            id: self.id.clone(),
+            start_time: self.start_time,
+            total_duration: self.total_duration(now_ms),
+            chunk_count: self.chunks.len(),
+            chapter_titles: self.chapters.iter().map(|c| c.title.clone()).collect(),
+        }
+    }
+}
+
+// ─── Anahtar Kelime / Baslik ───
+
+/// Turkce ozel harfleri ASCII'ye indir (stop-word listesi ASCII)
+fn fold_ascii(word: &str) -> String {
+    word.chars()
+        .map(|c| match c {
+            'ç' => 'c',
+            'ğ' => 'g',
+            'ı' => 'i',
+            'ö' => 'o',
+            'ş' => 's',
+            'ü' => 'u',
+            other => other,
+        })
+        .collect()
+}
+
+/// Turkce buyuk/kucuk harf kurallariyla kucult (I → ı, İ → i)
+fn turkish_lowercase(word: &str) -> String {
+    word.chars()
+        .flat_map(|c| match c {
+            'I' => vec!['ı'],
+            'İ' => vec!['i'],
+            other => other.to_lowercase().collect(),
+        })
+        .collect()
+}
+
+fn turkish_capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some('i') => format!("İ{}", chars.as_str()),
+        Some('ı') => format!("I{}", chars.as_str()),
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Icerik kelimeleri: 4+ harf, stop-word degil
+fn keywords(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= 4)
+        .map(turkish_lowercase)
+        .filter(|w| !w.chars().all(|c| c.is_numeric()))
+        .filter(|w| {
+            !crate::corrections::is_turkish_stopword(&fold_ascii(w))
+                && !ENGLISH_STOPWORDS.contains(&w.as_str())
+        })
+        .collect()
+}
+
+/// `recent` anahtar kelimelerinin `earlier` icinde gecme orani.
+/// Yeterli anahtar kelime yoksa `None` (karar verilemez).
+fn topic_overlap(earlier: &str, recent: &str) -> Option<f64> {
+    let earlier: std::collections::HashSet<String> = keywords(earlier).into_iter().collect();
+    let recent: std::collections::HashSet<String> = keywords(recent).into_iter().collect();
+    if recent.len() < TOPIC_MIN_KEYWORDS || earlier.is_empty() {
+        return None;
+    }
+    let shared = recent.iter().filter(|w| earlier.contains(*w)).count();
+    Some(shared as f64 / recent.len() as f64)
+}
+
+/// En sik gecen anahtar kelimelerden bolum basligi
+fn chapter_title(text: &str) -> Option<String> {
+    let words = keywords(text);
+    let mut counts: HashMap<&str, (usize, usize)> = HashMap::new();
+    for (idx, word) in words.iter().enumerate() {
+        let entry = counts.entry(word.as_str()).or_insert((0, idx));
+        entry.0 += 1;
+    }
+
+    let mut ranked: Vec<(&str, (usize, usize))> = counts.into_iter().collect();
+    // Sikliga gore, esitlikte ilk gorulme sirasina gore
+    ranked.sort_by(|a, b| b.1 .0.cmp(&a.1 .0).then(a.1 .1.cmp(&b.1 .1)));
+
+    let title: Vec<String> = ranked
+        .into_iter()
+        .take(TITLE_KEYWORDS)
+        .map(|(w, _)| turkish_capitalize(w))
+        .collect();
+    if title.is_empty() { None } else { Some(title.join(", ")) }
+}
+
+fn format_clock(secs: f64) -> String {
+    let total = secs.max(0.0) as u64;
+    format!("{:02}:{:02}:{:02}", total / 3600, (total % 3600) / 60, total % 60)
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+// ─── Global State ───
+
+/// Aktif toplanti ya da en son biten toplanti (notlar sonradan eklenebilsin diye)
+static SESSION: Mutex<Option<Meeting>> = Mutex::new(None);
+
+// ─── Dosya Yollari ───
+
+fn meetings_dir(app_handle: &tauri::AppHandle) -> PathBuf {
+    use tauri::Manager;
+    app_handle
+        .path()
+        .app_data_dir()
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join("meetings")
+}
+
+fn meeting_path(app_handle: &tauri::AppHandle, id: &str) -> Result<PathBuf, String> {
+    // Kimlik dosya adi olarak kullanilir — dizin disina cikmasin
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(format!("Gecersiz toplanti kimligi: {}", id));
+    }
+    Ok(meetings_dir(app_handle).join(format!("{}.json", id)))
+}
+
+fn save_meeting(app_handle: &tauri::AppHandle, meeting: &Meeting) -> Result<(), String> {
+    let path = meeting_path(app_handle, &meeting.id)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Toplanti dizini olusturulamadi: {}", e))?;
+    }
+    let json = serde_json::to_string_pretty(meeting).map_err(|e| format!("Toplanti serilestirilemedi: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Toplanti kaydedilemedi: {}", e))
+}
+
+pub fn load_meeting(app_handle: &tauri::AppHandle, id: &str) -> Result<Meeting, String> {
+    let path = meeting_path(app_handle, id)?;
+    let json = std::fs::read_to_string(&path).map_err(|e| format!("Toplanti okunamadi: {}", e))?;
+    serde_json::from_str(&json).map_err(|e| format!("Toplanti dosyasi bozuk: {}", e))
+}
+
+/// Aktif/son toplantiyi ya da kayitli bir toplantiyi dondur
+pub fn find_meeting(app_handle: &tauri::AppHandle, id: Option<&str>) -> Result<Meeting, String> {
+    let current = SESSION.lock().clone();
+    match (id, current) {
+        (None, Some(meeting)) => Ok(meeting),
+        (None, None) => Err("Toplanti bulunamadi".to_string()),
+        (Some(id), Some(meeting)) if meeting.id == id => Ok(meeting),
+        (Some(id), _) => load_meeting(app_handle, id),
+    }
+}
+
+/// Aktif toplantiya parca ekle (canli ceviri gibi diger kaynaklar da kullanir)
+pub fn add_chunk(
+    app_handle: &tauri::AppHandle,
+    text: &str,
+    start_time: f64,
+    end_time: f64,
+    speaker: Option<String>,
+) -> Result<(), String> {
+    let (event, snapshot) = {
+        let mut session = SESSION.lock();
+        let meeting = session
+            .as_mut()
+            .filter(|m| m.is_active())
+            .ok_or_else(|| "Aktif toplanti yok".to_string())?;
+
+        let (chunk, new_chapter) = meeting.add_chunk(text, start_time, end_time, speaker)?;
+        let chapter = meeting
+            .chapters
+            .iter()
+            .find(|c| c.id == chunk.chapter_id)
+            .cloned()
+            .ok_or_else(|| "Bolum bulunamadi".to_string())?;
+        // Yeni bolumde ara kayit — uygulama cokerse toplanti kaybolmasin
+        let snapshot = if new_chapter { Some(meeting.clone()) } else { None };
+        (MeetingChunkEvent { chunk, chapter, new_chapter }, snapshot)
+    };
+
+    if let Some(meeting) = snapshot {
+        if let Err(e) = save_meeting(app_handle, &meeting) {
+            log::warn!("Toplanti ara kaydi basarisiz: {}", e);
+        }
+    }
+    app_handle.emit("meeting-chunk-added", event).ok();
+    Ok(())
+}
+
+// ─── Tauri Komutlari ───
+
+#[tauri::command]
+pub fn start_meeting() -> MeetingState {
+    let mut session = SESSION.lock();
+    if let Some(meeting) = session.as_ref().filter(|m| m.is_active()) {
+        // Zaten suruyorsa mevcut durumu dondur
+        return meeting.state(now_ms());
+    }
+
+    let meeting = Meeting::new(now_ms());
+    log::info!("Toplanti basladi: {}", meeting.id);
+    let state = meeting.state(now_ms());
+    *session = Some(meeting);
+    state
+}
+
+#[tauri::command]
+pub fn stop_meeting(app_handle: tauri::AppHandle) -> Result<MeetingState, String> {
+    let meeting = {
+        let mut session = SESSION.lock();
+        let meeting = session
+            .as_mut()
+            .filter(|m| m.is_active())
+            .ok_or_else(|| "Aktif toplanti yok".to_string())?;
+        meeting.end_time = Some(now_ms());
+        meeting.clone()
+    };
+
+    if meeting.chunks.is_empty() {
+        log::info!("Toplanti bos bitti, kaydedilmedi: {}", meeting.id);
+    } else {
+        save_meeting(&app_handle, &meeting)?;
+        log::info!(
+            "Toplanti kaydedildi: {} ({} parca, {} bolum)",
+            meeting.id,
+            meeting.chunks.len(),
+            meeting.chapters.len()
+        );
+    }
+
+    Ok(meeting.state(now_ms()))
+}
+
+#[tauri::command]
+pub fn add_meeting_chunk(
+    app_handle: tauri::AppHandle,
+    text: String,
+    start_time: f64,
+    end_time: f64,
+    speaker: Option<String>,
+) -> Result<(), String> {
+    add_chunk(&app_handle, &text, start_time, end_time, speaker)
+}
+
+#[tauri::command]
+pub fn get_meeting_state() -> MeetingState {
+    match SESSION.lock().as_ref() {
+        Some(meeting) => meeting.state(now_ms()),
+        None => MeetingState {
+            is_active: false,
+            start_time: 0,
+            total_duration: 0.0,
+            chunks: Vec::new(),
+            chapters: Vec::new(),
+        },
+    }
+}
+
+#[tauri::command]
+pub fn get_meeting_transcript(app_handle: tauri::AppHandle, meeting_id: Option<String>) -> Result<String, String> {
+    Ok(find_meeting(&app_handle, meeting_id.as_deref())?.transcript())
+}
+
+#[tauri::command]
+pub fn get_meeting_speaker_stats(
+    app_handle: tauri::AppHandle,
+    meeting_id: Option<String>,
+) -> Result<Vec<SpeakerStats>, String> {
+    Ok(find_meeting(&app_handle, meeting_id.as_deref())?.speaker_stats())
+}
+
+#[tauri::command]
+pub fn update_chunk_speaker(app_handle: tauri::AppHandle, chunk_id: u32, speaker: String) -> Result<(), String> {
+    let finished = {
+        let mut session = SESSION.lock();
+        let meeting = session.as_mut().ok_or_else(|| "Toplanti bulunamadi".to_string())?;
+        meeting.set_speaker(chunk_id, &speaker)?;
+        (!meeting.is_active() && !meeting.chunks.is_empty()).then(|| meeting.clone())
+    };
+    // Bitmis toplantida degisiklik diskteki kayda da yansisin
+    if let Some(meeting) = finished {
+        save_meeting(&app_handle, &meeting)?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_meeting_notes(app_handle: tauri::AppHandle, notes: String) -> Result<(), String> {
+    let finished = {
+        let mut session = SESSION.lock();
+        let meeting = session.as_mut().ok_or_else(|| "Toplanti bulunamadi".to_string())?;
+        meeting.notes = notes;
+        (!meeting.is_active() && !meeting.chunks.is_empty()).then(|| meeting.clone())
+    };
+    if let Some(meeting) = finished {
+        save_meeting(&app_handle, &meeting)?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_meeting_notes() -> String {
+    SESSION.lock().as_ref().map(|m| m.notes.clone()).unwrap_or_default()
+}
+
+/// Diske kaydedilmis toplantilar (en yeni once)
+#[tauri::command]
+pub fn list_meetings(app_handle: tauri::AppHandle) -> Vec<MeetingSummary> {
+    let Ok(entries) = std::fs::read_dir(meetings_dir(&app_handle)) else {
+        return Vec::new();
+    };
+
+    let now = now_ms();
+    let mut summaries: Vec<MeetingSummary> = entries
+        .flatten()
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|e| std::fs::read_to_string(e.path()).ok())
+        .filter_map(|json| serde_json::from_str::<Meeting>(&json).ok())
+        .map(|m| m.summary(now))
+        .collect();
+    summaries.sort_by(|a, b| b.start_time.cmp(&a.start_time));
+    summaries
+}
+
+#[tauri::command]
+pub fn get_saved_meeting(app_handle: tauri::AppHandle, meeting_id: String) -> Result<Meeting, String> {
+    load_meeting(&app_handle, &meeting_id)
+}
+
+#[tauri::command]
+pub fn delete_saved_meeting(app_handle: tauri::AppHandle, meeting_id: String) -> Result<(), String> {
+    let path = meeting_path(&app_handle, &meeting_id)?;
+    std::fs::remove_file(&path).map_err(|e| format!("Toplanti silinemedi: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meeting_with(chunks: &[(&str, f64, f64)]) -> Meeting {
+        let mut meeting = Meeting::new(0);
+        for (text, start, end) in chunks {
+            meeting.add_chunk(text, *start, *end, None).unwrap();
+        }
+        meeting
+    }
+
+    #[test]
+    fn test_first_chunk_opens_chapter() {
+        let meeting = meeting_with(&[("Bütçe görüşmesine başlıyoruz", 0.0, 4.0)]);
+        assert_eq!(meeting.chapters.len(), 1);
+        assert_eq!(meeting.chunks[0].chapter_id, 1);
+        assert_eq!(meeting.chapters[0].end_time, 4.0);
+    }
+
+    #[test]
+    fn test_long_gap_starts_new_chapter() {
+        let meeting = meeting_with(&[
+            ("Bütçe görüşmesine başlıyoruz", 0.0, 4.0),
+            ("Bütçe onaylandı", 5.0, 8.0),
+            ("Tekrar hoş geldiniz", 90.0, 93.0),
+        ]);
+        assert_eq!(meeting.chapters.len(), 2);
+        assert_eq!(meeting.chunks[2].chapter_id, 2);
+        assert_eq!(meeting.chapters[1].start_time, 90.0);
+    }
+
+    #[test]
+    fn test_cue_phrase_starts_new_chapter() {
+        let meeting = meeting_with(&[
+            ("Sprint planlaması tamamlandı", 0.0, 4.0),
+            ("Sonraki konu müşteri şikayetleri", 5.0, 8.0),
+        ]);
+        assert_eq!(meeting.chapters.len(), 2);
+    }
+
+    #[test]
+    fn test_topic_shift_after_min_length() {
+        let mut chunks: Vec<(String, f64, f64)> = (0..6)
+            .map(|i| {
+                ("Bütçe harcamaları bütçe raporu finans tablosu".to_string(), i as f64 * 40.0, i as f64 * 40.0 + 30.0)
+            })
+            .collect();
+        chunks.push(("Sunucu kurulumu veritabanı yedekleme altyapısı güvenlik duvarı".to_string(), 240.0, 270.0));
+        chunks.push(("Sunucu kurulumu veritabanı yedekleme altyapısı güvenlik duvarı".to_string(), 275.0, 300.0));
+        chunks.push(("Sunucu kurulumu veritabanı yedekleme altyapısı güvenlik duvarı".to_string(), 305.0, 330.0));
+
+        let mut meeting = Meeting::new(0);
+        for (text, start, end) in &chunks {
+            meeting.add_chunk(text, *start, *end, None).unwrap();
+        }
+        assert_eq!(meeting.chapters.len(), 2);
+        assert!(meeting.chapters[1].title.contains("Sunucu"), "{}", meeting.chapters[1].title);
+    }
+
+    #[test]
+    fn test_chapter_title_uses_frequent_keywords() {
+        let title = chapter_title("İstanbul ofisi ve bütçe. Bütçe planı, bütçe onayı ve İstanbul ofisi").unwrap();
+        assert_eq!(title, "Bütçe, İstanbul, Ofisi");
+    }
+
+    #[test]
+    fn test_speaker_stats() {
+        let mut meeting = meeting_with(&[
+            ("bir iki üç", 0.0, 10.0),
+            ("dört beş", 10.0, 15.0),
+            ("altı", 15.0, 20.0),
+        ]);
+        meeting.set_speaker(1, "Ayşe").unwrap();
+        meeting.set_speaker(3, "Ayşe").unwrap();
+        let stats = meeting.speaker_stats();
+        assert_eq!(stats[0].speaker, "Ayşe");
+        assert_eq!(stats[0].word_count, 4);
+        assert_eq!(stats[0].chunk_count, 2);
+        assert!((stats[0].share - 0.75).abs() < 1e-9);
+        assert_eq!(stats[1].speaker, UNKNOWN_SPEAKER);
+        assert!(meeting.set_speaker(99, "Ali").is_err());
+    }
+
+    #[test]
+    fn test_transcript_format() {
+        let mut meeting = meeting_with(&[("Merhaba arkadaşlar", 65.0, 68.0)]);
+        meeting.set_speaker(1, "Ali").unwrap();
+        let transcript = meeting.transcript();
+        assert!(transcript.contains("[00:01:05] Ali: Merhaba arkadaşlar"), "{}", transcript);
+    }
+
+    #[test]
+    fn test_meeting_roundtrip_json() {
+        let meeting = meeting_with(&[("Toplantı notları", 0.0, 2.0)]);
+        let json = serde_json::to_string(&meeting).unwrap();
+        let restored: Meeting = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.chunks.len(), 1);
+        assert_eq!(restored.chapters[0].title, meeting.chapters[0].title);
+    }
+}
@@ -15,6 +15,8 @@ pub struct WhisperModel {
     pub filename: String,
     pub description: String,
     pub downloaded: bool,
+    /// Whisper ceviri modu (turbo modeller ceviri verisiyle egitilmedi)
+    pub supports_translate: bool,
 }
 
 #[derive(Clone, Serialize, Default)]
@@ -38,6 +40,7 @@ pub fn get_available_models() -> Vec<WhisperModel> {
             filename: "ggml-small.bin".to_string(),
             description: "Hizli, iyi kalite. Test icin uygun.".to_string(),
             downloaded: false,
+            supports_translate: true,
         },
         WhisperModel {
             id: "large-v3-turbo-q5".to_string(),
@@ -48,6 +51,7 @@ pub fn get_available_models() -> Vec<WhisperModel> {
             filename: "ggml-large-v3-turbo-q5_0.bin".to_string(),
             description: "Cok iyi kalite, hizli. 8GB RAM altina onerilen.".to_string(),
             downloaded: false,
+            supports_translate: false,
         },
         WhisperModel {
             id: "large-v3-turbo".to_string(),
@@ -58,6 +62,7 @@ pub fn get_available_models() -> Vec<WhisperModel> {
             filename: "ggml-large-v3-turbo.bin".to_string(),
             description: "Mukemmel kalite. Varsayilan onerilen model.".to_string(),
             downloaded: false,
+            supports_translate: false,
         },
         WhisperModel {
             id: "large-v3".to_string(),
@@ -68,10 +73,18 @@ pub fn get_available_models() -> Vec<WhisperModel> {
             filename: "ggml-large-v3.bin".to_string(),
             description: "En iyi kalite, yavas. Maksimum dogruluk isteyenler icin.".to_string(),
             downloaded: false,
+            supports_translate: true,
         },
     ]
 }
 
+/// Model Whisper ceviri modunu destekliyor mu (bilinmeyen model: hayir)
+pub fn supports_translate(model_id: &str) -> bool {
+    get_available_models()
+        .iter()
+        .any(|m| m.id == model_id && m.supports_translate)
+}
+
 pub fn get_models_dir(app_handle: &AppHandle) -> PathBuf {
     let data_dir = app_handle
         .path()
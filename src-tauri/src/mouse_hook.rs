@@ -0,0 +1,551 @@
+//! Fare ile metin secimi algilama — secim yapilinca context menu'yu acar.
+//!
+//! Platform katmani (Windows WH_MOUSE_LL hook'u, X11 isaretci yoklamasi)
+//! yalnizca sol tus basma/birakma olaylarini uretir. Bu olaylar tek bir
+//! isci thread'ine gonderilir; `GestureDetector` suruklemeli secimi ve
+//! cift/uclu tiklamayi tanir, isci de secili metni alip
+//! `show_context_menu_with_text` ile menuyu gosterir. Jest mantigi OS'ten
+//! bagimsizdir ve birim testleriyle dogrulanir.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, OnceLock};
+use std::time::Duration;
+use tauri::Manager;
+
+/// Suruklemeli secim sayilmasi icin en az hareket (piksel)
+const DRAG_MIN_PX: i32 = 8;
+/// Bundan kisa basmalar secim degil, titrek tiklamadir
+const DRAG_MIN_MS: u64 = 80;
+/// Cok uzun basili tutma (pencere tasima, cizim) secim sayilmaz
+const DRAG_MAX_MS: u64 = 15_000;
+/// Cift/uclu tiklama icin tiklamalar arasi en fazla sure ve mesafe
+const MULTI_CLICK_MS: u64 = 500;
+const MULTI_CLICK_PX: i32 = 4;
+/// Uygulamanin secimi guncellemesi icin bekleme
+const SELECTION_SETTLE: Duration = Duration::from_millis(120);
+/// Bundan uzun secimler icin menu acilmaz
+const MAX_SELECTION_CHARS: usize = 5_000;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+/// Son gosterilen secimin ozeti — ayni secim icin menu tekrar acilmaz
+static LAST_TEXT_HASH: AtomicU64 = AtomicU64::new(0);
+static EVENT_TX: OnceLock<mpsc::Sender<MouseEvent>> = OnceLock::new();
+
+// ─── Jest Algilama (platformdan bagimsiz) ───
+
+/// Sol fare tusu olayi; koordinatlar ekran pikseli, zaman ms
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MouseEvent {
+    Down { x: i32, y: i32, time_ms: u64 },
+    Up { x: i32, y: i32, time_ms: u64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    DragSelect { x: i32, y: i32 },
+    /// Kelime secimi
+    DoubleClick { x: i32, y: i32 },
+    /// Satir/paragraf secimi
+    TripleClick { x: i32, y: i32 },
+}
+
+impl Gesture {
+    pub fn position(&self) -> (i32, i32) {
+        match *self {
+            Gesture::DragSelect { x, y } | Gesture::DoubleClick { x, y } | Gesture::TripleClick { x, y } => (x, y),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct GestureDetector {
+    /// Basili tusun baslangici (x, y, ms)
+    press: Option<(i32, i32, u64)>,
+    /// Son tiklamanin birakilisi (x, y, ms)
+    last_click: Option<(i32, i32, u64)>,
+    clicks: u32,
+}
+
+fn dist2(ax: i32, ay: i32, bx: i32, by: i32) -> i64 {
+    let dx = (ax - bx) as i64;
+    let dy = (ay - by) as i64;
+    dx * dx + dy * dy
+}
+
+impl GestureDetector {
+    pub fn feed(&mut self, event: MouseEvent) -> Option<Gesture> {
+        match event {
+            MouseEvent::Down { x, y, time_ms } => {
+                self.press = Some((x, y, time_ms));
+                None
+            }
+            MouseEvent::Up { x, y, time_ms } => {
+                // Basma kacirildiysa (menu icinde basildi vb.) yok say
+                let (px, py, pressed_at) = self.press.take()?;
+
+                if dist2(px, py, x, y) >= (DRAG_MIN_PX * DRAG_MIN_PX) as i64 {
+                    self.clicks = 0;
+                    self.last_click = None;
+                    let held = time_ms.saturating_sub(pressed_at);
+                    return (DRAG_MIN_MS..=DRAG_MAX_MS)
+                        .contains(&held)
+                        .then_some(Gesture::DragSelect { x, y });
+                }
+
+                let continues = self.last_click.is_some_and(|(lx, ly, at)| {
+                    time_ms.saturating_sub(at) <= MULTI_CLICK_MS
+                        && dist2(lx, ly, x, y) <= (MULTI_CLICK_PX * MULTI_CLICK_PX) as i64
+                });
+                self.clicks = if continues { self.clicks + 1 } else { 1 };
+                self.last_click = Some((x, y, time_ms));
+                match self.clicks {
+                    2 => Some(Gesture::DoubleClick { x, y }),
+                    3 => {
+                        // Dorduncu tiklama yeni bir dizi baslatir
+                        self.clicks = 0;
+                        self.last_click = None;
+                        Some(Gesture::TripleClick { x, y })
+                    }
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+// ─── Ortak Isci ───
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Platform katmanindan cagrilir — devre disiyken olaylar atilir
+fn push_event(event: MouseEvent) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    if let Some(tx) = EVENT_TX.get() {
+        let _ = tx.send(event);
+    }
+}
+
+fn text_hash(text: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    text.hash(&mut hasher);
+    // 0 "henuz gosterilmedi" anlamina gelir
+    hasher.finish().max(1)
+}
+
+/// Context menu gorunur mu, nokta menunun icinde mi
+fn menu_hit(app: &tauri::AppHandle, x: i32, y: i32) -> (bool, bool) {
+    let Some(window) = app.get_webview_window("context-menu") else {
+        return (false, false);
+    };
+    if !window.is_visible().unwrap_or(false) {
+        return (false, false);
+    }
+    let inside = match (window.outer_position(), window.outer_size()) {
+        (Ok(pos), Ok(size)) => {
+            x >= pos.x && y >= pos.y && x < pos.x + size.width as i32 && y < pos.y + size.height as i32
+        }
+        _ => false,
+    };
+    (true, inside)
+}
+
+fn show_for_gesture(app: tauri::AppHandle, gesture: Gesture) {
+    std::thread::spawn(move || {
+        std::thread::sleep(SELECTION_SETTLE);
+        let (x, y) = gesture.position();
+        let Some(text) = backend::selected_text(x, y) else {
+            return;
+        };
+        let text = text.trim();
+        if text.is_empty() || text.chars().count() > MAX_SELECTION_CHARS {
+            return;
+        }
+        let hash = text_hash(text);
+        if LAST_TEXT_HASH.swap(hash, Ordering::Relaxed) == hash {
+            return;
+        }
+        log::debug!("{:?}: {} karakter secildi", gesture, text.chars().count());
+        crate::show_context_menu_with_text(&app, text, x, y);
+    });
+}
+
+fn run_worker(app: tauri::AppHandle, rx: mpsc::Receiver<MouseEvent>) {
+    let mut detector = GestureDetector::default();
+    for event in rx {
+        if let MouseEvent::Down { x, y, .. } = event {
+            let (visible, inside) = menu_hit(&app, x, y);
+            if inside {
+                // Menu dugmelerine tiklama secim jesti degil
+                continue;
+            }
+            if visible {
+                crate::hide_context_menu_internal(&app);
+            }
+        }
+        if let Some(gesture) = detector.feed(event) {
+            if ENABLED.load(Ordering::Relaxed) {
+                show_for_gesture(app.clone(), gesture);
+            }
+        }
+    }
+}
+
+// ─── Genel API ───
+
+/// Otomatik secim algilamayi ac/kapat (`translate_auto_detect`)
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+    log::info!("Secim algilama: {}", if enabled { "acik" } else { "kapali" });
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Hook'u ve isci thread'ini kur (uygulama basinda bir kez)
+pub fn install(app: tauri::AppHandle) {
+    let (tx, rx) = mpsc::channel();
+    if EVENT_TX.set(tx).is_err() {
+        return;
+    }
+    std::thread::Builder::new()
+        .name("mouse-gestures".into())
+        .spawn(move || run_worker(app, rx))
+        .ok();
+    backend::install();
+}
+
+/// Menu kapaninca cagrilir — ayni metin tekrar secilirse menu yeniden acilabilsin
+pub fn reset_text_hash() {
+    LAST_TEXT_HASH.store(0, Ordering::Relaxed);
+}
+
+#[cfg(target_os = "windows")]
+pub use win::{set_context_menu_hwnd, start_drag};
+
+#[cfg(not(target_os = "windows"))]
+pub fn set_context_menu_hwnd(_hwnd: isize) {}
+#[cfg(not(target_os = "windows"))]
+pub fn start_drag(_hwnd: isize, _cursor_x: i32, _cursor_y: i32, _window_x: i32, _window_y: i32) {}
+
+// ─── Windows: WH_MOUSE_LL ───
+
+#[cfg(target_os = "windows")]
+use win as backend;
+
+#[cfg(target_os = "windows")]
+mod win {
+    use super::{now_ms, push_event, MouseEvent};
+    use parking_lot::Mutex;
+    use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicU32, Ordering};
+
+    use windows::Win32::Foundation::{HINSTANCE, LPARAM, LRESULT, WPARAM};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CallNextHookEx, DispatchMessageW, GetMessageW, SetWindowsHookExW, TranslateMessage,
+        UnhookWindowsHookEx, HHOOK, MSG, MSLLHOOKSTRUCT, WH_MOUSE_LL,
+    };
+
+    extern "system" {
+        fn GetWindowRect(hwnd: isize, rect: *mut [i32; 4]) -> i32;
+        fn IsWindowVisible(hwnd: isize) -> i32;
+        fn SetWindowPos(hwnd: isize, after: isize, x: i32, y: i32, w: i32, h: i32, flags: u32) -> i32;
+    }
+
+    const WM_MOUSEMOVE_U: u32 = 0x0200;
+    const WM_LBUTTONDOWN_U: u32 = 0x0201;
+    const WM_LBUTTONUP_U: u32 = 0x0202;
+    const SWP_NOSIZE: u32 = 0x0001;
+    const SWP_NOZORDER: u32 = 0x0004;
+    const SWP_NOACTIVATE: u32 = 0x0010;
+
+    static HOOK_HANDLE: AtomicIsize = AtomicIsize::new(0);
+    static HOOK_THREAD_ID: AtomicU32 = AtomicU32::new(0);
+    static MENU_HWND: AtomicIsize = AtomicIsize::new(0);
+    static DRAGGING: AtomicBool = AtomicBool::new(false);
+    /// (hwnd, imlec x, imlec y, pencere x, pencere y) — surukleme baslangici
+    static DRAG_ORIGIN: Mutex<(isize, i32, i32, i32, i32)> = Mutex::new((0, 0, 0, 0, 0));
+
+    fn inside_menu(x: i32, y: i32) -> bool {
+        let hwnd = MENU_HWND.load(Ordering::Relaxed);
+        if hwnd == 0 {
+            return false;
+        }
+        let mut rect = [0i32; 4];
+        unsafe {
+            if IsWindowVisible(hwnd) == 0 || GetWindowRect(hwnd, &mut rect) == 0 {
+                return false;
+            }
+        }
+        x >= rect[0] && x < rect[2] && y >= rect[1] && y < rect[3]
+    }
+
+    unsafe extern "system" fn ll_mouse_proc(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+        if code >= 0 {
+            let info = unsafe { &*(lparam.0 as *const MSLLHOOKSTRUCT) };
+            let (x, y) = (info.pt.x, info.pt.y);
+            let message = wparam.0 as u32;
+            // Birakma suruklemeyi bitirir; surukleme birakmasi jest degil
+            let drag_ended = message == WM_LBUTTONUP_U && DRAGGING.swap(false, Ordering::Relaxed);
+            match message {
+                WM_MOUSEMOVE_U if DRAGGING.load(Ordering::Relaxed) => {
+                    let (hwnd, cx, cy, wx, wy) = *DRAG_ORIGIN.lock();
+                    unsafe {
+                        SetWindowPos(
+                            hwnd,
+                            0,
+                            wx + x - cx,
+                            wy + y - cy,
+                            0,
+                            0,
+                            SWP_NOSIZE | SWP_NOZORDER | SWP_NOACTIVATE,
+                        );
+                    }
+                }
+                // Menu icindeki tiklamalar isciye gitmez (cross-thread pencere sorgusu yok)
+                WM_LBUTTONDOWN_U if !inside_menu(x, y) => {
+                    push_event(MouseEvent::Down { x, y, time_ms: now_ms() });
+                }
+                WM_LBUTTONUP_U if !drag_ended && !inside_menu(x, y) => {
+                    push_event(MouseEvent::Up { x, y, time_ms: now_ms() });
+                }
+                _ => {}
+            }
+        }
+
+        unsafe {
+            CallNextHookEx(
+                HHOOK(HOOK_HANDLE.load(Ordering::Relaxed) as _),
+                code,
+                wparam,
+                lparam,
+            )
+        }
+    }
+
+    pub fn install() {
+        if HOOK_THREAD_ID.load(Ordering::Relaxed) != 0 {
+            return;
+        }
+        std::thread::Builder::new()
+            .name("mouse-hook".into())
+            .spawn(move || unsafe {
+                use windows::Win32::System::Threading::GetCurrentThreadId;
+
+                let tid = GetCurrentThreadId();
+                HOOK_THREAD_ID.store(tid, Ordering::Relaxed);
+
+                match SetWindowsHookExW(WH_MOUSE_LL, Some(ll_mouse_proc), HINSTANCE::default(), 0) {
+                    Ok(h) => {
+                        HOOK_HANDLE.store(h.0 as isize, Ordering::Relaxed);
+                        log::info!("Mouse hook kuruldu, thread_id={}", tid);
+
+                        // Mesaj pompasi — LL hook icin gerekli
+                        let mut msg = MSG::default();
+                        while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+                            let _ = TranslateMessage(&msg);
+                            DispatchMessageW(&msg);
+                        }
+
+                        let h = HOOK_HANDLE.swap(0, Ordering::Relaxed);
+                        if h != 0 {
+                            let _ = UnhookWindowsHookEx(HHOOK(h as _));
+                        }
+                        log::info!("Mouse hook thread sonlandi");
+                    }
+                    Err(e) => {
+                        log::error!("SetWindowsHookExW basarisiz: {}", e);
+                    }
+                }
+                HOOK_THREAD_ID.store(0, Ordering::Relaxed);
+            })
+            .ok();
+    }
+
+    pub fn selected_text(x: i32, y: i32) -> Option<String> {
+        crate::get_selected_text_uia_at_point(x, y)
+    }
+
+    /// Context menu HWND'si — menu icindeki tiklamalar secim jesti sayilmaz
+    pub fn set_context_menu_hwnd(hwnd: isize) {
+        MENU_HWND.store(hwnd, Ordering::Relaxed);
+    }
+
+    /// WS_EX_NOACTIVATE menu penceresini sol tus birakilana kadar imlecle tasi
+    pub fn start_drag(hwnd: isize, cursor_x: i32, cursor_y: i32, window_x: i32, window_y: i32) {
+        *DRAG_ORIGIN.lock() = (hwnd, cursor_x, cursor_y, window_x, window_y);
+        DRAGGING.store(true, Ordering::Relaxed);
+    }
+}
+
+// ─── Linux: X11 isaretci yoklamasi ───
+
+#[cfg(target_os = "linux")]
+use x11 as backend;
+
+/// X11'de global fare hook'u icin XRecord gerekir; bunun yerine isaretci
+/// durumu kisa araliklarla yoklanir (yalnizca tus gecisleri olay uretir).
+/// Secili metin PRIMARY secimden okunur. Wayland oturumlarinda yalnizca
+/// XWayland pencereleri gorulur.
+#[cfg(target_os = "linux")]
+mod x11 {
+    use super::{now_ms, push_event, MouseEvent, MULTI_CLICK_MS};
+    use parking_lot::Mutex;
+    use std::time::Duration;
+    use x11rb::connection::Connection;
+    use x11rb::protocol::xproto::{ConnectionExt, KeyButMask};
+
+    const POLL_INTERVAL: Duration = Duration::from_millis(15);
+    /// Secim algilama kapaliyken isaretci sorgulanmaz; yalnizca bayrak kontrol edilir
+    const DISABLED_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+    static CLIPBOARD: Mutex<Option<arboard::Clipboard>> = Mutex::new(None);
+    /// Yeni bir tiklama dizisi basladiginda PRIMARY'nin icerigi.
+    /// Bos alana cift tiklamada eski secim tekrar gosterilmesin diye.
+    static SNAPSHOT: Mutex<Option<String>> = Mutex::new(None);
+
+    fn read_primary() -> Option<String> {
+        use arboard::{GetExtLinux, LinuxClipboardKind};
+        let mut clipboard = CLIPBOARD.lock();
+        if clipboard.is_none() {
+            *clipboard = arboard::Clipboard::new().ok();
+        }
+        clipboard
+            .as_mut()?
+            .get()
+            .clipboard(LinuxClipboardKind::Primary)
+            .text()
+            .ok()
+    }
+
+    pub fn install() {
+        std::thread::Builder::new()
+            .name("mouse-hook".into())
+            .spawn(|| {
+                let (conn, screen_num) = match x11rb::connect(None) {
+                    Ok(c) => c,
+                    Err(e) => {
+                        log::warn!("X11 baglantisi kurulamadi: {}", e);
+                        return;
+                    }
+                };
+                let root = conn.setup().roots[screen_num].root;
+                log::info!("X11 isaretci yoklamasi basladi");
+
+                let mut was_down = false;
+                let mut last_up_ms = 0u64;
+                loop {
+                    if !super::is_enabled() {
+                        was_down = false;
+                        std::thread::sleep(DISABLED_POLL_INTERVAL);
+                        continue;
+                    }
+                    std::thread::sleep(POLL_INTERVAL);
+                    let reply = match conn.query_pointer(root).map(|c| c.reply()) {
+                        Ok(Ok(reply)) => reply,
+                        _ => {
+                            log::warn!("X11 baglantisi koptu");
+                            break;
+                        }
+                    };
+                    let down = u16::from(reply.mask) & u16::from(KeyButMask::BUTTON1) != 0;
+                    if down == was_down {
+                        continue;
+                    }
+                    was_down = down;
+                    let (x, y, time_ms) = (i32::from(reply.root_x), i32::from(reply.root_y), now_ms());
+                    if down {
+                        if time_ms.saturating_sub(last_up_ms) > MULTI_CLICK_MS {
+                            *SNAPSHOT.lock() = read_primary();
+                        }
+                        push_event(MouseEvent::Down { x, y, time_ms });
+                    } else {
+                        last_up_ms = time_ms;
+                        push_event(MouseEvent::Up { x, y, time_ms });
+                    }
+                }
+            })
+            .ok();
+    }
+
+    pub fn selected_text(_x: i32, _y: i32) -> Option<String> {
+        let text = read_primary()?;
+        (SNAPSHOT.lock().as_deref() != Some(text.as_str())).then_some(text)
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+mod backend {
+    pub fn install() {}
+
+    pub fn selected_text(_x: i32, _y: i32) -> Option<String> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn down(x: i32, y: i32, time_ms: u64) -> MouseEvent {
+        MouseEvent::Down { x, y, time_ms }
+    }
+
+    fn up(x: i32, y: i32, time_ms: u64) -> MouseEvent {
+        MouseEvent::Up { x, y, time_ms }
+    }
+
+    #[test]
+    fn test_drag_select() {
+        let mut d = GestureDetector::default();
+        assert_eq!(d.feed(down(100, 100, 0)), None);
+        assert_eq!(d.feed(up(180, 104, 400)), Some(Gesture::DragSelect { x: 180, y: 104 }));
+
+        // Cok kisa titreme ve cok uzun basili tutma secim degil
+        d.feed(down(0, 0, 1000));
+        assert_eq!(d.feed(up(20, 0, 1030)), None);
+        d.feed(down(0, 0, 2000));
+        assert_eq!(d.feed(up(300, 0, 2000 + DRAG_MAX_MS + 1)), None);
+    }
+
+    #[test]
+    fn test_single_double_triple_click() {
+        let mut d = GestureDetector::default();
+        d.feed(down(50, 50, 0));
+        assert_eq!(d.feed(up(50, 50, 60)), None);
+        d.feed(down(51, 50, 200));
+        assert_eq!(d.feed(up(51, 51, 260)), Some(Gesture::DoubleClick { x: 51, y: 51 }));
+        d.feed(down(51, 51, 400));
+        assert_eq!(d.feed(up(51, 51, 450)), Some(Gesture::TripleClick { x: 51, y: 51 }));
+        // Dorduncu tiklama yeni dizi
+        d.feed(down(51, 51, 600));
+        assert_eq!(d.feed(up(51, 51, 650)), None);
+    }
+
+    #[test]
+    fn test_click_sequence_breaks() {
+        let mut d = GestureDetector::default();
+        // Zaman asimi
+        d.feed(down(10, 10, 0));
+        d.feed(up(10, 10, 50));
+        d.feed(down(10, 10, 700));
+        assert_eq!(d.feed(up(10, 10, 750)), None);
+        // Uzak ikinci tiklama
+        d.feed(down(40, 40, 900));
+        assert_eq!(d.feed(up(40, 40, 950)), None);
+        // Araya giren surukleme diziyi sifirlar
+        d.feed(down(40, 40, 1000));
+        assert!(matches!(d.feed(up(90, 40, 1200)), Some(Gesture::DragSelect { .. })));
+        d.feed(down(90, 40, 1300));
+        assert_eq!(d.feed(up(90, 40, 1350)), None);
+        // Basmasi kacirilan birakma yok sayilir
+        assert_eq!(d.feed(up(90, 40, 1400)), None);
+    }
+}
@@ -0,0 +1,297 @@
+//! Yerel agda diger Fisilti orneklerini mDNS ile bulma.
+//!
+//! Isbirligi sunucusu acildiginda bu ornek `_fisilti._tcp` servisi olarak
+//! collab portu ve peer kimligiyle duyurulur; ayni anda agdaki diger ornekler
+//! dinlenir. Bulunan/kaybolan ornekler "peer-discovered" ve "peer-lost"
+//! olaylariyla frontend'e bildirilir.
+
+use mdns_sd::{ServiceDaemon, ServiceEvent, ServiceInfo};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use tauri::Emitter;
+
+const SERVICE_TYPE: &str = "_fisilti._tcp.local.";
+const PROP_PEER_ID: &str = "peer_id";
+const PROP_NAME: &str = "name";
+const PROP_VERSION: &str = "version";
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PeerInfo {
+    /// Karsi bilgisayarin adi
+    pub name: String,
+    pub address: String,
+    /// Karsi ornegin collab HTTP portu
+    pub port: u16,
+    pub peer_id: String,
+}
+
+struct PeerService {
+    daemon: ServiceDaemon,
+    /// Duyurulan kendi servisimiz (tam ad, peer kimligi)
+    advertised: Option<(String, String)>,
+}
+
+static SERVICE: Mutex<Option<PeerService>> = Mutex::new(None);
+/// Servis tam adi -> peer
+static PEERS: Mutex<Option<HashMap<String, PeerInfo>>> = Mutex::new(None);
+
+fn local_hostname() -> String {
+    hostname::get()
+        .ok()
+        .and_then(|h| h.into_string().ok())
+        .filter(|h| !h.is_empty())
+        .unwrap_or_else(|| "fisilti".to_string())
+}
+
+/// DNS etiketi olarak guvenli ad: ASCII harf/rakam ve '-'
+fn dns_label(name: &str) -> String {
+    let label: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let label = label.trim_matches('-');
+    if label.is_empty() {
+        "fisilti".to_string()
+    } else {
+        label.chars().take(40).collect()
+    }
+}
+
+/// Ayni bilgisayarda birden fazla ornek olabilir — peer kimliginin basi eklenir
+fn instance_name(host: &str, peer_id: &str) -> String {
+    let suffix: String = peer_id.chars().filter(|c| c.is_ascii_alphanumeric()).take(8).collect();
+    if suffix.is_empty() {
+        dns_label(host)
+    } else {
+        format!("{}-{}", dns_label(host), suffix)
+    }
+}
+
+/// Cozulen servisten PeerInfo; Fisilti kimligi olmayan servisler atlanir
+fn peer_from_parts(
+    fullname: &str,
+    name: Option<&str>,
+    peer_id: Option<&str>,
+    addresses: &HashSet<IpAddr>,
+    port: u16,
+) -> Option<PeerInfo> {
+    let peer_id = peer_id.filter(|id| !id.is_empty())?;
+    // IPv4 oncelikli, sonra IPv6; link-local IPv6 tarayicida kullanilamaz
+    let mut candidates: Vec<&IpAddr> = addresses
+        .iter()
+        .filter(|ip| !ip.is_loopback() && !ip.is_unspecified())
+        .collect();
+    candidates.sort_by_key(|ip| (!ip.is_ipv4(), ip.to_string()));
+    let address = candidates.first()?.to_string();
+    let name = name
+        .filter(|n| !n.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| fullname.split('.').next().unwrap_or(fullname).to_string());
+    Some(PeerInfo {
+        name,
+        address,
+        port,
+        peer_id: peer_id.to_string(),
+    })
+}
+
+fn is_own_service(fullname: &str, peer_id: &str) -> bool {
+    SERVICE
+        .lock()
+        .as_ref()
+        .and_then(|s| s.advertised.as_ref())
+        .is_some_and(|(own_name, own_id)| own_name == fullname || own_id == peer_id)
+}
+
+fn handle_event(app_handle: &tauri::AppHandle, event: ServiceEvent) {
+    match event {
+        ServiceEvent::ServiceResolved(info) => {
+            let Some(peer) = peer_from_parts(
+                info.get_fullname(),
+                info.get_property_val_str(PROP_NAME),
+                info.get_property_val_str(PROP_PEER_ID),
+                info.get_addresses(),
+                info.get_port(),
+            ) else {
+                return;
+            };
+            if is_own_service(info.get_fullname(), &peer.peer_id) {
+                return;
+            }
+            let previous = PEERS
+                .lock()
+                .get_or_insert_with(HashMap::new)
+                .insert(info.get_fullname().to_string(), peer.clone());
+            // Ayni peer tekrar cozulunce (TTL yenileme) olay tekrarlanmaz
+            if previous.as_ref() != Some(&peer) {
+                log::info!("Peer bulundu: {} ({}:{})", peer.name, peer.address, peer.port);
+                let _ = app_handle.emit("peer-discovered", &peer);
+            }
+        }
+        ServiceEvent::ServiceRemoved(_, fullname) => {
+            let removed = PEERS.lock().as_mut().and_then(|peers| peers.remove(&fullname));
+            if let Some(peer) = removed {
+                log::info!("Peer ayrildi: {}", peer.name);
+                let _ = app_handle.emit("peer-lost", &peer);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// mDNS servisini baslat ve diger ornekleri dinle. Zaten calisiyorsa bir sey yapmaz.
+pub fn start_peer_service(app_handle: tauri::AppHandle) -> Result<(), String> {
+    let mut service = SERVICE.lock();
+    if service.is_some() {
+        return Ok(());
+    }
+    let daemon = ServiceDaemon::new().map_err(|e| format!("mDNS baslatilamadi: {}", e))?;
+    let receiver = daemon
+        .browse(SERVICE_TYPE)
+        .map_err(|e| format!("mDNS tarama baslatilamadi: {}", e))?;
+
+    std::thread::spawn(move || {
+        // Daemon kapatilinca kanal kapanir ve dongu biter
+        while let Ok(event) = receiver.recv() {
+            handle_event(&app_handle, event);
+        }
+        log::info!("mDNS tarama durdu");
+    });
+
+    *service = Some(PeerService {
+        daemon,
+        advertised: None,
+    });
+    log::info!("mDNS peer kesfi basladi ({})", SERVICE_TYPE);
+    Ok(())
+}
+
+/// Bu ornegi collab portu ve peer kimligiyle duyur (oncekinin yerine gecer)
+pub fn advertise(app_handle: tauri::AppHandle, peer_id: &str, port: u16) -> Result<(), String> {
+    start_peer_service(app_handle)?;
+    unadvertise();
+
+    let host = local_hostname();
+    let instance = instance_name(&host, peer_id);
+    let host_name = format!("{}.local.", dns_label(&host));
+    let properties = [
+        (PROP_PEER_ID, peer_id),
+        (PROP_NAME, host.as_str()),
+        (PROP_VERSION, env!("CARGO_PKG_VERSION")),
+    ];
+    let info = ServiceInfo::new(SERVICE_TYPE, &instance, &host_name, (), port, &properties[..])
+        .map_err(|e| format!("mDNS servis bilgisi olusturulamadi: {}", e))?
+        .enable_addr_auto();
+    let fullname = info.get_fullname().to_string();
+
+    let mut service = SERVICE.lock();
+    let service = service.as_mut().ok_or("mDNS servisi calismiyor")?;
+    service
+        .daemon
+        .register(info)
+        .map_err(|e| format!("mDNS servisi duyurulamadi: {}", e))?;
+    service.advertised = Some((fullname.clone(), peer_id.to_string()));
+    log::info!("mDNS servisi duyuruldu: {} (port {})", fullname, port);
+    Ok(())
+}
+
+/// Duyuruyu geri cek; tarama devam eder
+pub fn unadvertise() {
+    let mut service = SERVICE.lock();
+    if let Some(service) = service.as_mut() {
+        if let Some((fullname, _)) = service.advertised.take() {
+            if let Err(e) = service.daemon.unregister(&fullname) {
+                log::warn!("mDNS duyurusu geri cekilemedi: {}", e);
+            }
+        }
+    }
+}
+
+/// `features.collaboration` degisince kesfi baslat/durdur
+pub fn set_enabled(app_handle: tauri::AppHandle, enabled: bool) {
+    let result = if enabled {
+        start_peer_service(app_handle)
+    } else {
+        stop_peer_service()
+    };
+    if let Err(e) = result {
+        log::warn!("Peer kesfi guncellenemedi: {}", e);
+    }
+}
+
+// ─── Tauri Komutlari ───
+
+#[tauri::command]
+pub fn get_discovered_peers() -> Vec<PeerInfo> {
+    let mut peers: Vec<PeerInfo> = PEERS
+        .lock()
+        .as_ref()
+        .map(|p| p.values().cloned().collect())
+        .unwrap_or_default();
+    peers.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.peer_id.cmp(&b.peer_id)));
+    peers
+}
+
+/// Duyuruyu geri cek, taramayi durdur ve bulunan peer listesini temizle
+#[tauri::command]
+pub fn stop_peer_service() -> Result<(), String> {
+    unadvertise();
+    let service = SERVICE.lock().take();
+    PEERS.lock().take();
+    if let Some(service) = service {
+        service
+            .daemon
+            .shutdown()
+            .map_err(|e| format!("mDNS kapatilamadi: {}", e))?;
+        log::info!("mDNS peer kesfi durduruldu");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instance_name() {
+        assert_eq!(instance_name("Ayşe'nin MacBook'u", "a1b2c3d4e5f6"), "Ay-e-nin-MacBook-u-a1b2c3d4");
+        assert_eq!(instance_name("", "---"), "fisilti");
+        assert_eq!(dns_label("host.local"), "host-local");
+    }
+
+    #[test]
+    fn test_peer_from_parts_prefers_ipv4() {
+        let addresses: HashSet<IpAddr> = ["fe80::1", "192.168.1.20", "127.0.0.1"]
+            .iter()
+            .map(|a| a.parse().unwrap())
+            .collect();
+        let peer = peer_from_parts(
+            "masaustu-abc._fisilti._tcp.local.",
+            Some("masaustu"),
+            Some("abc123"),
+            &addresses,
+            4455,
+        )
+        .unwrap();
+        assert_eq!(
+            peer,
+            PeerInfo {
+                name: "masaustu".to_string(),
+                address: "192.168.1.20".to_string(),
+                port: 4455,
+                peer_id: "abc123".to_string(),
+            }
+        );
+
+        // Ad yoksa servis ornek adi kullanilir
+        let unnamed = peer_from_parts("laptop-9f._fisilti._tcp.local.", None, Some("9f"), &addresses, 1).unwrap();
+        assert_eq!(unnamed.name, "laptop-9f");
+
+        // Peer kimligi veya adres yoksa Fisilti ornegi sayilmaz
+        assert!(peer_from_parts("x._fisilti._tcp.local.", Some("x"), None, &addresses, 1).is_none());
+        let loopback: HashSet<IpAddr> = ["127.0.0.1".parse().unwrap()].into_iter().collect();
+        assert!(peer_from_parts("x._fisilti._tcp.local.", Some("x"), Some("id"), &loopback, 1).is_none());
+    }
+}
@@ -0,0 +1,136 @@
+//! Tek ureticili / tek tuketicili (SPSC) kilitsiz ornek halkasi.
+//!
+//! Ses thread'i (cpal geri cagirimi) `Producer` ile yazar, tuketici thread'i
+//! `Consumer` ile okur. Yazma ve okuma yalnizca atomik indeksler uzerinden
+//! senkronize olur; ses thread'i hic kilit almaz ve bellek ayirmaz. Halka
+//! doluysa yeni ornekler atilir ve `dropped` sayacina eklenir.
+
+use std::cell::UnsafeCell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+struct Ring {
+    slots: Box<[UnsafeCell<f32>]>,
+    /// Siradaki okunacak ornek (tasarak artan, mod kapasite ile indekslenir)
+    read: AtomicUsize,
+    /// Siradaki yazilacak ornek
+    write: AtomicUsize,
+    dropped: AtomicUsize,
+}
+
+// Her slota ayni anda yalnizca bir taraf erisir: uretici [write, read + cap),
+// tuketici [read, write) araligina dokunur; sinirlar Acquire/Release ile yayinlanir.
+unsafe impl Sync for Ring {}
+unsafe impl Send for Ring {}
+
+impl Ring {
+    fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+}
+
+pub struct Producer {
+    ring: Arc<Ring>,
+}
+
+pub struct Consumer {
+    ring: Arc<Ring>,
+}
+
+/// `capacity` ornek tutan halka olustur
+pub fn channel(capacity: usize) -> (Producer, Consumer) {
+    let slots = (0..capacity.max(1)).map(|_| UnsafeCell::new(0.0)).collect();
+    let ring = Arc::new(Ring {
+        slots,
+        read: AtomicUsize::new(0),
+        write: AtomicUsize::new(0),
+        dropped: AtomicUsize::new(0),
+    });
+    (Producer { ring: ring.clone() }, Consumer { ring })
+}
+
+impl Producer {
+    /// Ornekleri yaz; sigmayanlar atilir. Yazilan ornek sayisini dondurur.
+    pub fn push(&mut self, samples: impl ExactSizeIterator<Item = f32>) -> usize {
+        let ring = &*self.ring;
+        let write = ring.write.load(Ordering::Relaxed);
+        let free = ring.capacity() - write.wrapping_sub(ring.read.load(Ordering::Acquire));
+        let total = samples.len();
+        let count = total.min(free);
+        for (i, sample) in samples.take(count).enumerate() {
+            // SAFETY: [write, write + count) tuketiciye henuz yayinlanmadi
+            unsafe { *ring.slots[write.wrapping_add(i) % ring.capacity()].get() = sample };
+        }
+        ring.write.store(write.wrapping_add(count), Ordering::Release);
+        if count < total {
+            ring.dropped.fetch_add(total - count, Ordering::Relaxed);
+        }
+        count
+    }
+}
+
+impl Consumer {
+    /// Hazir ornekleri `out`'a oku; okunan ornek sayisini dondurur
+    pub fn pop(&mut self, out: &mut [f32]) -> usize {
+        let ring = &*self.ring;
+        let read = ring.read.load(Ordering::Relaxed);
+        let available = ring.write.load(Ordering::Acquire).wrapping_sub(read);
+        let count = available.min(out.len());
+        for (i, slot) in out[..count].iter_mut().enumerate() {
+            // SAFETY: [read, read + count) uretici tarafindan yayinlandi
+            *slot = unsafe { *ring.slots[read.wrapping_add(i) % ring.capacity()].get() };
+        }
+        ring.read.store(read.wrapping_add(count), Ordering::Release);
+        count
+    }
+
+    /// Halka dolu oldugu icin simdiye kadar atilan ornekler
+    pub fn dropped(&self) -> usize {
+        self.ring.dropped.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wraparound_and_overflow() {
+        let (mut tx, mut rx) = channel(4);
+        let mut out = [0.0; 4];
+        assert_eq!(tx.push([1.0, 2.0, 3.0].into_iter()), 3);
+        assert_eq!(rx.pop(&mut out[..2]), 2);
+        assert_eq!(&out[..2], &[1.0, 2.0]);
+
+        // Halkanin sonundan basina sarar; bos yer 3, fazlasi atilir
+        assert_eq!(tx.push([4.0, 5.0, 6.0, 7.0].into_iter()), 3);
+        assert_eq!(rx.dropped(), 1);
+        assert_eq!(rx.pop(&mut out), 4);
+        assert_eq!(out, [3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(rx.pop(&mut out), 0);
+    }
+
+    #[test]
+    fn test_threaded_order_is_preserved() {
+        const TOTAL: usize = 200_000;
+        let (mut tx, mut rx) = channel(1024);
+        let producer = std::thread::spawn(move || {
+            let mut next = 0;
+            while next < TOTAL {
+                let end = (next + 300).min(TOTAL);
+                // Sigmayan kisim atilir; uretici yazilabilen kadar ilerleyip tekrar dener
+                next += tx.push((next..end).map(|i| i as f32));
+                std::thread::yield_now();
+            }
+        });
+
+        let mut received = Vec::with_capacity(TOTAL);
+        let mut buf = [0.0; 256];
+        while received.len() < TOTAL {
+            let n = rx.pop(&mut buf);
+            received.extend_from_slice(&buf[..n]);
+        }
+        producer.join().unwrap();
+        assert!(received.iter().enumerate().all(|(i, &s)| s == i as f32));
+    }
+}
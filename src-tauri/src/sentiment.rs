@@ -0,0 +1,431 @@
+//! Sozluk tabanli duygu analizi (Turkce + Ingilizce).
+//!
+//! Sozluk `assets/sentiment_lexicon.txt` dosyasindan derlemeye gomulur;
+//! uygulama veri dizinine ayni bicimde bir `sentiment_lexicon.txt` konursa
+//! ustune eklenir. Olumsuzluk eki (-me/-ma, -siz), "degil"/"not" gibi
+//! olumsuzlayicilar ve "cok"/"very" gibi guclendiriciler dikkate alinir.
+
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+const DEFAULT_LEXICON: &str = include_str!("../assets/sentiment_lexicon.txt");
+const USER_LEXICON_FILE: &str = "sentiment_lexicon.txt";
+
+/// Olumsuzlanan kelimenin puani ters cevrilir ve zayiflar ("iyi degil" != "kotu")
+const NEGATION_FACTOR: f32 = -0.75;
+/// Onden olumsuzlayicinin etki mesafesi (not very good)
+const PRE_NEGATION_WINDOW: usize = 3;
+/// Arkadan olumsuzlayicinin etki mesafesi (iyi hic degil)
+const POST_NEGATION_WINDOW: usize = 2;
+/// Toplam puani -1..1 araligina sikistirma sabiti
+const NORMALIZATION_ALPHA: f32 = 15.0;
+/// Bu esigin altindaki puanlar notr sayilir
+const NEUTRAL_THRESHOLD: f32 = 0.05;
+/// Govde eslesmesi icin en kisa on ek
+const MIN_STEM_CHARS: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Emotion {
+    Joy,
+    Sadness,
+    Anger,
+    Fear,
+    Surprise,
+    Disgust,
+    Trust,
+}
+
+impl Emotion {
+    fn parse(name: &str) -> Result<Option<Self>, String> {
+        Ok(Some(match name {
+            "joy" => Self::Joy,
+            "sadness" => Self::Sadness,
+            "anger" => Self::Anger,
+            "fear" => Self::Fear,
+            "surprise" => Self::Surprise,
+            "disgust" => Self::Disgust,
+            "trust" => Self::Trust,
+            "-" => return Ok(None),
+            other => return Err(format!("bilinmeyen duygu '{}'", other)),
+        }))
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Joy => "joy",
+            Self::Sadness => "sadness",
+            Self::Anger => "anger",
+            Self::Fear => "fear",
+            Self::Surprise => "surprise",
+            Self::Disgust => "disgust",
+            Self::Trust => "trust",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SentimentResult {
+    /// -1 (cok olumsuz) .. 1 (cok olumlu)
+    pub score: f32,
+    /// "positive" | "negative" | "neutral"
+    pub label: String,
+    /// 0..1 — duygu kelimesi yoksa veya kelimeler celisiyorsa dusuk
+    pub confidence: f32,
+    /// Baskin duygu kategorisi ya da "neutral"
+    pub dominant_emotion: String,
+    /// Puan alan kelimeler (olumsuzlama/guclendirme uygulanmis)
+    pub word_scores: Vec<(String, f32)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct WordEntry {
+    score: f32,
+    emotion: Option<Emotion>,
+}
+
+/// Tam kelimeler ve '*' ile isaretlenmis govdeler
+#[derive(Debug, Default)]
+struct Terms {
+    exact: HashSet<String>,
+    stems: HashSet<String>,
+}
+
+impl Terms {
+    fn insert(&mut self, term: &str) {
+        match term.strip_suffix('*') {
+            Some(stem) => self.stems.insert(normalize_token(stem)),
+            None => self.exact.insert(normalize_token(term)),
+        };
+    }
+
+    fn contains(&self, token: &str) -> bool {
+        self.exact.contains(token) || prefixes(token).any(|(prefix, _)| self.stems.contains(prefix))
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Lexicon {
+    words: HashMap<String, WordEntry>,
+    stems: HashMap<String, WordEntry>,
+    intensifiers: HashMap<String, f32>,
+    negators_pre: Terms,
+    negators_post: Terms,
+    negation_suffixes: Vec<String>,
+}
+
+impl Lexicon {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut lexicon = Self::default();
+        lexicon.extend(text)?;
+        Ok(lexicon)
+    }
+
+    /// Ayni bicimdeki ek sozlugu ekle; tekrar tanimlanan kelimeler ustune yazilir
+    pub fn extend(&mut self, text: &str) -> Result<(), String> {
+        let mut section = String::new();
+        for (index, raw) in text.lines().enumerate() {
+            let line = raw.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = name.trim().to_string();
+                continue;
+            }
+            self.parse_line(&section, line)
+                .map_err(|e| format!("Duygu sozlugu satir {}: {}", index + 1, e))?;
+        }
+        Ok(())
+    }
+
+    fn parse_line(&mut self, section: &str, line: &str) -> Result<(), String> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match section {
+            "words" => {
+                let [term, score, emotion] = fields[..] else {
+                    return Err("'kelime puan duygu' bekleniyordu".to_string());
+                };
+                let score: f32 = score.parse().map_err(|_| format!("gecersiz puan '{}'", score))?;
+                let entry = WordEntry {
+                    score,
+                    emotion: Emotion::parse(emotion)?,
+                };
+                match term.strip_suffix('*') {
+                    Some(stem) => self.stems.insert(normalize_token(stem), entry),
+                    None => self.words.insert(normalize_token(term), entry),
+                };
+            }
+            "intensifiers" => {
+                let [term, factor] = fields[..] else {
+                    return Err("'kelime carpan' bekleniyordu".to_string());
+                };
+                let factor: f32 = factor.parse().map_err(|_| format!("gecersiz carpan '{}'", factor))?;
+                self.intensifiers.insert(normalize_token(term), factor);
+            }
+            "negators_pre" | "negators_post" | "negation_suffixes" => {
+                let [term] = fields[..] else {
+                    return Err("satir basina tek kelime bekleniyordu".to_string());
+                };
+                match section {
+                    "negators_pre" => self.negators_pre.insert(term),
+                    "negators_post" => self.negators_post.insert(term),
+                    _ => self.negation_suffixes.push(normalize_token(term)),
+                }
+            }
+            "" => return Err("bolum basligindan ([words] vb.) once kayit".to_string()),
+            other => return Err(format!("bilinmeyen bolum [{}]", other)),
+        }
+        Ok(())
+    }
+
+    /// Kelimenin sozluk karsiligi; govdeden sonra olumsuzluk eki varsa true
+    fn lookup(&self, token: &str) -> Option<(WordEntry, bool)> {
+        if let Some(entry) = self.words.get(token) {
+            return Some((*entry, false));
+        }
+        // En uzun govde kazanir (acimasiz* > aci*)
+        prefixes(token).find_map(|(stem, rest)| {
+            self.stems
+                .get(stem)
+                .map(|entry| (*entry, self.is_negation_suffix(rest)))
+        })
+    }
+
+    fn is_negation_suffix(&self, rest: &str) -> bool {
+        // Mastar eki (sevmek, sevmekten) olumsuzluk degil
+        if rest.starts_with("mek") || rest.starts_with("mak") {
+            return false;
+        }
+        self.negation_suffixes.iter().any(|s| rest.starts_with(s.as_str()))
+    }
+}
+
+/// Uzundan kisaya (govde, kalan ek) ciftleri; govde en az MIN_STEM_CHARS karakter
+fn prefixes(token: &str) -> impl Iterator<Item = (&str, &str)> {
+    let bounds: Vec<usize> = token
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(token.len()))
+        .skip(MIN_STEM_CHARS)
+        .collect();
+    bounds.into_iter().rev().map(move |i| token.split_at(i))
+}
+
+/// Turkce kucuk harf, kesme isareti ve kenar noktalamasi temizligi
+fn normalize_token(raw: &str) -> String {
+    raw.chars()
+        .filter(|c| !matches!(c, '\'' | '’'))
+        .flat_map(|c| match c {
+            'I' => vec!['ı'],
+            'İ' => vec!['i'],
+            _ => c.to_lowercase().collect(),
+        })
+        .collect::<String>()
+        .trim_matches(|c: char| !c.is_alphanumeric())
+        .to_string()
+}
+
+/// Metni sozlukle puanla
+pub fn analyze(lexicon: &Lexicon, text: &str) -> SentimentResult {
+    let tokens: Vec<(&str, String)> = text
+        .split_whitespace()
+        .map(|w| (w.trim_matches(|c: char| !c.is_alphanumeric()), normalize_token(w)))
+        .filter(|(_, n)| !n.is_empty())
+        .collect();
+    let n = tokens.len();
+    let bigram = |i: usize| (i + 1 < n).then(|| format!("{}_{}", tokens[i].1, tokens[i + 1].1));
+
+    // Her kelimenin rolu: guclendirici carpani ve sozluk karsiligi (kelime sayisiyla)
+    let mut intensity: Vec<Option<f32>> = vec![None; n];
+    let mut entries: Vec<Option<(WordEntry, bool, usize)>> = vec![None; n];
+    for i in 0..n {
+        if let Some(factor) = bigram(i).and_then(|b| lexicon.intensifiers.get(&b).copied()) {
+            intensity[i] = Some(1.0);
+            intensity[i + 1] = Some(factor);
+        } else if intensity[i].is_none() {
+            intensity[i] = lexicon.intensifiers.get(&tokens[i].1).copied();
+        }
+        entries[i] = match bigram(i).and_then(|b| lexicon.words.get(&b).copied()) {
+            Some(entry) => Some((entry, false, 2)),
+            None => lexicon.lookup(&tokens[i].1).map(|(e, neg)| (e, neg, 1)),
+        };
+    }
+
+    let mut word_scores = Vec::new();
+    let mut emotions: HashMap<Emotion, f32> = HashMap::new();
+    let mut i = 0;
+    while i < n {
+        let Some((entry, suffix_negated, span)) = entries[i] else {
+            i += 1;
+            continue;
+        };
+        // "cok guzel" — guclendirici olarak kullanilan kelime ayrica puanlanmaz
+        if intensity[i].is_some() && entries.get(i + 1).is_some_and(Option::is_some) {
+            i += 1;
+            continue;
+        }
+
+        let mut multiplier = 1.0;
+        let mut j = i;
+        while j > 0 && i - j < 3 {
+            match intensity[j - 1] {
+                Some(factor) => multiplier *= factor,
+                None => break,
+            }
+            j -= 1;
+        }
+
+        let end = i + span;
+        let pre_negated = tokens[i.saturating_sub(PRE_NEGATION_WINDOW)..i]
+            .iter()
+            .any(|(_, t)| lexicon.negators_pre.contains(t));
+        let post_negated = (end..(end + POST_NEGATION_WINDOW).min(n))
+            .take_while(|&k| k == end || entries[k].is_none())
+            .any(|k| lexicon.negators_post.contains(&tokens[k].1));
+        // Cift olumsuzluk ("sevmedim degil") birbirini goturur
+        let negated = [suffix_negated, pre_negated, post_negated].iter().filter(|b| **b).count() % 2 == 1;
+
+        let mut score = entry.score * multiplier;
+        if negated {
+            score *= NEGATION_FACTOR;
+        } else if let Some(emotion) = entry.emotion {
+            *emotions.entry(emotion).or_insert(0.0) += score.abs();
+        }
+        if score != 0.0 || entry.emotion.is_some() {
+            let word = tokens[i..end].iter().map(|(w, _)| *w).collect::<Vec<_>>().join(" ");
+            word_scores.push((word, score));
+        }
+        i = end;
+    }
+
+    let sum: f32 = word_scores.iter().map(|(_, s)| s).sum();
+    let abs_sum: f32 = word_scores.iter().map(|(_, s)| s.abs()).sum();
+    let score = sum / (sum * sum + NORMALIZATION_ALPHA).sqrt();
+    let label = if score >= NEUTRAL_THRESHOLD {
+        "positive"
+    } else if score <= -NEUTRAL_THRESHOLD {
+        "negative"
+    } else {
+        "neutral"
+    };
+    // Uyum (hep ayni yonde mi) x kanit miktari
+    let confidence = if abs_sum > 0.0 {
+        (sum.abs() / abs_sum) * (1.0 - (-abs_sum / 4.0).exp())
+    } else {
+        0.0
+    };
+    let dominant_emotion = emotions
+        .iter()
+        .max_by(|a, b| a.1.total_cmp(b.1).then_with(|| b.0.as_str().cmp(a.0.as_str())))
+        .map(|(e, _)| e.as_str())
+        .unwrap_or("neutral");
+
+    SentimentResult {
+        score,
+        label: label.to_string(),
+        confidence,
+        dominant_emotion: dominant_emotion.to_string(),
+        word_scores,
+    }
+}
+
+// ─── Global Sozluk ───
+
+static LEXICON: OnceLock<Lexicon> = OnceLock::new();
+
+fn get_lexicon(app_handle: &tauri::AppHandle) -> &'static Lexicon {
+    LEXICON.get_or_init(|| {
+        use tauri::Manager;
+        let mut lexicon = Lexicon::parse(DEFAULT_LEXICON).unwrap_or_else(|e| {
+            log::error!("Gomulu duygu sozlugu okunamadi: {}", e);
+            Lexicon::default()
+        });
+        if let Ok(dir) = app_handle.path().app_data_dir() {
+            let path = dir.join(USER_LEXICON_FILE);
+            if let Ok(text) = std::fs::read_to_string(&path) {
+                match lexicon.extend(&text) {
+                    Ok(()) => log::info!("Kullanici duygu sozlugu yuklendi: {:?}", path),
+                    Err(e) => log::warn!("Kullanici duygu sozlugu atlandi: {}", e),
+                }
+            }
+        }
+        lexicon
+    })
+}
+
+#[tauri::command]
+pub fn analyze_text_sentiment(app: tauri::AppHandle, text: String) -> SentimentResult {
+    analyze(get_lexicon(&app), &text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_lexicon() -> Lexicon {
+        Lexicon::parse(DEFAULT_LEXICON).unwrap()
+    }
+
+    #[test]
+    fn test_default_lexicon_parses() {
+        let lexicon = default_lexicon();
+        assert!(lexicon.words.len() + lexicon.stems.len() > 150);
+        assert!(lexicon.negators_post.contains("değilim"));
+        assert!(Lexicon::parse("[words]\nmutlu cok joy").unwrap_err().contains("satir 2"));
+        assert!(Lexicon::parse("mutlu 3 joy").is_err());
+    }
+
+    #[test]
+    fn test_turkish_polarity_and_emotion() {
+        let lexicon = default_lexicon();
+        let pos = analyze(&lexicon, "Bugün çok mutluyum, her şey harika!");
+        assert_eq!(pos.label, "positive");
+        assert_eq!(pos.dominant_emotion, "joy");
+        assert!(pos.confidence > 0.8);
+        assert_eq!(pos.word_scores[0].0, "mutluyum");
+        assert!((pos.word_scores[0].1 - 4.5).abs() < 1e-4, "cok = 1.5x");
+
+        let neg = analyze(&lexicon, "Bu karar beni gerçekten sinirlendirdi.");
+        assert_eq!(neg.label, "negative");
+        assert_eq!(neg.dominant_emotion, "anger");
+
+        let neutral = analyze(&lexicon, "Toplantı saat üçte başlayacak.");
+        assert_eq!(neutral.label, "neutral");
+        assert_eq!(neutral.confidence, 0.0);
+        assert_eq!(neutral.dominant_emotion, "neutral");
+    }
+
+    #[test]
+    fn test_negation() {
+        let lexicon = default_lexicon();
+        // Olumsuzluk eki
+        assert_eq!(analyze(&lexicon, "Filmi hiç beğenmedim").label, "negative");
+        assert_eq!(analyze(&lexicon, "Sorunsuz bir kurulumdu").label, "positive");
+        // Mastar eki olumsuzluk degil
+        assert_eq!(analyze(&lexicon, "Kazanmak istiyorum").label, "positive");
+        // degil / yok
+        let not_happy = analyze(&lexicon, "Mutlu değilim");
+        assert_eq!(not_happy.label, "negative");
+        assert_eq!(not_happy.dominant_emotion, "neutral");
+        assert_eq!(analyze(&lexicon, "Hiç sorun yok").label, "positive");
+        // Ingilizce
+        assert_eq!(analyze(&lexicon, "This is not very good").label, "negative");
+        assert_eq!(analyze(&lexicon, "I don't hate it").label, "positive");
+    }
+
+    #[test]
+    fn test_intensifiers_and_user_extension() {
+        let mut lexicon = default_lexicon();
+        let plain = analyze(&lexicon, "güzel").score;
+        let strong = analyze(&lexicon, "çok çok güzel").score;
+        let weak = analyze(&lexicon, "biraz güzel").score;
+        assert!(strong > plain && plain > weak && weak > 0.0);
+        assert_eq!(analyze(&lexicon, "son derece kötü").word_scores, vec![("kötü".to_string(), -3.0 * 1.6)]);
+        assert_eq!(analyze(&lexicon, "ne yazık ki").word_scores[0].0, "ne yazık");
+
+        lexicon.extend("[words]\nfısıltı* 4 joy\ngüzel -1 -").unwrap();
+        assert_eq!(analyze(&lexicon, "Fısıltı'yı seviyorum").dominant_emotion, "joy");
+        assert_eq!(analyze(&lexicon, "güzel").label, "negative");
+    }
+}
@@ -58,6 +58,11 @@ pub struct AppSettings {
     pub gemini_api_key: String,
     #[serde(default = "default_ollama_model")]
     pub ollama_model: String,
+    #[serde(default = "default_ollama_url")]
+    pub ollama_url: String,
+    /// "openai" saglayicisi icin OpenAI uyumlu API adresi (LM Studio, vLLM vb.)
+    #[serde(default = "default_llm_base_url")]
+    pub llm_base_url: String,
     // Canli Ceviri
     #[serde(default)]
     pub live_translation_source_lang: String,
@@ -69,11 +74,100 @@ pub struct AppSettings {
     pub live_translation_vad_sensitivity: String,
     #[serde(default = "default_live_translation_shortcut")]
     pub live_translation_shortcut: String,
+    // Whisper Decoding
+    #[serde(default = "default_decoding_profile")]
+    pub decoding_profile: String,
+    #[serde(default = "default_decoding_profiles")]
+    pub decoding_profiles: Vec<DecodingProfile>,
+    /// Acilista secili modeli arka planda yukle
+    #[serde(default = "default_true")]
+    pub preload_model: bool,
+    /// Bu kadar dakika kullanilmayan model bellekten bosaltilir (0 = hic)
+    #[serde(default = "default_model_idle_timeout_mins")]
+    pub model_idle_timeout_mins: u32,
+    /// Whisper kaydi sirasinda canli onizleme (ikinci bir decoder calistirir)
+    #[serde(default = "default_true")]
+    pub whisper_live_preview: bool,
+    /// Whisper'in yerlesik Ingilizce cevirisi (turbo modeller desteklemez)
+    #[serde(default)]
+    pub whisper_translate: bool,
     // Feature Flags
     #[serde(default)]
     pub features: FeatureFlags,
 }
 
+/// Whisper decoding parametreleri — hiz/dogruluk dengesi profili
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct DecodingProfile {
+    pub name: String,
+    /// "beam" veya "greedy"
+    pub strategy: String,
+    pub beam_size: i32,
+    pub best_of: i32,
+    pub temperature: f32,
+    pub temperature_inc: f32,
+    pub entropy_thold: f32,
+    pub logprob_thold: f32,
+    pub no_speech_thold: f32,
+    /// 0 = tum cekirdekler
+    pub threads: i32,
+}
+
+pub const PROFILE_ACCURATE_BEAM: &str = "accurate_beam";
+pub const PROFILE_FAST_GREEDY: &str = "fast_greedy";
+pub const PROFILE_LOW_POWER: &str = "low_power";
+
+impl Default for DecodingProfile {
+    /// Dogru BeamSearch — onceki sabit degerlerle ayni
+    fn default() -> Self {
+        Self {
+            name: PROFILE_ACCURATE_BEAM.to_string(),
+            strategy: "beam".to_string(),
+            beam_size: 5,
+            best_of: 5,
+            temperature: 0.0,
+            temperature_inc: 0.2,
+            entropy_thold: 2.4,
+            logprob_thold: -0.8,
+            no_speech_thold: 0.3,
+            threads: 0,
+        }
+    }
+}
+
+impl DecodingProfile {
+    pub fn is_greedy(&self) -> bool {
+        self.strategy == "greedy"
+    }
+}
+
+impl AppSettings {
+    /// Isme gore decoding profili bul; `None` ise ayarlardaki secili profil.
+    /// Bilinmeyen isimde varsayilan (accurate_beam) profile duser.
+    pub fn decoding_profile(&self, name: Option<&str>) -> DecodingProfile {
+        let name = name.unwrap_or(&self.decoding_profile);
+        self.decoding_profiles
+            .iter()
+            .find(|p| p.name == name)
+            .cloned()
+            .or_else(|| default_decoding_profiles().into_iter().find(|p| p.name == name))
+            .unwrap_or_else(|| {
+                log::warn!("Decoding profili bulunamadi: {}, varsayilan kullaniliyor", name);
+                DecodingProfile::default()
+            })
+    }
+
+    /// Dil tespiti yalnizca Whisper'da var; bulut motorlari ve metin isleme
+    /// "auto" yerine varsayilan dili (Turkce) kullanir.
+    pub fn spoken_language(&self) -> &str {
+        if self.language == crate::transcription::AUTO_LANGUAGE {
+            "tr"
+        } else {
+            &self.language
+        }
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct FeatureFlags {
     #[serde(default = "default_true")]
@@ -127,6 +221,39 @@ impl Default for FeatureFlags {
     }
 }
 
+fn default_decoding_profile() -> String {
+    PROFILE_ACCURATE_BEAM.to_string()
+}
+
+pub fn default_decoding_profiles() -> Vec<DecodingProfile> {
+    vec![
+        DecodingProfile::default(),
+        // Greedy: 2-3x hizli, tek gecis; sicaklik fallback'i kapali
+        DecodingProfile {
+            name: PROFILE_FAST_GREEDY.to_string(),
+            strategy: "greedy".to_string(),
+            beam_size: 1,
+            best_of: 1,
+            temperature_inc: 0.0,
+            ..DecodingProfile::default()
+        },
+        // Pil modu: greedy + 2 thread, daha az fallback
+        DecodingProfile {
+            name: PROFILE_LOW_POWER.to_string(),
+            strategy: "greedy".to_string(),
+            beam_size: 1,
+            best_of: 1,
+            temperature_inc: 0.0,
+            threads: 2,
+            ..DecodingProfile::default()
+        },
+    ]
+}
+
+fn default_model_idle_timeout_mins() -> u32 {
+    15
+}
+
 fn default_ai_provider() -> String {
     "groq".to_string()
 }
@@ -135,6 +262,14 @@ fn default_ollama_model() -> String {
     "llama3.1".to_string()
 }
 
+fn default_ollama_url() -> String {
+    "http://localhost:11434".to_string()
+}
+
+fn default_llm_base_url() -> String {
+    "https://api.openai.com/v1".to_string()
+}
+
 fn default_tts_shortcut() -> String {
     "Ctrl+Shift+R".to_string()
 }
@@ -214,12 +349,460 @@ impl Default for AppSettings {
             groq_api_key: String::new(),
             gemini_api_key: String::new(),
             ollama_model: "llama3.1".to_string(),
+            ollama_url: default_ollama_url(),
+            llm_base_url: default_llm_base_url(),
             live_translation_source_lang: String::new(),
             live_translation_target_lang: "en".to_string(),
             live_translation_device_id: None,
             live_translation_vad_sensitivity: "medium".to_string(),
             live_translation_shortcut: "Ctrl+Shift+L".to_string(),
+            decoding_profile: default_decoding_profile(),
+            decoding_profiles: default_decoding_profiles(),
+            preload_model: true,
+            model_idle_timeout_mins: 15,
+            whisper_live_preview: true,
+            whisper_translate: false,
             features: FeatureFlags::default(),
         }
     }
 }
+
+// ─── Kalicilik ───
+
+impl AppSettings {
+    /// Store anahtarlarindan ayarlari oku; eksik ya da bozuk anahtar varsayilana duser.
+    /// `store_entries` ile ayni anahtarlari kullanir — yeni alan ikisine birden eklenmeli.
+    pub fn load(get: impl Fn(&str) -> Option<serde_json::Value>) -> Self {
+        let model = get("selected_model")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_else(|| "large-v3-turbo-q5".to_string());
+        let device = get("selected_device")
+            .and_then(|v| v.as_str().map(|s| s.to_string()));
+        let theme = get("theme")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_else(|| "dark".to_string());
+        let shortcut = get("shortcut")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_else(|| "Ctrl+Shift+Space".to_string());
+        let recording_mode = get("recording_mode")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_else(|| "button".to_string());
+        let vad_threshold = get("vad_threshold")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.3) as f32;
+        let auto_paste = get("auto_paste")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        let language = get("language")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_else(|| "tr".to_string());
+        let transcription_engine = get("transcription_engine")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_else(|| "web".to_string());
+        let deepgram_api_key = get("deepgram_api_key")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_default();
+        let azure_speech_key = get("azure_speech_key")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_default();
+        let azure_speech_region = get("azure_speech_region")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_default();
+        let google_cloud_api_key = get("google_cloud_api_key")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_default();
+        let voice_activation = get("voice_activation")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let wake_word = get("wake_word")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_else(|| "fısıltı".to_string());
+        let sound_enabled = get("sound_enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        let auto_start = get("auto_start")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let silence_timeout = get("silence_timeout")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(4.0) as f32;
+        let max_record_duration = get("max_record_duration")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(60.0) as f32;
+        let turkish_corrections = get("turkish_corrections")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        let hallucination_filter = get("hallucination_filter")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        let overlay_follow_cursor = get("overlay_follow_cursor")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        let auto_punctuation = get("auto_punctuation")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        let auto_capitalization = get("auto_capitalization")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        let preserve_english_words = get("preserve_english_words")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        let auto_comma = get("auto_comma")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        let paragraph_break = get("paragraph_break")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let notifications = get("notifications")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        let log_level = get("log_level")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_else(|| "info".to_string());
+        let tts_shortcut = get("tts_shortcut")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_else(|| "Ctrl+Shift+R".to_string());
+        let decoding_profile = get("decoding_profile")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_else(|| PROFILE_ACCURATE_BEAM.to_string());
+        let decoding_profiles = get("decoding_profiles")
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_else(default_decoding_profiles);
+        let preload_model = get("preload_model")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        let model_idle_timeout_mins = get("model_idle_timeout_mins")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(15) as u32;
+        let whisper_live_preview = get("whisper_live_preview")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        let whisper_translate = get("whisper_translate")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let defaults = AppSettings::default();
+        let ai_provider = get("ai_provider")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or(defaults.ai_provider);
+        let groq_api_key = get("groq_api_key")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_default();
+        let gemini_api_key = get("gemini_api_key")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_default();
+        let ollama_model = get("ollama_model")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or(defaults.ollama_model);
+        let ollama_url = get("ollama_url")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or(defaults.ollama_url);
+        let llm_base_url = get("llm_base_url")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or(defaults.llm_base_url);
+        let translate_engine = get("translate_engine")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or(defaults.translate_engine);
+        let deepl_api_key = get("deepl_api_key")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_default();
+        let translate_target_lang = get("translate_target_lang")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or(defaults.translate_target_lang);
+        let translate_source_lang = get("translate_source_lang")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or(defaults.translate_source_lang);
+        let translate_auto_detect = get("translate_auto_detect")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        let translate_shortcut = get("translate_shortcut")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or(defaults.translate_shortcut);
+        let live_translation_source_lang = get("live_translation_source_lang")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_default();
+        let live_translation_target_lang = get("live_translation_target_lang")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or(defaults.live_translation_target_lang);
+        let live_translation_device_id = get("live_translation_device_id")
+            .and_then(|v| v.as_str().map(|s| s.to_string()));
+        let live_translation_vad_sensitivity = get("live_translation_vad_sensitivity")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or(defaults.live_translation_vad_sensitivity);
+        let live_translation_shortcut = get("live_translation_shortcut")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or(defaults.live_translation_shortcut);
+        let features = get("features")
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default();
+
+        Self {
+            selected_model: model,
+            selected_device: device,
+            theme,
+            shortcut,
+            recording_mode,
+            vad_threshold,
+            auto_paste,
+            language,
+            transcription_engine,
+            deepgram_api_key,
+            azure_speech_key,
+            azure_speech_region,
+            google_cloud_api_key,
+            voice_activation,
+            wake_word,
+            sound_enabled,
+            auto_start,
+            silence_timeout,
+            max_record_duration,
+            turkish_corrections,
+            hallucination_filter,
+            overlay_follow_cursor,
+            auto_punctuation,
+            auto_capitalization,
+            preserve_english_words,
+            auto_comma,
+            paragraph_break,
+            notifications,
+            log_level,
+            tts_shortcut,
+            decoding_profile,
+            decoding_profiles,
+            preload_model,
+            model_idle_timeout_mins,
+            whisper_live_preview,
+            whisper_translate,
+            ai_provider,
+            groq_api_key,
+            gemini_api_key,
+            ollama_model,
+            ollama_url,
+            llm_base_url,
+            translate_engine,
+            deepl_api_key,
+            translate_target_lang,
+            translate_source_lang,
+            translate_auto_detect,
+            translate_shortcut,
+            live_translation_source_lang,
+            live_translation_target_lang,
+            live_translation_device_id,
+            live_translation_vad_sensitivity,
+            live_translation_shortcut,
+            features,
+        }
+    }
+
+    /// Store'a yazilacak (anahtar, deger) ciftleri
+    pub fn store_entries(&self) -> Vec<(&'static str, serde_json::Value)> {
+        use serde_json::json;
+        vec![
+            ("selected_model", json!(self.selected_model)),
+            ("selected_device", json!(self.selected_device)),
+            ("theme", json!(self.theme)),
+            ("shortcut", json!(self.shortcut)),
+            ("recording_mode", json!(self.recording_mode)),
+            ("vad_threshold", json!(self.vad_threshold)),
+            ("auto_paste", json!(self.auto_paste)),
+            ("language", json!(self.language)),
+            ("transcription_engine", json!(self.transcription_engine)),
+            ("deepgram_api_key", json!(self.deepgram_api_key)),
+            ("azure_speech_key", json!(self.azure_speech_key)),
+            ("azure_speech_region", json!(self.azure_speech_region)),
+            ("google_cloud_api_key", json!(self.google_cloud_api_key)),
+            ("voice_activation", json!(self.voice_activation)),
+            ("wake_word", json!(self.wake_word)),
+            ("sound_enabled", json!(self.sound_enabled)),
+            ("auto_start", json!(self.auto_start)),
+            ("silence_timeout", json!(self.silence_timeout)),
+            ("max_record_duration", json!(self.max_record_duration)),
+            ("turkish_corrections", json!(self.turkish_corrections)),
+            ("hallucination_filter", json!(self.hallucination_filter)),
+            ("overlay_follow_cursor", json!(self.overlay_follow_cursor)),
+            ("auto_punctuation", json!(self.auto_punctuation)),
+            ("auto_capitalization", json!(self.auto_capitalization)),
+            ("preserve_english_words", json!(self.preserve_english_words)),
+            ("auto_comma", json!(self.auto_comma)),
+            ("paragraph_break", json!(self.paragraph_break)),
+            ("notifications", json!(self.notifications)),
+            ("log_level", json!(self.log_level)),
+            ("tts_shortcut", json!(self.tts_shortcut)),
+            ("translate_engine", json!(self.translate_engine)),
+            ("deepl_api_key", json!(self.deepl_api_key)),
+            ("translate_target_lang", json!(self.translate_target_lang)),
+            ("translate_source_lang", json!(self.translate_source_lang)),
+            ("translate_auto_detect", json!(self.translate_auto_detect)),
+            ("translate_shortcut", json!(self.translate_shortcut)),
+            ("ai_provider", json!(self.ai_provider)),
+            ("groq_api_key", json!(self.groq_api_key)),
+            ("gemini_api_key", json!(self.gemini_api_key)),
+            ("ollama_model", json!(self.ollama_model)),
+            ("ollama_url", json!(self.ollama_url)),
+            ("llm_base_url", json!(self.llm_base_url)),
+            ("live_translation_source_lang", json!(self.live_translation_source_lang)),
+            ("live_translation_target_lang", json!(self.live_translation_target_lang)),
+            ("live_translation_device_id", json!(self.live_translation_device_id)),
+            ("live_translation_vad_sensitivity", json!(self.live_translation_vad_sensitivity)),
+            ("live_translation_shortcut", json!(self.live_translation_shortcut)),
+            ("decoding_profile", json!(self.decoding_profile)),
+            ("decoding_profiles", json!(self.decoding_profiles)),
+            ("preload_model", json!(self.preload_model)),
+            ("model_idle_timeout_mins", json!(self.model_idle_timeout_mins)),
+            ("whisper_live_preview", json!(self.whisper_live_preview)),
+            ("whisper_translate", json!(self.whisper_translate)),
+            ("features", json!(self.features)),
+        ]
+    }
+
+    /// Gelen alanlari mevcut ayarlarin uzerine yaz. Tum alanlari gondermeyen
+    /// paneller (ayri `toBackend` kopyalari) kayitli degerleri sifirlamasin.
+    /// Ic ice nesneler (`features` gibi) alan alan birlestirilir.
+    pub fn merged(&self, incoming: serde_json::Value) -> Result<Self, String> {
+        let mut merged = serde_json::to_value(self).map_err(|e| format!("Ayarlar okunamadi: {}", e))?;
+        if incoming.is_object() {
+            merge_json(&mut merged, incoming);
+        }
+        serde_json::from_value(merged).map_err(|e| format!("Gecersiz ayarlar: {}", e))
+    }
+}
+
+/// `incoming` nesnesini `target` uzerine ozyinelemeli yaz; nesne olmayan
+/// degerler (diziler dahil) oldugu gibi degistirilir
+fn merge_json(target: &mut serde_json::Value, incoming: serde_json::Value) {
+    match (target, incoming) {
+        (serde_json::Value::Object(target), serde_json::Value::Object(fields)) => {
+            for (key, value) in fields {
+                match target.get_mut(&key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        target.insert(key, value);
+                    }
+                }
+            }
+        }
+        (target, incoming) => *target = incoming,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decoding_profile_lookup() {
+        let mut settings = AppSettings::default();
+        assert_eq!(settings.decoding_profile(None).name, PROFILE_ACCURATE_BEAM);
+        assert!(settings.decoding_profile(Some(PROFILE_FAST_GREEDY)).is_greedy());
+
+        // Kullanici profili ayni isimli yerlesik profili ezer
+        settings.decoding_profiles = vec![DecodingProfile {
+            name: PROFILE_LOW_POWER.to_string(),
+            threads: 1,
+            ..DecodingProfile::default()
+        }];
+        assert_eq!(settings.decoding_profile(Some(PROFILE_LOW_POWER)).threads, 1);
+        // Listede olmayan yerlesik profil yine bulunur
+        assert!(settings.decoding_profile(Some(PROFILE_FAST_GREEDY)).is_greedy());
+        // Bilinmeyen isim varsayilana duser
+        assert_eq!(settings.decoding_profile(Some("yok")).name, PROFILE_ACCURATE_BEAM);
+    }
+
+    /// Her degeri varsayilandan farkli bir degere cevir
+    fn mutate(value: &serde_json::Value) -> serde_json::Value {
+        use serde_json::Value;
+        match value {
+            Value::Bool(b) => Value::Bool(!b),
+            Value::Number(n) => match n.as_i64() {
+                Some(i) => serde_json::json!(i + 1),
+                None => serde_json::json!(n.as_f64().unwrap_or(0.0) + 1.0),
+            },
+            Value::String(s) => Value::String(format!("{}_x", s)),
+            Value::Null => Value::String("x".to_string()),
+            Value::Array(items) => Value::Array(items.iter().map(mutate).collect()),
+            Value::Object(fields) => Value::Object(
+                fields.iter().map(|(k, v)| (k.clone(), mutate(v))).collect(),
+            ),
+        }
+    }
+
+    #[test]
+    fn test_spoken_language_never_auto() {
+        let mut settings = AppSettings { language: "auto".to_string(), ..AppSettings::default() };
+        assert_eq!(settings.spoken_language(), "tr");
+        settings.language = "en".to_string();
+        assert_eq!(settings.spoken_language(), "en");
+    }
+
+    #[test]
+    fn test_every_field_round_trips_through_store() {
+        let changed = mutate(&serde_json::to_value(AppSettings::default()).unwrap());
+        let settings: AppSettings = serde_json::from_value(changed).unwrap();
+        let expected = serde_json::to_value(&settings).unwrap();
+        assert_ne!(expected, serde_json::to_value(AppSettings::default()).unwrap());
+
+        let store: std::collections::HashMap<_, _> = settings.store_entries().into_iter().collect();
+        let loaded = serde_json::to_value(AppSettings::load(|key| store.get(key).cloned())).unwrap();
+
+        for (key, value) in expected.as_object().unwrap() {
+            assert_eq!(&loaded[key], value, "{} kaydedilip geri okunmuyor", key);
+        }
+    }
+
+    #[test]
+    fn test_store_entries_cover_every_field() {
+        let default = serde_json::to_value(AppSettings::default()).unwrap();
+        let mut fields: Vec<&str> = default.as_object().unwrap().keys().map(|k| k.as_str()).collect();
+        let mut keys: Vec<&str> = AppSettings::default().store_entries().iter().map(|(k, _)| *k).collect();
+        fields.sort_unstable();
+        keys.sort_unstable();
+        assert_eq!(keys, fields);
+    }
+
+    #[test]
+    fn test_partial_save_keeps_other_fields() {
+        let mut current = AppSettings::default();
+        current.features.collaboration = false;
+        current.deepl_api_key = "key".to_string();
+
+        let merged = current
+            .merged(serde_json::json!({ "theme": "light", "translate_engine": "deepl" }))
+            .unwrap();
+        assert_eq!(merged.theme, "light");
+        assert_eq!(merged.translate_engine, "deepl");
+        assert!(!merged.features.collaboration);
+        assert_eq!(merged.deepl_api_key, "key");
+
+        assert!(current.merged(serde_json::json!({ "theme": 5 })).is_err());
+    }
+
+    #[test]
+    fn test_partial_features_keep_other_flags() {
+        let mut current = AppSettings::default();
+        current.features.collaboration = false;
+        current.features.sentiment = true;
+
+        let merged = current
+            .merged(serde_json::json!({ "features": { "mouse_gestures": true } }))
+            .unwrap();
+        assert!(merged.features.mouse_gestures);
+        assert!(!merged.features.collaboration);
+        assert!(merged.features.sentiment);
+    }
+
+    #[test]
+    fn test_deepl_choice_survives_store() {
+        let mut store = std::collections::HashMap::new();
+        let save = |store: &mut std::collections::HashMap<&str, serde_json::Value>, payload| {
+            let current = AppSettings::load(|key| store.get(key).cloned());
+            store.extend(current.merged(payload).unwrap().store_entries());
+        };
+
+        save(&mut store, serde_json::json!({ "translate_engine": "deepl", "deepl_api_key": "abc:fx" }));
+        // Ceviri alanlarini gondermeyen bir panel kaydetse de secim kalir
+        save(&mut store, serde_json::json!({ "theme": "light" }));
+
+        let loaded = AppSettings::load(|key| store.get(key).cloned());
+        assert_eq!(loaded.translate_engine, "deepl");
+        assert_eq!(loaded.deepl_api_key, "abc:fx");
+    }
+}
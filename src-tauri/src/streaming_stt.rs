@@ -0,0 +1,406 @@
+//! Canli (akis) transkripsiyon — kayit surerken Whisper ile ara sonuclar.
+//!
+//! Kaydedicinin yayinladigi 16kHz cerceveler kisa araliklarla toplanir ve
+//! kesinlesmemis ses bir kayan pencerede biriktirilir. Her adimda pencere
+//! yeniden cozulur ve "stt-partial" yayinlanir; art arda iki hipotezde ayni
+//! kalan bas kisim "stable" sayilir (local agreement). VAD konusmanin bittigini
+//! soyledikten sonra ya da pencere dolunca pencere kesinlestirilir ve "stt-final"
+//! yayinlanir. Dikte bittiginde tam kayit yine normal yoldan transkript edilir;
+//! buradaki metin overlay'deki canli onizleme icindir.
+
+use crate::audio::SharedAudioState;
+use crate::settings::PROFILE_FAST_GREEDY;
+use crate::transcription::{StreamDecoder, TranscriptionSegment};
+use crate::vad::{Vad, VadConfig};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+use tauri::Emitter;
+
+const SAMPLE_RATE: usize = 16000;
+/// Tamponun okunma / pencerenin yeniden cozulme araligi
+const STEP_INTERVAL: Duration = Duration::from_millis(700);
+/// Son cozumden beri en az bu kadar yeni ses yoksa pencere tekrar cozulmez (0.3 sn)
+const MIN_NEW_SAMPLES: usize = SAMPLE_RATE * 3 / 10;
+/// Kesinlestirilmeden tutulan maksimum pencere (20 sn)
+const MAX_WINDOW_SAMPLES: usize = SAMPLE_RATE * 20;
+/// Konusma yokken saklanan on kayit — kelime baslari kesilmesin (0.5 sn)
+const PRE_ROLL_SAMPLES: usize = SAMPLE_RATE / 2;
+/// Canli cozum diktenin son transkripsiyonuyla CPU paylasir — cekirdeklerin yarisi
+const STREAM_THREAD_DIVISOR: usize = 2;
+
+#[derive(Clone, Serialize)]
+pub struct PartialHypothesis {
+    pub session_id: u64,
+    /// Art arda iki hipotezde degismeyen bas kisim
+    pub stable: String,
+    /// Henuz degisebilecek son kisim
+    pub unstable: String,
+    pub start_ms: u64,
+}
+
+#[derive(Clone, Serialize)]
+pub struct FinalHypothesis {
+    pub session_id: u64,
+    pub text: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub segments: Vec<TranscriptionSegment>,
+}
+
+#[derive(Clone, Default, Serialize)]
+pub struct StreamingStatus {
+    pub running: bool,
+    pub session_id: u64,
+    pub language: String,
+    /// Bu oturumda kesinlesen metnin tamami
+    pub text: String,
+}
+
+struct Session {
+    id: u64,
+    stop: Arc<AtomicBool>,
+    status: Arc<Mutex<StreamingStatus>>,
+}
+
+static SESSION: Mutex<Option<Session>> = Mutex::new(None);
+static NEXT_SESSION_ID: AtomicU64 = AtomicU64::new(1);
+
+// ─── Pencere Yonetimi ───
+
+#[derive(Debug, PartialEq)]
+enum StepAction {
+    /// Cozulecek yeni konusma yok
+    Idle,
+    /// Pencereyi coz ve ara sonuc yayinla
+    Partial,
+    /// Pencereyi coz, kesinlestir ve temizle
+    Finalize,
+}
+
+/// Kesinlesmemis 16kHz ses ve VAD durumu
+struct StreamWindow {
+    audio: Vec<f32>,
+    /// Kaydin basindan pencere basina kadar gecen ornek sayisi
+    start_sample: u64,
+    vad: Vad,
+    has_speech: bool,
+    /// Son cozumde pencere uzunlugu
+    decoded_len: usize,
+    last_hypothesis: String,
+}
+
+impl StreamWindow {
+    fn new(vad_config: VadConfig) -> Self {
+        // Zaman asimi olayi burada kullanilmaz; kesinlestirme konusma bitisine baglidir
+        let vad_config = VadConfig { silence_timeout_ms: 0, ..vad_config };
+        Self {
+            audio: Vec::new(),
+            start_sample: 0,
+            vad: Vad::new(vad_config, SAMPLE_RATE as u32),
+            has_speech: false,
+            decoded_len: 0,
+            last_hypothesis: String::new(),
+        }
+    }
+
+    fn push(&mut self, samples: &[f32]) -> StepAction {
+        self.vad.process(samples);
+        self.audio.extend_from_slice(samples);
+        if self.vad.is_speech() {
+            self.has_speech = true;
+        }
+
+        if !self.has_speech {
+            // Konusma baslamadiysa yalnizca on kaydi tut
+            if self.audio.len() > PRE_ROLL_SAMPLES {
+                let drop = self.audio.len() - PRE_ROLL_SAMPLES;
+                self.audio.drain(..drop);
+                self.start_sample += drop as u64;
+            }
+            return StepAction::Idle;
+        }
+
+        if !self.vad.is_speech() || self.audio.len() >= MAX_WINDOW_SAMPLES {
+            return StepAction::Finalize;
+        }
+        if self.audio.len() < self.decoded_len + MIN_NEW_SAMPLES {
+            return StepAction::Idle;
+        }
+        StepAction::Partial
+    }
+
+    fn start_ms(&self) -> u64 {
+        self.start_sample * 1000 / SAMPLE_RATE as u64
+    }
+
+    fn end_ms(&self) -> u64 {
+        (self.start_sample + self.audio.len() as u64) * 1000 / SAMPLE_RATE as u64
+    }
+
+    /// Hipotezi kaydet; (stable, unstable) olarak bol
+    fn update_hypothesis(&mut self, hypothesis: &str) -> (String, String) {
+        self.decoded_len = self.audio.len();
+        let stable_words = agreed_prefix_len(&self.last_hypothesis, hypothesis);
+        self.last_hypothesis = hypothesis.to_string();
+
+        let words: Vec<&str> = hypothesis.split_whitespace().collect();
+        (words[..stable_words].join(" "), words[stable_words..].join(" "))
+    }
+
+    /// Pencereyi kesinlestir ve bir sonraki konusmaya hazirla
+    fn commit(&mut self) {
+        self.start_sample += self.audio.len() as u64;
+        self.audio.clear();
+        self.has_speech = false;
+        self.decoded_len = 0;
+        self.last_hypothesis.clear();
+    }
+}
+
+fn normalize_word(word: &str) -> String {
+    word.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+/// Iki hipotezin ortak bas kismindaki kelime sayisi (noktalama/buyuk harf yok sayilir).
+/// Yeni hipotezin son kelimesi her zaman kararsizdir — yarim kalmis olabilir.
+fn agreed_prefix_len(previous: &str, current: &str) -> usize {
+    let current_words: Vec<&str> = current.split_whitespace().collect();
+    let agreed = previous
+        .split_whitespace()
+        .zip(current_words.iter())
+        .take_while(|(a, b)| normalize_word(a) == normalize_word(b))
+        .count();
+    agreed.min(current_words.len().saturating_sub(1))
+}
+
+// ─── Oturum ───
+
+fn stream_threads() -> i32 {
+    std::thread::available_parallelism()
+        .map(|n| (n.get() / STREAM_THREAD_DIVISOR).max(1) as i32)
+        .unwrap_or(2)
+}
+
+/// Kaydedici cercevelerini izleyip pencereyi cozen dongu
+fn run_session(
+    app: tauri::AppHandle,
+    audio: &'static SharedAudioState,
+    mut decoder: StreamDecoder,
+    vad_config: VadConfig,
+    session_id: u64,
+    stop: Arc<AtomicBool>,
+    status: Arc<Mutex<StreamingStatus>>,
+) -> Result<(), String> {
+    // Kaydedici 16kHz cerceveleri yayinlar; kayit bitince kanal kapanir
+    let frames = audio.subscribe();
+    let mut window = StreamWindow::new(vad_config);
+
+    loop {
+        std::thread::sleep(STEP_INTERVAL);
+        let mut new_samples = Vec::new();
+        let mut recording_ended = false;
+        loop {
+            match frames.try_recv() {
+                Ok(frame) => new_samples.extend_from_slice(&frame),
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    recording_ended = true;
+                    break;
+                }
+            }
+        }
+        // Acik durdurmada son pencere cozulmez — kaydin tamami zaten ayrica
+        // transkribe ediliyor, ikinci bir decode yalnizca sonucu geciktirir
+        if stop.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+        let action = window.push(&new_samples);
+
+        let action = if recording_ended && window.has_speech { StepAction::Finalize } else { action };
+        if action != StepAction::Idle {
+            let context = status.lock().text.clone();
+            let (text, segments) = decoder.decode(&window.audio, &context, window.start_ms())?;
+            let text = text.trim().to_string();
+
+            if action == StepAction::Finalize {
+                if !text.is_empty() {
+                    let event = FinalHypothesis {
+                        session_id,
+                        text: text.clone(),
+                        start_ms: window.start_ms(),
+                        end_ms: window.end_ms(),
+                        segments,
+                    };
+                    {
+                        let mut status = status.lock();
+                        if !status.text.is_empty() {
+                            status.text.push(' ');
+                        }
+                        status.text.push_str(&text);
+                        status.language = decoder.language().to_string();
+                    }
+                    app.emit("stt-final", event).ok();
+                }
+                window.commit();
+            } else {
+                let (stable, unstable) = window.update_hypothesis(&text);
+                let event = PartialHypothesis { session_id, stable, unstable, start_ms: window.start_ms() };
+                app.emit("stt-partial", event).ok();
+            }
+        }
+
+        if recording_ended {
+            return Ok(());
+        }
+    }
+}
+
+fn finish_session(app: &tauri::AppHandle, session_id: u64) {
+    let status = {
+        let mut session = SESSION.lock();
+        match session.as_ref() {
+            Some(s) if s.id == session_id => session.take().map(|s| s.status.lock().clone()),
+            _ => None,
+        }
+    };
+    let mut status = status.unwrap_or_default();
+    status.running = false;
+    status.session_id = session_id;
+    app.emit("stt-stopped", status).ok();
+}
+
+// ─── Tauri Komutlari ───
+
+/// Aktif kayit icin canli transkripsiyonu baslat. Kayit durunca oturum kendiliginden biter.
+#[tauri::command]
+pub fn start_streaming_transcription(app: tauri::AppHandle, model_id: Option<String>) -> Result<u64, String> {
+    let audio = crate::commands::audio::get_state();
+    if !audio.is_recording.load(Ordering::SeqCst) {
+        return Err("Canli transkripsiyon icin once kayit baslatilmali".to_string());
+    }
+    if SESSION.lock().is_some() {
+        return Err("Canli transkripsiyon zaten calisiyor".to_string());
+    }
+
+    let settings = crate::commands::settings::get_settings(app.clone());
+    if !settings.whisper_live_preview {
+        return Err("Canli onizleme ayarlarda kapali".to_string());
+    }
+    let model_id = model_id.unwrap_or_else(|| settings.selected_model.clone());
+    let models_dir = crate::model::get_models_dir(&app);
+    let model_path = crate::model::get_model_path(&models_dir, &model_id)
+        .ok_or_else(|| format!("Model bulunamadi: {}", model_id))?;
+
+    // Canli onizleme hiz icin greedy profil kullanir
+    let mut decoding = settings.decoding_profile(Some(PROFILE_FAST_GREEDY));
+    decoding.threads = stream_threads();
+    let decoder = StreamDecoder::new(&model_path, &settings.language, decoding)?;
+    let vad_config = VadConfig::from_settings(&settings);
+
+    let session_id = NEXT_SESSION_ID.fetch_add(1, Ordering::SeqCst);
+    let stop = Arc::new(AtomicBool::new(false));
+    let status = Arc::new(Mutex::new(StreamingStatus {
+        running: true,
+        session_id,
+        language: settings.language.clone(),
+        text: String::new(),
+    }));
+    *SESSION.lock() = Some(Session { id: session_id, stop: stop.clone(), status: status.clone() });
+
+    log::info!("Canli transkripsiyon basladi (oturum {}, model {})", session_id, model_id);
+    std::thread::spawn(move || {
+        if let Err(e) = run_session(app.clone(), audio, decoder, vad_config, session_id, stop, status) {
+            log::error!("Canli transkripsiyon hatasi: {}", e);
+            app.emit("stt-error", e).ok();
+        }
+        finish_session(&app, session_id);
+        log::info!("Canli transkripsiyon bitti (oturum {})", session_id);
+    });
+
+    Ok(session_id)
+}
+
+/// Canli transkripsiyonu durdur; son pencere cozulmeden "stt-stopped" yayinlanir.
+/// Kayit kendiliginden biterse son pencere "stt-final" olarak kesinlestirilir.
+#[tauri::command]
+pub fn stop_streaming_transcription() {
+    if let Some(session) = SESSION.lock().as_ref() {
+        session.stop.store(true, Ordering::SeqCst);
+    }
+}
+
+#[tauri::command]
+pub fn get_streaming_status() -> StreamingStatus {
+    SESSION
+        .lock()
+        .as_ref()
+        .map(|s| s.status.lock().clone())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{silence, tone};
+
+    #[test]
+    fn test_agreed_prefix_len() {
+        assert_eq!(agreed_prefix_len("", "merhaba dunya"), 0);
+        assert_eq!(agreed_prefix_len("Merhaba dunya bu", "merhaba, dunya bugun guzel"), 2);
+        // Son kelime ayni olsa bile kararsiz kalir
+        assert_eq!(agreed_prefix_len("merhaba dunya", "merhaba dunya"), 1);
+    }
+
+    #[test]
+    fn test_window_keeps_only_pre_roll_before_speech() {
+        let mut window = StreamWindow::new(VadConfig::default());
+        assert_eq!(window.push(&silence(3000)), StepAction::Idle);
+        assert_eq!(window.audio.len(), PRE_ROLL_SAMPLES);
+        assert_eq!(window.start_ms(), 2500);
+    }
+
+    #[test]
+    fn test_window_partial_then_finalize_on_speech_end() {
+        let mut window = StreamWindow::new(VadConfig::default());
+        window.push(&silence(1000));
+        assert_eq!(window.push(&tone(1000)), StepAction::Partial);
+
+        let (stable, unstable) = window.update_hypothesis("bugun hava");
+        assert_eq!((stable.as_str(), unstable.as_str()), ("", "bugun hava"));
+        // Yeterli yeni ses gelmeden tekrar cozulmez
+        assert_eq!(window.push(&tone(100)), StepAction::Idle);
+        assert_eq!(window.push(&tone(500)), StepAction::Partial);
+        let (stable, unstable) = window.update_hypothesis("bugun hava cok");
+        assert_eq!((stable.as_str(), unstable.as_str()), ("bugun hava", "cok"));
+
+        assert_eq!(window.push(&silence(1000)), StepAction::Finalize);
+        let end = window.end_ms();
+        window.commit();
+        assert!(window.audio.is_empty() && !window.has_speech);
+        assert_eq!(window.start_ms(), end);
+    }
+
+    #[test]
+    fn test_window_finalizes_when_full() {
+        let mut window = StreamWindow::new(VadConfig::default());
+        window.push(&silence(500));
+        // Hangover'dan kisa duraklamali kesintisiz konusma
+        let mut speech = tone(800);
+        speech.extend(silence(200));
+        let mut action = StepAction::Idle;
+        for _ in 0..25 {
+            action = window.push(&speech);
+            if action == StepAction::Finalize {
+                break;
+            }
+        }
+        assert_eq!(action, StepAction::Finalize);
+        assert!(window.audio.len() >= MAX_WINDOW_SAMPLES);
+    }
+}
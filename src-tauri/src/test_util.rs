@@ -0,0 +1,63 @@
+//! Testlerde ortak kullanilan ses fiksturleri ve yerel HTTP mock sunucusu
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Fikstur ornekleme hizi (Whisper girisiyle ayni)
+pub const RATE: u32 = 16000;
+
+/// 220 Hz sinus — VAD'in konusma sayacagi seviyede
+pub fn tone(ms: u32) -> Vec<f32> {
+    (0..RATE * ms / 1000)
+        .map(|i| 0.2 * (2.0 * std::f32::consts::PI * 220.0 * i as f32 / RATE as f32).sin())
+        .collect()
+}
+
+/// Sabit genlikli hafif arka plan gurultusu (~-60 dB)
+pub fn silence(ms: u32) -> Vec<f32> {
+    (0..RATE * ms / 1000)
+        .map(|i| if i % 2 == 0 { 0.001 } else { -0.001 })
+        .collect()
+}
+
+/// Tek istege verilen durum koduyla sabit JSON donen yerel mock sunucu.
+/// Adres ve gelen ham istegi (baslik + govde) donduren handle verir.
+pub async fn mock_server(status: u16, body: &'static str) -> (String, tokio::task::JoinHandle<String>) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 4096];
+        // Baslik + govde gelene kadar oku (Content-Length kadar)
+        loop {
+            let n = socket.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&request);
+            if let Some(header_end) = text.find("\r\n\r\n") {
+                let content_length = text[..header_end]
+                    .lines()
+                    .find_map(|l| {
+                        l.to_lowercase()
+                            .strip_prefix("content-length:")
+                            .map(|v| v.trim().parse::<usize>().unwrap_or(0))
+                    })
+                    .unwrap_or(0);
+                if request.len() >= header_end + 4 + content_length {
+                    break;
+                }
+            }
+            if n == 0 {
+                break;
+            }
+        }
+        let response = format!(
+            "HTTP/1.1 {} OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        socket.write_all(response.as_bytes()).await.unwrap();
+        String::from_utf8_lossy(&request).to_string()
+    });
+    (url, handle)
+}
@@ -1,26 +1,259 @@
+use crate::settings::DecodingProfile;
 use parking_lot::Mutex;
-use std::path::PathBuf;
-use std::sync::OnceLock;
-use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters, WhisperState};
 
 pub struct TranscriptionResult {
     pub text: String,
     pub duration_ms: u64,
+    pub segments: Vec<TranscriptionSegment>,
+    /// Kullanilan dil — "auto" modunda Whisper'in tespit ettigi dil
+    pub language: String,
+    /// Tespit edilen dilin olasiligi (sadece "auto" modunda)
+    pub language_probability: Option<f32>,
 }
 
-struct CachedModel {
-    ctx: WhisperContext,
+/// Whisper segmenti — zamanlar kaydin basindan itibaren milisaniye cinsinden
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct TranscriptionSegment {
+    pub text: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    /// Kelimeler ve guvenleri; zamanlar yalnizca `word_timestamps` acikken hassas
+    #[serde(default)]
+    pub words: Vec<TranscriptionWord>,
+    /// Metin token'larinin ortalama olasiligi (0..1)
+    #[serde(default)]
+    pub confidence: f32,
+    /// Whisper'in "bu segmentte konusma yok" olasiligi (0..1)
+    #[serde(default)]
+    pub no_speech_prob: f32,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct TranscriptionWord {
+    pub text: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    /// Kelimeyi olusturan token'larin ortalama olasiligi (0..1)
+    #[serde(default)]
+    pub confidence: f32,
+}
+
+/// Bu olasiligin altindaki kelimeler "dusuk guvenli" sayilir
+pub const LOW_CONFIDENCE_THRESHOLD: f32 = 0.5;
+/// Konusma-yok olasiligi bu esigi asan ve guveni dusuk segmentler halusinasyon sayilir
+const HALLUCINATION_NO_SPEECH_PROB: f32 = 0.6;
+const HALLUCINATION_MAX_CONFIDENCE: f32 = 0.4;
+
+/// Whisper token'i — metin parcasi, zaman (centisecond) ve olasilik
+struct TokenPiece {
+    text: String,
+    t0: i64,
+    t1: i64,
+    p: f32,
+}
+
+/// Ilerleme bildirimi — 0..=100 yuzde
+pub type ProgressCallback = Arc<dyn Fn(i32) + Send + Sync>;
+
+/// Bu dil kodu verildiginde Whisper dil tespiti yapilir
+pub const AUTO_LANGUAGE: &str = "auto";
+
+/// Iptal edilen transkripsiyonun hata mesaji
+pub const CANCELLED_ERROR: &str = "Transkripsiyon iptal edildi";
+
+/// Transkripsiyon secenekleri
+#[derive(Clone)]
+pub struct TranscriptionOptions<'a> {
+    pub language: &'a str,
+    /// Hassas token zamanlari (kelime zaman damgalari icin)
+    pub word_timestamps: bool,
+    /// 60 saniyeden uzun sesi kirpmak yerine sessizlik sinirlarindan parcala
+    pub long_form: bool,
+    /// Whisper ilerleme callback'i (uzun kayitta tum parcalara yayilir)
+    pub progress: Option<ProgressCallback>,
+    /// true yapildiginda Whisper abort callback'i ile islem durdurulur
+    pub cancel: Option<Arc<AtomicBool>>,
+    /// Beam/greedy, sicaklik, esikler ve thread sayisi
+    pub decoding: DecodingProfile,
+    /// Whisper'in yerlesik cevirisi — cikti her zaman Ingilizce
+    pub translate: bool,
+    /// Konusma-yok olasiligi yuksek ve guveni dusuk segmentleri at
+    pub drop_hallucinations: bool,
+}
+
+impl<'a> TranscriptionOptions<'a> {
+    pub fn new(language: &'a str) -> Self {
+        Self {
+            language,
+            word_timestamps: false,
+            long_form: false,
+            progress: None,
+            cancel: None,
+            decoding: DecodingProfile::default(),
+            translate: false,
+            drop_hallucinations: true,
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel.as_ref().is_some_and(|c| c.load(Ordering::Relaxed))
+    }
+}
+
+// ─── Model Onbellegi ───
+
+/// Bellekte ayni anda tutulan maksimum model sayisi (ornegin small + large-v3-turbo)
+const MODEL_CACHE_CAPACITY: usize = 2;
+
+struct CachedModel<T> {
+    /// Arc: transkripsiyon suresince cache kilidi tutulmaz
+    ctx: Arc<T>,
     model_path: String,
+    last_used: Instant,
 }
 
-static CACHED_MODEL: OnceLock<Mutex<Option<CachedModel>>> = OnceLock::new();
+/// Kucuk LRU model onbellegi — en son kullanilan sonda
+struct ModelCache<T> {
+    entries: Vec<CachedModel<T>>,
+    capacity: usize,
+}
+
+impl<T> ModelCache<T> {
+    fn new(capacity: usize) -> Self {
+        Self { entries: Vec::new(), capacity }
+    }
 
-fn get_cache() -> &'static Mutex<Option<CachedModel>> {
-    CACHED_MODEL.get_or_init(|| Mutex::new(None))
+    /// Modeli dondur ve en son kullanilan olarak isaretle
+    fn get(&mut self, model_path: &str) -> Option<Arc<T>> {
+        let idx = self.entries.iter().position(|e| e.model_path == model_path)?;
+        let mut entry = self.entries.remove(idx);
+        entry.last_used = Instant::now();
+        let ctx = entry.ctx.clone();
+        self.entries.push(entry);
+        Some(ctx)
+    }
+
+    /// Modeli ekle; kapasite asilirsa en uzun suredir kullanilmayani cikar
+    fn insert(&mut self, model_path: &str, ctx: Arc<T>) {
+        self.entries.retain(|e| e.model_path != model_path);
+        self.entries.push(CachedModel {
+            ctx,
+            model_path: model_path.to_string(),
+            last_used: Instant::now(),
+        });
+        while self.entries.len() > self.capacity {
+            let evicted = self.entries.remove(0);
+            log::info!("Model onbellekten cikarildi (LRU): {}", evicted.model_path);
+        }
+    }
+
+    /// `timeout` suresince kullanilmayan modelleri cikar, cikarilan yollari dondur
+    fn evict_idle(&mut self, now: Instant, timeout: Duration) -> Vec<String> {
+        let mut evicted = Vec::new();
+        self.entries.retain(|e| {
+            let idle = now.saturating_duration_since(e.last_used) >= timeout;
+            if idle {
+                evicted.push(e.model_path.clone());
+            }
+            !idle
+        });
+        evicted
+    }
+
+    fn remove(&mut self, model_path: &str) -> bool {
+        let before = self.entries.len();
+        self.entries.retain(|e| e.model_path != model_path);
+        self.entries.len() != before
+    }
+
+    fn paths(&self) -> Vec<String> {
+        self.entries.iter().map(|e| e.model_path.clone()).collect()
+    }
 }
 
-/// Maksimum ses uzunlugu: 60 saniye (16kHz * 60 = 960000 sample)
+static MODEL_CACHE: OnceLock<Mutex<ModelCache<WhisperContext>>> = OnceLock::new();
+
+fn get_cache() -> &'static Mutex<ModelCache<WhisperContext>> {
+    MODEL_CACHE.get_or_init(|| Mutex::new(ModelCache::new(MODEL_CACHE_CAPACITY)))
+}
+
+/// Modeli onbellekten al ya da diskten yukle.
+/// Kilit yukleme boyunca tutulur: isitma sirasinda gelen dikte ayni modeli iki kez yuklemez.
+fn load_model(model_path: &Path) -> Result<Arc<WhisperContext>, String> {
+    let model_str = model_path
+        .to_str()
+        .ok_or_else(|| "Model yolu gecersiz".to_string())?;
+
+    let mut cache = get_cache().lock();
+    if let Some(ctx) = cache.get(model_str) {
+        return Ok(ctx);
+    }
+
+    let start = Instant::now();
+    log::info!("Model yukleniyor: {}", model_str);
+    let ctx = WhisperContext::new_with_params(model_str, WhisperContextParameters::default())
+        .map_err(|e| format!("Model yuklenemedi: {}", e))?;
+    let ctx = Arc::new(ctx);
+    cache.insert(model_str, ctx.clone());
+    log::info!("Model yuklendi: {:.1}s", start.elapsed().as_secs_f64());
+    Ok(ctx)
+}
+
+/// Modeli onceden yukle (uygulama acilisinda ilk diktenin beklememesi icin)
+pub fn preload_model(model_path: &Path) -> Result<(), String> {
+    if !model_path.exists() {
+        return Err("Model dosyasi bulunamadi. Lutfen once bir model indirin.".to_string());
+    }
+    load_model(model_path).map(|_| ())
+}
+
+/// `timeout` suresince kullanilmayan modelleri bellekten bosalt.
+/// Calisan bir transkripsiyon Arc'i tuttugu icin bellek o bitince serbest kalir.
+pub fn unload_idle_models(timeout: Duration) -> usize {
+    let evicted = get_cache().lock().evict_idle(Instant::now(), timeout);
+    for path in &evicted {
+        log::info!("Bosta kalan model bosaltildi: {}", path);
+    }
+    evicted.len()
+}
+
+/// Tek bir modeli bellekten cikar (ornegin model dosyasi silindiginde)
+pub fn unload_model(model_path: &Path) -> bool {
+    model_path
+        .to_str()
+        .is_some_and(|p| get_cache().lock().remove(p))
+}
+
+/// Tum modelleri bellekten bosalt
+pub fn unload_all_models() -> usize {
+    let mut cache = get_cache().lock();
+    let count = cache.entries.len();
+    cache.entries.clear();
+    count
+}
+
+/// Bellekte yuklu model yollari (en son kullanilan sonda)
+pub fn loaded_models() -> Vec<String> {
+    get_cache().lock().paths()
+}
+
+/// Tek geciste islenen maksimum ses uzunlugu: 60 saniye (16kHz * 60 = 960000 sample).
+/// Daha uzun kayitlar uzun kayit modunda parcalara bolunur.
 const MAX_AUDIO_SAMPLES: usize = 16000 * 60;
+/// Uzun kayit modu: hedef parca uzunlugu (Whisper penceresi = 30 saniye)
+const LONG_FORM_CHUNK_SAMPLES: usize = 16000 * 30;
+/// Uzun kayit modu: parcalar arasi ortusme (1 saniye)
+const LONG_FORM_OVERLAP_SAMPLES: usize = 16000;
+/// Uzun kayit modu: kesim noktasi icin geriye dogru aranan sessizlik araligi (5 saniye)
+const LONG_FORM_SEARCH_SAMPLES: usize = 16000 * 5;
+/// Sonraki parcaya baglam olarak aktarilan onceki metin uzunlugu (karakter)
+const CONTEXT_CARRY_CHARS: usize = 200;
 /// Minimum ses uzunlugu: 0.5 saniye
 const MIN_AUDIO_SAMPLES: usize = 8000;
 /// Sessizlik RMS esigi
@@ -29,9 +262,10 @@ const SILENCE_RMS_THRESHOLD: f32 = 0.001;
 pub fn transcribe_audio_data(
     audio_data: &[f32],
     model_path: &PathBuf,
-    language: &str,
+    options: &TranscriptionOptions,
 ) -> Result<TranscriptionResult, String> {
-    let start = std::time::Instant::now();
+    let start = Instant::now();
+    let language = options.language;
 
     if !model_path.exists() {
         return Err("Model dosyasi bulunamadi. Lutfen once bir model indirin.".to_string());
@@ -40,10 +274,7 @@ pub fn transcribe_audio_data(
     // Cok kisa ses kontrolu
     if audio_data.len() < MIN_AUDIO_SAMPLES {
         log::info!("Ses cok kisa ({} sample), atlaniyor", audio_data.len());
-        return Ok(TranscriptionResult {
-            text: String::new(),
-            duration_ms: 0,
-        });
+        return Ok(TranscriptionResult::empty(language));
     }
 
     // Sessizlik kontrolu - RMS enerji hesapla
@@ -51,10 +282,7 @@ pub fn transcribe_audio_data(
     log::info!("Ses RMS seviyesi: {:.6}", rms);
     if rms < SILENCE_RMS_THRESHOLD {
         log::info!("Ses cok sessiz (RMS: {:.6}), atlaniyor", rms);
-        return Ok(TranscriptionResult {
-            text: String::new(),
-            duration_ms: 0,
-        });
+        return Ok(TranscriptionResult::empty(language));
     }
 
     // High-pass filtre uygula (50Hz alti gurultuyu kes — Turkce fricatifler icin 80Hz cok agresif)
@@ -63,8 +291,8 @@ pub fn transcribe_audio_data(
     // Sesi normaliz et
     let mut audio_data = normalize_audio(&audio_data);
 
-    // Maksimum uzunluga kirp (60 saniye)
-    if audio_data.len() > MAX_AUDIO_SAMPLES {
+    // Uzun kayit modu kapaliysa maksimum uzunluga kirp (60 saniye)
+    if !options.long_form && audio_data.len() > MAX_AUDIO_SAMPLES {
         log::info!(
             "Ses {} saniyeye kirpildi (orijinal: {} saniye)",
             MAX_AUDIO_SAMPLES / 16000,
@@ -74,56 +302,196 @@ pub fn transcribe_audio_data(
     }
 
     // Bas ve son sessizligi kirp (0.005: kelime sinirlarini korumak icin daha toleransli)
-    audio_data = trim_silence(&audio_data, 0.005);
+    // Kirpilan bas kismi segment zamanlarina geri eklenir
+    let (trim_start, trim_end) = silence_bounds(&audio_data, 0.005);
+    let offset_ms = (trim_start / 16) as u64;
+    audio_data = audio_data[trim_start..trim_end.max(trim_start)].to_vec();
     if audio_data.len() < MIN_AUDIO_SAMPLES {
         log::info!("Sessizlik kirpma sonrasi ses cok kisa, atlaniyor");
-        return Ok(TranscriptionResult {
-            text: String::new(),
-            duration_ms: 0,
-        });
+        return Ok(TranscriptionResult::empty(language));
     }
 
-    let model_str = model_path
-        .to_str()
-        .ok_or_else(|| "Model yolu gecersiz".to_string())?
-        .to_string();
+    // Kilit sadece model yukleme/secme icin tutulur; Whisper calisirken
+    // diger isler (iptal, durum sorgusu, baska transkripsiyon) beklemez
+    let ctx = load_model(model_path)?;
 
-    let mut cache = get_cache().lock();
+    if options.is_cancelled() {
+        return Err(CANCELLED_ERROR.to_string());
+    }
+
+    let mut state = ctx
+        .create_state()
+        .map_err(|e| format!("Whisper state olusturulamadi: {}", e))?;
+
+    // "auto" modunda ilk 30 saniyeden dil tespiti; sonraki tum parcalar bu dille cozulur
+    let (language, language_probability) = if language == AUTO_LANGUAGE {
+        let (detected, probability) =
+            detect_language(&mut state, &audio_data, decoding_threads(&options.decoding) as usize)?;
+        // Dil tespiti abort callback'i desteklemez; iptal burada yakalanir
+        if options.is_cancelled() {
+            return Err(CANCELLED_ERROR.to_string());
+        }
+        log::info!("Tespit edilen dil: {} (olasilik {:.2})", detected, probability);
+        (detected, Some(probability))
+    } else {
+        (language.to_string(), None)
+    };
+
+    // Dinamik initial prompt - dil + domain + kullanici kelimeleri
+    // Fallback: kullanici profili bossa statik prompt kullan.
+    // Ceviri modunda Turkce prompt (ve kullanici kelimeleri) ciktiyi Turkceye ceker;
+    // sabit Ingilizce prompt kullanilir.
+    let dynamic_prompt = if options.translate {
+        TRANSLATE_PROMPT.to_string()
+    } else {
+        crate::corrections::build_dynamic_prompt(&language)
+    };
+
+    log::info!("Decoding profili: {}", options.decoding.name);
 
-    let needs_reload = match &*cache {
-        Some(cached) => cached.model_path != model_str,
-        None => true,
+    // Tespit edilen dil tum Whisper gecislerinde kullanilir
+    let options = TranscriptionOptions { language: &language, ..options.clone() };
+    let (text, segments) = if options.long_form && audio_data.len() > MAX_AUDIO_SAMPLES {
+        transcribe_long_form(&ctx, &mut state, &audio_data, &options, &dynamic_prompt, offset_ms)?
+    } else {
+        run_whisper(&ctx, &mut state, &audio_data, &options, &dynamic_prompt, offset_ms)?
     };
 
-    if needs_reload {
-        log::info!("Model yukleniyor: {}", model_str);
-        let ctx =
-            WhisperContext::new_with_params(&model_str, WhisperContextParameters::default())
-                .map_err(|e| format!("Model yuklenemedi: {}", e))?;
-        *cache = Some(CachedModel {
+    let text = text.trim().to_string();
+    let duration_ms = start.elapsed().as_millis() as u64;
+
+    log::info!(
+        "Transkripsiyon tamamlandi: {}ms, {} karakter, {} segment, metin: {}",
+        duration_ms,
+        text.len(),
+        segments.len(),
+        &text
+    );
+
+    Ok(TranscriptionResult {
+        text,
+        duration_ms,
+        segments,
+        language,
+        language_probability,
+    })
+}
+
+// ─── Canli (Akis) Cozumleme ───
+
+/// "auto" dilinde tespitin sabitlenmesi icin gereken minimum pencere (3 saniye)
+const STREAM_LANG_LOCK_SAMPLES: usize = 16000 * 3;
+
+/// Canli transkripsiyon icin kalici Whisper state'i.
+/// Model onbellekten alinir; kayan pencerenin her adimi ayni state ile cozulur,
+/// boylece her adimda state ayirma maliyeti odenmez.
+pub struct StreamDecoder {
+    ctx: Arc<WhisperContext>,
+    state: WhisperState,
+    language: String,
+    /// Dil sabitlendiginde bir kez olusturulan initial prompt
+    base_prompt: Option<String>,
+    decoding: DecodingProfile,
+}
+
+impl StreamDecoder {
+    pub fn new(model_path: &Path, language: &str, decoding: DecodingProfile) -> Result<Self, String> {
+        if !model_path.exists() {
+            return Err("Model dosyasi bulunamadi. Lutfen once bir model indirin.".to_string());
+        }
+        let ctx = load_model(model_path)?;
+        let state = ctx
+            .create_state()
+            .map_err(|e| format!("Whisper state olusturulamadi: {}", e))?;
+        Ok(Self {
             ctx,
-            model_path: model_str.clone(),
-        });
-        log::info!("Model yuklendi: {:.1}s", start.elapsed().as_secs_f64());
+            state,
+            language: language.to_string(),
+            base_prompt: None,
+            decoding,
+        })
     }
 
-    let cached = cache.as_ref().unwrap();
+    /// Cozumde kullanilan dil ("auto" ise henuz sabitlenmedi)
+    pub fn language(&self) -> &str {
+        &self.language
+    }
 
-    let mut state = cached
-        .ctx
-        .create_state()
-        .map_err(|e| format!("Whisper state olusturulamadi: {}", e))?;
+    /// 16kHz pencereyi coz. `context` onceki kesinlesmis metnin sonudur ve
+    /// prompt'a eklenir. "auto" dilinde pencere 3 saniyeyi gecince dil sabitlenir.
+    pub fn decode(
+        &mut self,
+        audio: &[f32],
+        context: &str,
+        offset_ms: u64,
+    ) -> Result<(String, Vec<TranscriptionSegment>), String> {
+        if audio.len() < MIN_AUDIO_SAMPLES || calculate_rms(audio) < SILENCE_RMS_THRESHOLD {
+            return Ok((String::new(), Vec::new()));
+        }
+        let audio = normalize_audio(&apply_high_pass_filter(audio, 50.0, 16000.0));
+
+        let language = if self.language == AUTO_LANGUAGE {
+            let threads = decoding_threads(&self.decoding) as usize;
+            let (detected, probability) = detect_language(&mut self.state, &audio, threads)?;
+            if audio.len() >= STREAM_LANG_LOCK_SAMPLES {
+                log::info!("Canli transkripsiyon dili: {} (olasilik {:.2})", detected, probability);
+                self.language = detected.clone();
+            }
+            detected
+        } else {
+            self.language.clone()
+        };
+
+        let base_prompt = match &self.base_prompt {
+            Some(prompt) if language == self.language => prompt.clone(),
+            _ => {
+                let prompt = crate::corrections::build_dynamic_prompt(&language);
+                if language == self.language {
+                    self.base_prompt = Some(prompt.clone());
+                }
+                prompt
+            }
+        };
+        let prompt = if context.is_empty() {
+            base_prompt
+        } else {
+            format!("{} {}", base_prompt, tail_chars(context, CONTEXT_CARRY_CHARS))
+        };
+
+        let mut options = TranscriptionOptions::new(&language);
+        options.decoding = self.decoding.clone();
+        run_whisper(&self.ctx, &mut self.state, &audio, &options, &prompt, offset_ms)
+    }
+}
+
+/// Tek bir Whisper gecisi — metin ve zaman damgali segmentleri dondurur.
+/// Dil ve ilerleme callback'i `options`'tan alinir ("auto" burada cozulmus olmali).
+/// `offset_ms` segment zamanlarina eklenir (kirpma / parca baslangici).
+fn run_whisper(
+    ctx: &WhisperContext,
+    state: &mut WhisperState,
+    audio_data: &[f32],
+    options: &TranscriptionOptions,
+    prompt: &str,
+    offset_ms: u64,
+) -> Result<(String, Vec<TranscriptionSegment>), String> {
+    let decoding = &options.decoding;
 
     // BeamSearch decoding — Turkce gibi morfolojik diller icin cok daha dogru
     // Greedy'den 2-3x yavas ama kelime dogrulugu %15-20 daha iyi
-    let mut params = FullParams::new(SamplingStrategy::BeamSearch {
-        beam_size: 5,
-        patience: 1.0,
-    });
+    let strategy = if decoding.is_greedy() {
+        SamplingStrategy::Greedy { best_of: decoding.best_of.max(1) }
+    } else {
+        SamplingStrategy::BeamSearch {
+            beam_size: decoding.beam_size.max(1),
+            patience: 1.0,
+        }
+    };
+    let mut params = FullParams::new(strategy);
 
     // Dil ayarlari
-    params.set_language(Some(language));
-    params.set_translate(false);
+    params.set_language(Some(options.language));
+    params.set_translate(options.translate);
     params.set_print_special(false);
     params.set_print_progress(false);
     params.set_print_realtime(false);
@@ -138,61 +506,371 @@ pub fn transcribe_audio_data(
     // Turkce baglam-bagimli: unlu uyumu, ek morfolojisi icin context her zaman acik
     params.set_no_context(false);
 
-    // Timestamp'ler
-    params.set_no_timestamps(true);
+    // Timestamp'ler — segment zamanlari her zaman, token zamanlari istege bagli
+    params.set_no_timestamps(false);
+    params.set_token_timestamps(options.word_timestamps);
     params.set_max_len(0);
 
-    // Dinamik initial prompt - dil + domain + kullanici kelimeleri
-    // Fallback: kullanici profili bossa statik prompt kullan
-    let dynamic_prompt = crate::corrections::build_dynamic_prompt(language);
-    params.set_initial_prompt(&dynamic_prompt);
+    params.set_initial_prompt(prompt);
 
-    // Kalite/performans ayarlari
-    params.set_n_threads(num_cpus());
-    params.set_entropy_thold(2.4);
+    // Kalite/performans ayarlari (profil; threads 0 = tum cekirdekler)
+    params.set_n_threads(decoding_threads(decoding));
+    params.set_entropy_thold(decoding.entropy_thold);
     // Sicaklik 0.0 = tamamen deterministik (en dogru tek sonuc)
-    params.set_temperature(0.0);
+    params.set_temperature(decoding.temperature);
 
     // Halusinasyon onleme — non-speech token'lari bastir, gercek konusmayi reddetmesin
-    params.set_no_speech_thold(0.3);
+    params.set_no_speech_thold(decoding.no_speech_thold);
     params.set_suppress_blank(true);
     params.set_suppress_nst(true);
 
-    // BeamSearch: sicaklik artisi ile fallback (ilk sonuc basarisizsa 0.2 ile tekrar dene)
-    params.set_temperature_inc(0.2);
-    params.set_logprob_thold(-0.8);
+    // Sicaklik artisi ile fallback (ilk sonuc basarisizsa temperature_inc ile tekrar dene)
+    params.set_temperature_inc(decoding.temperature_inc);
+    params.set_logprob_thold(decoding.logprob_thold);
 
-    state
-        .full(params, &audio_data)
-        .map_err(|e| format!("Transkripsiyon hatasi: {}", e))?;
+    if let Some(progress) = options.progress.clone() {
+        params.set_progress_callback_safe(move |p: i32| progress(p));
+    }
+    if let Some(cancel) = options.cancel.clone() {
+        params.set_abort_callback_safe(move || cancel.load(Ordering::Relaxed));
+    }
+
+    if let Err(e) = state.full(params, audio_data) {
+        if options.is_cancelled() {
+            log::info!("Transkripsiyon kullanici tarafindan iptal edildi");
+            return Err(CANCELLED_ERROR.to_string());
+        }
+        return Err(format!("Transkripsiyon hatasi: {}", e));
+    }
+    if options.is_cancelled() {
+        return Err(CANCELLED_ERROR.to_string());
+    }
 
     let num_segments = state.full_n_segments();
+    let token_eot = ctx.token_eot();
 
     let mut text = String::new();
+    let mut segments = Vec::new();
     for i in 0..num_segments {
         if let Some(segment) = state.get_segment(i) {
             if let Ok(segment_text) = segment.to_str_lossy() {
                 let seg = segment_text.trim();
                 if seg.is_empty() { continue; }
+
+                let mut tokens = Vec::new();
+                for t in 0..segment.n_tokens() {
+                    let Some(token) = segment.get_token(t) else { continue };
+                    // Ozel token'lari (EOT, timestamp, dil vb.) atla
+                    if token.token_id() >= token_eot { continue; }
+                    let data = token.token_data();
+                    if let Ok(piece) = token.to_str_lossy() {
+                        tokens.push(TokenPiece { text: piece.to_string(), t0: data.t0, t1: data.t1, p: data.p });
+                    }
+                }
+
+                let segment_info = TranscriptionSegment {
+                    text: seg.to_string(),
+                    // Whisper zamanlari 10ms biriminde (centisecond)
+                    start_ms: centis_to_ms(segment.start_timestamp()) + offset_ms,
+                    end_ms: centis_to_ms(segment.end_timestamp()) + offset_ms,
+                    // Kelime guvenleri ogrenme icin her zaman gerekli
+                    words: group_tokens_into_words(&tokens, offset_ms),
+                    confidence: mean_probability(&tokens),
+                    no_speech_prob: segment.no_speech_probability(),
+                };
+
+                if options.drop_hallucinations && is_hallucinated(&segment_info) {
+                    log::info!(
+                        "Halusinasyon segmenti atildi (no_speech {:.2}, guven {:.2}): {}",
+                        segment_info.no_speech_prob,
+                        segment_info.confidence,
+                        seg
+                    );
+                    continue;
+                }
+
                 if !text.is_empty() && !text.ends_with(' ') {
                     text.push(' ');
                 }
                 text.push_str(seg);
+                segments.push(segment_info);
             }
         }
     }
 
-    let text = text.trim().to_string();
-    let duration_ms = start.elapsed().as_millis() as u64;
+    Ok((text, segments))
+}
 
+/// Uzun kayit modu: sesi sessizlik sinirlarindan ortusmeli parcalara bol,
+/// her parcayi ayni state ile isle ve metni tekrarsiz birlestir.
+fn transcribe_long_form(
+    ctx: &WhisperContext,
+    state: &mut WhisperState,
+    audio_data: &[f32],
+    options: &TranscriptionOptions,
+    base_prompt: &str,
+    offset_ms: u64,
+) -> Result<(String, Vec<TranscriptionSegment>), String> {
+    let ranges = split_at_silence(
+        audio_data,
+        LONG_FORM_CHUNK_SAMPLES,
+        LONG_FORM_OVERLAP_SAMPLES,
+        LONG_FORM_SEARCH_SAMPLES,
+    );
     log::info!(
-        "Transkripsiyon tamamlandi: {}ms, {} karakter, metin: {}",
-        duration_ms,
-        text.len(),
-        &text
+        "Uzun kayit modu: {:.1}s ses, {} parca",
+        audio_data.len() as f64 / 16000.0,
+        ranges.len()
     );
 
-    Ok(TranscriptionResult { text, duration_ms })
+    let mut text = String::new();
+    let mut segments: Vec<TranscriptionSegment> = Vec::new();
+    // Onceki parcalarin kapsadigi son an — ortusme tekrari zamana gore ayiklanir
+    let mut covered_ms = 0u64;
+
+    // Ortusmeyi ayiklamak icin kelime zamanlari hassas olmali
+    let mut chunk_options = options.clone();
+    chunk_options.word_timestamps = true;
+
+    let total = ranges.len() as i32;
+
+    for (idx, &(chunk_start, chunk_end)) in ranges.iter().enumerate() {
+        if options.is_cancelled() {
+            return Err(CANCELLED_ERROR.to_string());
+        }
+
+        // Parca ilerlemesini genel ilerlemeye olcekle
+        chunk_options.progress = options.progress.clone().map(|cb| {
+            let done = idx as i32;
+            Arc::new(move |p: i32| cb((done * 100 + p) / total)) as ProgressCallback
+        });
+
+        // Onceki parcanin sonunu prompt'a ekle — baglam parcalar arasi korunur.
+        // Ayni state kullanildigi icin whisper.cpp'nin kendi prompt_past'i da tasinir.
+        let prompt = if text.is_empty() {
+            base_prompt.to_string()
+        } else {
+            format!("{} {}", base_prompt, tail_chars(&text, CONTEXT_CARRY_CHARS))
+        };
+
+        let chunk_offset_ms = offset_ms + (chunk_start / 16) as u64;
+        let (chunk_text, mut chunk_segments) = run_whisper(
+            ctx,
+            state,
+            &audio_data[chunk_start..chunk_end],
+            &chunk_options,
+            &prompt,
+            chunk_offset_ms,
+        )?;
+
+        // Onceki parcanin zaten yazdigi sureye dusen kelimeleri at. Metin
+        // karsilastirmasi yapilmaz: "evet evet" gibi gercek tekrarlar korunur.
+        let overlap = words_before(&chunk_segments, covered_ms);
+        drop_leading_words(&mut chunk_segments, overlap);
+        let new_words: Vec<&str> = chunk_text.split_whitespace().skip(overlap).collect();
+
+        log::info!(
+            "Parca {}/{}: {:.1}s-{:.1}s, {} kelime ({} tekrar atildi)",
+            idx + 1,
+            ranges.len(),
+            chunk_start as f64 / 16000.0,
+            chunk_end as f64 / 16000.0,
+            new_words.len(),
+            overlap
+        );
+
+        if !new_words.is_empty() {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(&new_words.join(" "));
+        }
+        segments.extend(chunk_segments);
+        if let Some(end) = segments.last().map(|s| s.words.last().map_or(s.end_ms, |w| w.end_ms)) {
+            covered_ms = covered_ms.max(end);
+        }
+    }
+
+    Ok((text, segments))
+}
+
+/// Sesi ~`chunk` uzunlugunda, `overlap` kadar ortusen parcalara bol.
+/// Kesim noktasi, hedef bitisten onceki `search` araliginda en sessiz 100ms penceresidir.
+fn split_at_silence(audio: &[f32], chunk: usize, overlap: usize, search: usize) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let len = audio.len();
+    let mut start = 0usize;
+
+    while start < len {
+        let target_end = start + chunk;
+        // Kalan kisim cok kisaysa son parcaya ekle
+        if target_end + MIN_AUDIO_SAMPLES >= len {
+            ranges.push((start, len));
+            break;
+        }
+
+        let search_from = target_end.saturating_sub(search).max(start + overlap * 2);
+        let end = quietest_point(audio, search_from, target_end);
+        ranges.push((start, end));
+        start = end - overlap;
+    }
+
+    ranges
+}
+
+/// [from, to) araliginda en dusuk RMS'li 100ms pencerenin ortasini dondur
+fn quietest_point(audio: &[f32], from: usize, to: usize) -> usize {
+    let window = 1600; // 100ms @ 16kHz
+    let hop = 160; // 10ms
+    let mut best = to;
+    let mut best_rms = f32::MAX;
+
+    let mut pos = from;
+    while pos + window <= to {
+        let rms = calculate_rms(&audio[pos..pos + window]);
+        if rms < best_rms {
+            best_rms = rms;
+            best = pos + window / 2;
+        }
+        pos += hop;
+    }
+
+    best
+}
+
+/// Ortasi `covered_ms`'den once kalan bastaki kelime sayisi (ortusme tekrari).
+/// Kelimesi olmayan segment tamamen kapsanmissa tum kelimeleri sayilir.
+fn words_before(segments: &[TranscriptionSegment], covered_ms: u64) -> usize {
+    let mut count = 0;
+    for seg in segments {
+        if seg.words.is_empty() {
+            if seg.end_ms > covered_ms {
+                return count;
+            }
+            count += seg.text.split_whitespace().count();
+            continue;
+        }
+        for word in &seg.words {
+            if (word.start_ms + word.end_ms) / 2 >= covered_ms {
+                return count;
+            }
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Segment listesinin basindan `count` kelimeyi dus (ortusme tekrari)
+fn drop_leading_words(segments: &mut Vec<TranscriptionSegment>, count: usize) {
+    let mut remaining = count;
+    while remaining > 0 && !segments.is_empty() {
+        let n = segments[0].text.split_whitespace().count();
+        if n <= remaining {
+            segments.remove(0);
+            remaining -= n;
+            continue;
+        }
+
+        let seg = &mut segments[0];
+        seg.text = seg.text.split_whitespace().skip(remaining).collect::<Vec<_>>().join(" ");
+        if seg.words.len() >= remaining {
+            seg.words.drain(..remaining);
+        }
+        if let Some(first) = seg.words.first() {
+            seg.start_ms = first.start_ms;
+        }
+        remaining = 0;
+    }
+}
+
+/// Metnin son `max_chars` karakterini kelime sinirindan al
+fn tail_chars(text: &str, max_chars: usize) -> &str {
+    let total = text.chars().count();
+    if total <= max_chars {
+        return text;
+    }
+    let byte_idx = text
+        .char_indices()
+        .nth(total - max_chars)
+        .map(|(i, _)| i)
+        .unwrap_or(0);
+    let tail = &text[byte_idx..];
+    match tail.find(' ') {
+        Some(sp) => tail[sp + 1..].trim_start(),
+        None => tail,
+    }
+}
+
+impl TranscriptionResult {
+    fn empty(language: &str) -> Self {
+        Self {
+            text: String::new(),
+            duration_ms: 0,
+            segments: Vec::new(),
+            language: language.to_string(),
+            language_probability: None,
+        }
+    }
+}
+
+fn centis_to_ms(t: i64) -> u64 {
+    (t.max(0) as u64) * 10
+}
+
+/// Whisper alt-kelime token'larini kelimelere birlestir.
+/// Bosluk ile baslayan token yeni kelime baslatir; zamanlar centisecond cinsinden.
+fn group_tokens_into_words(tokens: &[TokenPiece], offset_ms: u64) -> Vec<TranscriptionWord> {
+    let mut words: Vec<TranscriptionWord> = Vec::new();
+    // Her kelimenin token olasilik toplami ve token sayisi
+    let mut prob_sums: Vec<(f32, u32)> = Vec::new();
+
+    for token in tokens {
+        if token.text.is_empty() {
+            continue;
+        }
+        let starts_word = token.text.starts_with(' ') || words.is_empty();
+        let trimmed = token.text.trim();
+
+        if starts_word {
+            if trimmed.is_empty() {
+                continue;
+            }
+            words.push(TranscriptionWord {
+                text: trimmed.to_string(),
+                start_ms: centis_to_ms(token.t0) + offset_ms,
+                end_ms: centis_to_ms(token.t1) + offset_ms,
+                confidence: 0.0,
+            });
+            prob_sums.push((token.p, 1));
+        } else if let Some(last) = words.last_mut() {
+            last.text.push_str(trimmed);
+            last.end_ms = last.end_ms.max(centis_to_ms(token.t1) + offset_ms);
+            if let Some(sum) = prob_sums.last_mut() {
+                sum.0 += token.p;
+                sum.1 += 1;
+            }
+        }
+    }
+
+    for (word, (sum, count)) in words.iter_mut().zip(prob_sums) {
+        word.confidence = sum / count as f32;
+    }
+
+    words
+}
+
+/// Token olasiliklarinin ortalamasi (token yoksa 0)
+fn mean_probability(tokens: &[TokenPiece]) -> f32 {
+    if tokens.is_empty() {
+        return 0.0;
+    }
+    tokens.iter().map(|t| t.p).sum::<f32>() / tokens.len() as f32
+}
+
+/// Sessizlige uydurulmus metin: Whisper konusma olmadigini dusunuyor ve token'lardan emin degil
+fn is_hallucinated(segment: &TranscriptionSegment) -> bool {
+    segment.no_speech_prob >= HALLUCINATION_NO_SPEECH_PROB
+        && segment.confidence < HALLUCINATION_MAX_CONFIDENCE
 }
 
 /// High-pass filtre (basit birinci derece IIR)
@@ -225,10 +903,10 @@ fn calculate_rms(audio: &[f32]) -> f32 {
     (sum_sq / audio.len() as f64).sqrt() as f32
 }
 
-/// Bas ve son sessizligi kirp
-fn trim_silence(audio: &[f32], threshold: f32) -> Vec<f32> {
+/// Bas ve son sessizligin sinirlarini bul — (baslangic, bitis) sample indeksi
+fn silence_bounds(audio: &[f32], threshold: f32) -> (usize, usize) {
     if audio.is_empty() {
-        return Vec::new();
+        return (0, 0);
     }
 
     let window = 160; // 10ms @ 16kHz
@@ -268,10 +946,10 @@ fn trim_silence(audio: &[f32], threshold: f32) -> Vec<f32> {
     }
 
     if start_idx >= end_idx {
-        return Vec::new();
+        return (0, 0);
     }
 
-    audio[start_idx..end_idx].to_vec()
+    (start_idx, end_idx)
 }
 
 /// Ses verisini normalize et - peak normalizasyon
@@ -292,6 +970,37 @@ fn normalize_audio(audio: &[f32]) -> Vec<f32> {
     audio.iter().map(|s| (s * gain).clamp(-1.0, 1.0)).collect()
 }
 
+/// Ceviri modunun initial prompt'u — cikti dilini Ingilizcede tutar
+const TRANSLATE_PROMPT: &str = "Hello, how are you today? I'm doing well, thank you.";
+
+/// Dil tespiti icin kullanilan pencere (Whisper encoder girdisi: 30 saniye)
+const LANG_DETECT_SAMPLES: usize = 16000 * 30;
+
+/// Whisper dil tespiti — en olasi dil kodu ve olasiligi
+fn detect_language(state: &mut WhisperState, audio: &[f32], threads: usize) -> Result<(String, f32), String> {
+    let window = &audio[..audio.len().min(LANG_DETECT_SAMPLES)];
+    state
+        .pcm_to_mel(window, threads)
+        .map_err(|e| format!("Mel spektrum hesaplanamadi: {}", e))?;
+    let (lang_id, probs) = state
+        .lang_detect(0, threads)
+        .map_err(|e| format!("Dil tespiti basarisiz: {}", e))?;
+
+    let lang = whisper_rs::get_lang_str(lang_id)
+        .ok_or_else(|| format!("Bilinmeyen dil kimligi: {}", lang_id))?;
+    let probability = probs.get(lang_id as usize).copied().unwrap_or(0.0);
+    Ok((lang.to_string(), probability))
+}
+
+/// Profildeki thread sayisi (0 = tum cekirdekler, cekirdek sayisini asmaz)
+fn decoding_threads(decoding: &DecodingProfile) -> i32 {
+    if decoding.threads > 0 {
+        decoding.threads.min(num_cpus())
+    } else {
+        num_cpus()
+    }
+}
+
 fn num_cpus() -> i32 {
     std::thread::available_parallelism()
         .map(|n| n.get() as i32)
@@ -299,3 +1008,160 @@ fn num_cpus() -> i32 {
         .min(8)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn piece(text: &str, t0: i64, t1: i64) -> TokenPiece {
+        TokenPiece { text: text.to_string(), t0, t1, p: 0.9 }
+    }
+
+    #[test]
+    fn test_group_tokens_into_words() {
+        let tokens = vec![
+            piece(" Mer", 0, 20),
+            piece("ha", 20, 35),
+            piece("ba", 35, 50),
+            piece(" dünya", 60, 90),
+            piece(".", 90, 95),
+        ];
+        let words = group_tokens_into_words(&tokens, 0);
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].text, "Merhaba");
+        assert_eq!(words[0].start_ms, 0);
+        assert_eq!(words[0].end_ms, 500);
+        assert_eq!(words[1].text, "dünya.");
+        assert_eq!(words[1].start_ms, 600);
+        assert_eq!(words[1].end_ms, 950);
+    }
+
+    #[test]
+    fn test_group_tokens_applies_offset() {
+        let tokens = vec![piece(" evet", 10, 30)];
+        let words = group_tokens_into_words(&tokens, 1000);
+        assert_eq!(words[0].start_ms, 1100);
+        assert_eq!(words[0].end_ms, 1300);
+    }
+
+    #[test]
+    fn test_word_confidence_is_token_mean() {
+        let tokens = vec![
+            TokenPiece { text: " İstan".to_string(), t0: 0, t1: 20, p: 0.9 },
+            TokenPiece { text: "bul".to_string(), t0: 20, t1: 40, p: 0.5 },
+            TokenPiece { text: " deploy".to_string(), t0: 40, t1: 70, p: 0.2 },
+        ];
+        let words = group_tokens_into_words(&tokens, 0);
+        assert!((words[0].confidence - 0.7).abs() < 1e-6);
+        assert!((words[1].confidence - 0.2).abs() < 1e-6);
+        assert!((mean_probability(&tokens) - 1.6 / 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_is_hallucinated() {
+        let segment = |no_speech_prob, confidence| TranscriptionSegment {
+            text: "Altyazı M.K.".to_string(),
+            no_speech_prob,
+            confidence,
+            ..Default::default()
+        };
+        assert!(is_hallucinated(&segment(0.8, 0.2)));
+        // Konusma yok olasiligi yuksek ama metinden eminse tut
+        assert!(!is_hallucinated(&segment(0.8, 0.9)));
+        assert!(!is_hallucinated(&segment(0.1, 0.2)));
+    }
+
+    #[test]
+    fn test_words_before_uses_timestamps() {
+        let word = |text: &str, start_ms, end_ms| TranscriptionWord {
+            text: text.to_string(),
+            start_ms,
+            end_ms,
+            confidence: 0.9,
+        };
+        // Onceki parca 5000ms'ye kadar yazdi; ortusmedeki "çok güzel" tekrar,
+        // sonrasinda gercekten tekrar soylenen "çok güzel" korunur
+        let segments = vec![TranscriptionSegment {
+            text: "çok güzel çok güzel".to_string(),
+            start_ms: 4200,
+            end_ms: 5900,
+            words: vec![
+                word("çok", 4200, 4500),
+                word("güzel", 4500, 4900),
+                word("çok", 5200, 5500),
+                word("güzel", 5500, 5900),
+            ],
+            ..Default::default()
+        }];
+        assert_eq!(words_before(&segments, 5000), 2);
+        assert_eq!(words_before(&segments, 0), 0);
+
+        let untimed = vec![TranscriptionSegment { text: "evet evet".to_string(), start_ms: 4000, end_ms: 4800, ..Default::default() }];
+        assert_eq!(words_before(&untimed, 5000), 2);
+        assert_eq!(words_before(&untimed, 4500), 0);
+    }
+
+    #[test]
+    fn test_drop_leading_words() {
+        let mut segments = vec![
+            TranscriptionSegment { text: "çok güzel".to_string(), start_ms: 0, end_ms: 800, ..Default::default() },
+            TranscriptionSegment { text: "yürüyüşe çıktık".to_string(), start_ms: 800, end_ms: 2000, ..Default::default() },
+        ];
+        drop_leading_words(&mut segments, 3);
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].text, "çıktık");
+    }
+
+    #[test]
+    fn test_split_at_silence_covers_audio() {
+        // 100 saniyelik ses, 27-28. saniyeler arasi sessizlik
+        let mut audio = vec![0.3f32; 16000 * 100];
+        for s in &mut audio[16000 * 27..16000 * 28] {
+            *s = 0.0;
+        }
+        let ranges = split_at_silence(&audio, 16000 * 30, 16000, 16000 * 5);
+        assert_eq!(ranges.first().unwrap().0, 0);
+        assert_eq!(ranges.last().unwrap().1, audio.len());
+        // Ilk kesim sessizlik bolgesinde olmali
+        let first_end = ranges[0].1;
+        assert!(first_end >= 16000 * 27 && first_end <= 16000 * 28, "kesim: {}", first_end);
+        // Parcalar 1 saniye ortusmeli
+        for pair in ranges.windows(2) {
+            assert_eq!(pair[1].0, pair[0].1 - 16000);
+        }
+    }
+
+    #[test]
+    fn test_tail_chars_word_boundary() {
+        assert_eq!(tail_chars("kısa", 10), "kısa");
+        assert_eq!(tail_chars("bir iki üç dört", 8), "üç dört");
+    }
+
+    #[test]
+    fn test_silence_bounds_all_silent() {
+        let audio = vec![0.0f32; 16000];
+        assert_eq!(silence_bounds(&audio, 0.005), (0, 0));
+    }
+
+    #[test]
+    fn test_model_cache_lru_eviction() {
+        let mut cache: ModelCache<u32> = ModelCache::new(2);
+        cache.insert("small", Arc::new(1));
+        cache.insert("large", Arc::new(2));
+        // small'a erisim onu en son kullanilan yapar; large cikarilir
+        assert_eq!(cache.get("small").as_deref(), Some(&1));
+        cache.insert("medium", Arc::new(3));
+        assert_eq!(cache.paths(), vec!["small".to_string(), "medium".to_string()]);
+        assert!(cache.get("large").is_none());
+    }
+
+    #[test]
+    fn test_model_cache_idle_eviction() {
+        let mut cache: ModelCache<u32> = ModelCache::new(2);
+        cache.insert("small", Arc::new(1));
+        let now = Instant::now();
+        assert!(cache.evict_idle(now, Duration::from_secs(60)).is_empty());
+        let later = now + Duration::from_secs(120);
+        assert_eq!(cache.evict_idle(later, Duration::from_secs(60)), vec!["small".to_string()]);
+        assert!(cache.paths().is_empty());
+    }
+}
@@ -0,0 +1,348 @@
+//! Metin cevirisi — degistirilebilir motorlar (Google, DeepL).
+//!
+//! Her motor `TranslationEngine` trait'ini uygular. Kaynak dil "auto" ya da
+//! bos ise tespit motora birakilir ve tespit edilen dil yanitta doner.
+//! Motor adresleri ortam degiskeniyle degistirilebilir (yerel mock sunucu):
+//! `FISILTI_GOOGLE_TRANSLATE_URL`, `FISILTI_DEEPL_URL`.
+
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::time::Duration;
+
+const GOOGLE_BASE_URL: &str = "https://translate.googleapis.com";
+const DEEPL_BASE_URL: &str = "https://api.deepl.com";
+const DEEPL_FREE_BASE_URL: &str = "https://api-free.deepl.com";
+
+const GOOGLE_URL_ENV: &str = "FISILTI_GOOGLE_TRANSLATE_URL";
+const DEEPL_URL_ENV: &str = "FISILTI_DEEPL_URL";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+pub const ENGINE_GOOGLE: &str = "google";
+pub const ENGINE_DEEPL: &str = "deepl";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TranslateResponse {
+    pub translated_text: String,
+    /// Kaynak dil otomatik tespit edildiyse motorun buldugu dil (kucuk harf, "tr")
+    pub detected_source_lang: Option<String>,
+    pub engine: String,
+}
+
+/// Ceviri motoru — `translate` tek bir HTTP istegiyle metni cevirir
+pub trait TranslationEngine {
+    fn name(&self) -> &'static str;
+
+    fn translate(
+        &self,
+        client: &reqwest::Client,
+        text: &str,
+        source: &str,
+        target: &str,
+    ) -> impl Future<Output = Result<TranslateResponse, String>> + Send;
+}
+
+/// "auto", "" ve "detect" kaynak dil tespiti anlamina gelir
+fn is_auto(lang: &str) -> bool {
+    matches!(lang.trim().to_lowercase().as_str(), "" | "auto" | "detect")
+}
+
+/// "TR", "en-US", "pt_BR" → "tr", "en", "pt"
+fn normalize_lang(lang: &str) -> String {
+    lang.trim()
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+// ─── Google ───
+
+/// Google Translate (anahtarsiz gtx istemcisi)
+pub struct GoogleEngine {
+    base_url: String,
+}
+
+impl GoogleEngine {
+    pub fn new() -> Self {
+        Self::with_base_url(&std::env::var(GOOGLE_URL_ENV).unwrap_or_else(|_| GOOGLE_BASE_URL.to_string()))
+    }
+
+    pub fn with_base_url(base_url: &str) -> Self {
+        Self { base_url: base_url.trim_end_matches('/').to_string() }
+    }
+}
+
+impl Default for GoogleEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// gtx yaniti: `[[["ceviri","kaynak",...],...], null, "tr", ...]`
+fn parse_google_response(body: &str) -> Result<(String, Option<String>), String> {
+    let json: serde_json::Value =
+        serde_json::from_str(body).map_err(|e| format!("Google yaniti cozulemedi: {}", e))?;
+
+    let sentences = json
+        .get(0)
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| "Google yaniti beklenmeyen formatta".to_string())?;
+    let translated: String = sentences
+        .iter()
+        .filter_map(|s| s.get(0).and_then(|t| t.as_str()))
+        .collect();
+
+    let detected = json.get(2).and_then(|v| v.as_str()).map(normalize_lang);
+    Ok((translated, detected))
+}
+
+impl TranslationEngine for GoogleEngine {
+    fn name(&self) -> &'static str {
+        ENGINE_GOOGLE
+    }
+
+    async fn translate(
+        &self,
+        client: &reqwest::Client,
+        text: &str,
+        source: &str,
+        target: &str,
+    ) -> Result<TranslateResponse, String> {
+        let auto = is_auto(source);
+        let sl = if auto { "auto".to_string() } else { normalize_lang(source) };
+        let tl = normalize_lang(target);
+
+        // Metin govdede gider — uzun secimlerde URL siniri asilmaz
+        let resp = client
+            .post(format!("{}/translate_a/single", self.base_url))
+            .query(&[("client", "gtx"), ("sl", sl.as_str()), ("tl", tl.as_str()), ("dt", "t")])
+            .form(&[("q", text)])
+            .send()
+            .await
+            .map_err(|e| format!("Google ceviri istegi basarisiz: {}", e))?;
+
+        if !resp.status().is_success() {
+            return Err(format!("Google ceviri hatasi: HTTP {}", resp.status()));
+        }
+        let body = resp
+            .text()
+            .await
+            .map_err(|e| format!("Yanit okunamadi: {}", e))?;
+
+        let (translated_text, detected) = parse_google_response(&body)?;
+        Ok(TranslateResponse {
+            translated_text,
+            detected_source_lang: if auto { detected } else { None },
+            engine: self.name().to_string(),
+        })
+    }
+}
+
+// ─── DeepL ───
+
+pub struct DeeplEngine {
+    base_url: String,
+    api_key: String,
+}
+
+impl DeeplEngine {
+    /// Ucretsiz anahtarlar (":fx" ile biten) api-free adresini kullanir
+    pub fn new(api_key: &str) -> Self {
+        let default_url = if api_key.trim().ends_with(":fx") { DEEPL_FREE_BASE_URL } else { DEEPL_BASE_URL };
+        let base_url = std::env::var(DEEPL_URL_ENV).unwrap_or_else(|_| default_url.to_string());
+        Self::with_base_url(&base_url, api_key)
+    }
+
+    pub fn with_base_url(base_url: &str, api_key: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.trim().to_string(),
+        }
+    }
+}
+
+/// DeepL hedef dil kodu — Ingilizce ve Portekizce icin bolge zorunlu
+fn deepl_target_lang(target: &str) -> String {
+    match normalize_lang(target).as_str() {
+        "en" => "EN-US".to_string(),
+        "pt" => "PT-PT".to_string(),
+        other => other.to_uppercase(),
+    }
+}
+
+#[derive(Deserialize)]
+struct DeeplTranslation {
+    #[serde(default)]
+    detected_source_language: Option<String>,
+    text: String,
+}
+
+#[derive(Deserialize)]
+struct DeeplResponse {
+    translations: Vec<DeeplTranslation>,
+}
+
+fn parse_deepl_response(body: &str) -> Result<(String, Option<String>), String> {
+    let resp: DeeplResponse =
+        serde_json::from_str(body).map_err(|e| format!("DeepL yaniti cozulemedi: {}", e))?;
+    let first = resp
+        .translations
+        .into_iter()
+        .next()
+        .ok_or_else(|| "DeepL bos yanit dondu".to_string())?;
+    Ok((first.text, first.detected_source_language.as_deref().map(normalize_lang)))
+}
+
+impl TranslationEngine for DeeplEngine {
+    fn name(&self) -> &'static str {
+        ENGINE_DEEPL
+    }
+
+    async fn translate(
+        &self,
+        client: &reqwest::Client,
+        text: &str,
+        source: &str,
+        target: &str,
+    ) -> Result<TranslateResponse, String> {
+        if self.api_key.is_empty() {
+            return Err("DeepL API anahtari gerekli".to_string());
+        }
+
+        let auto = is_auto(source);
+        let target_lang = deepl_target_lang(target);
+        let source_lang = normalize_lang(source).to_uppercase();
+        let mut form = vec![("text", text), ("target_lang", target_lang.as_str())];
+        if !auto {
+            form.push(("source_lang", source_lang.as_str()));
+        }
+
+        let resp = client
+            .post(format!("{}/v2/translate", self.base_url))
+            .header("Authorization", format!("DeepL-Auth-Key {}", self.api_key))
+            .form(&form)
+            .send()
+            .await
+            .map_err(|e| format!("DeepL istegi basarisiz: {}", e))?;
+
+        match resp.status().as_u16() {
+            200 => {}
+            403 => return Err("DeepL API anahtari gecersiz".to_string()),
+            456 => return Err("DeepL kullanim kotasi doldu".to_string()),
+            code => return Err(format!("DeepL hatasi: HTTP {}", code)),
+        }
+        let body = resp
+            .text()
+            .await
+            .map_err(|e| format!("Yanit okunamadi: {}", e))?;
+
+        let (translated_text, detected) = parse_deepl_response(&body)?;
+        Ok(TranslateResponse {
+            translated_text,
+            detected_source_lang: if auto { detected } else { None },
+            engine: self.name().to_string(),
+        })
+    }
+}
+
+// ─── Ortak Giris Noktasi ───
+
+async fn run_engine<E: TranslationEngine>(
+    engine: &E,
+    text: &str,
+    source: &str,
+    target: &str,
+) -> Result<TranslateResponse, String> {
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| format!("HTTP istemcisi olusturulamadi: {}", e))?;
+    engine.translate(&client, text, source, target).await
+}
+
+/// Metni `engine` ("google" | "deepl") ile cevir.
+/// `source` "auto" ise kaynak dil motor tarafindan tespit edilir.
+pub async fn translate(
+    text: &str,
+    source: &str,
+    target: &str,
+    engine: &str,
+    deepl_api_key: &str,
+) -> Result<TranslateResponse, String> {
+    let engine_name = engine.trim().to_lowercase();
+    if text.trim().is_empty() {
+        return Ok(TranslateResponse {
+            translated_text: String::new(),
+            detected_source_lang: None,
+            engine: engine_name,
+        });
+    }
+    if is_auto(target) {
+        return Err("Hedef dil belirtilmeli".to_string());
+    }
+
+    match engine_name.as_str() {
+        ENGINE_GOOGLE | "" => run_engine(&GoogleEngine::new(), text, source, target).await,
+        ENGINE_DEEPL => run_engine(&DeeplEngine::new(deepl_api_key), text, source, target).await,
+        other => Err(format!("Bilinmeyen ceviri motoru: {}", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::mock_server;
+
+    #[test]
+    fn test_parse_google_response() {
+        let body = r#"[[["Hello ","Merhaba ",null,null,10],["world","dünya",null,null,10]],null,"tr",null]"#;
+        let (text, detected) = parse_google_response(body).unwrap();
+        assert_eq!(text, "Hello world");
+        assert_eq!(detected.as_deref(), Some("tr"));
+    }
+
+    #[test]
+    fn test_lang_codes() {
+        assert_eq!(normalize_lang("EN-US"), "en");
+        assert_eq!(deepl_target_lang("en"), "EN-US");
+        assert_eq!(deepl_target_lang("tr"), "TR");
+        assert!(is_auto("Auto") && is_auto(""));
+    }
+
+    #[tokio::test]
+    async fn test_google_engine_against_mock() {
+        let (url, request) = mock_server(200, r#"[[["Good morning","Günaydın",null,null,10]],null,"tr"]"#).await;
+        let resp = run_engine(&GoogleEngine::with_base_url(&url), "Günaydın", "auto", "en").await.unwrap();
+        assert_eq!(resp.translated_text, "Good morning");
+        assert_eq!(resp.detected_source_lang.as_deref(), Some("tr"));
+        assert_eq!(resp.engine, ENGINE_GOOGLE);
+
+        let request = request.await.unwrap();
+        assert!(request.starts_with("POST /translate_a/single?"), "{}", request);
+        assert!(request.contains("sl=auto") && request.contains("tl=en"));
+    }
+
+    #[tokio::test]
+    async fn test_deepl_engine_against_mock() {
+        let (url, request) =
+            mock_server(200, r#"{"translations":[{"detected_source_language":"TR","text":"Thank you"}]}"#).await;
+        let engine = DeeplEngine::with_base_url(&url, "anahtar:fx");
+        let resp = run_engine(&engine, "Teşekkürler", "tr", "en").await.unwrap();
+        assert_eq!(resp.translated_text, "Thank you");
+        // Kaynak dil verildiginde tespit sonucu dondurulmez
+        assert_eq!(resp.detected_source_lang, None);
+
+        let request = request.await.unwrap();
+        assert!(request.contains("DeepL-Auth-Key anahtar:fx"));
+        assert!(request.contains("target_lang=EN-US") && request.contains("source_lang=TR"));
+    }
+
+    #[tokio::test]
+    async fn test_deepl_quota_error() {
+        let (url, _request) = mock_server(456, "{}").await;
+        let engine = DeeplEngine::with_base_url(&url, "anahtar");
+        let err = run_engine(&engine, "metin", "auto", "en").await.unwrap_err();
+        assert!(err.contains("kota"), "{}", err);
+    }
+}
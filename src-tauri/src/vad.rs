@@ -0,0 +1,354 @@
+//! Yerel ses aktivitesi algilama (VAD).
+//!
+//! Her 30ms'lik cerceve icin bir konusma olasiligi hesaplanir: cercevenin
+//! enerjisi uyarlanabilir gurultu tabanina gore SNR'a cevrilir ve lojistik
+//! egriyle 0..1 araligina oturtulur. Konusma baslangici `threshold` ile,
+//! bitisi daha dusuk bir esik ve hangover suresiyle belirlenir (histerezis),
+//! boylece Turkce uzun hecelerde ve kisa duraklamalarda kayit bolunmez.
+//!
+//! Kaydedici, canli ceviri ve toplanti modu ayni `Vad` yapisini kullanir.
+
+use crate::settings::AppSettings;
+use serde::Serialize;
+
+/// Cerceve uzunlugu (ms)
+pub const FRAME_MS: u32 = 30;
+
+/// Konusma bitisi esigi, baslangic esiginin bu kadar altinda
+const HYSTERESIS: f32 = 0.15;
+/// Dijital sessizlik — bu seviyenin altindaki cerceveler her zaman sessiz
+const ABSOLUTE_SILENCE_DB: f32 = -65.0;
+/// Gurultu tabani bunun altina inmez (sifir dolu tampondan sonra asiri SNR olusmasin)
+const MIN_NOISE_FLOOR_DB: f32 = -75.0;
+/// Olasiligin 0.5 oldugu SNR (dB) ve egrinin yumusakligi
+const SNR_MIDPOINT_DB: f32 = 9.0;
+const SNR_SLOPE_DB: f32 = 3.0;
+/// Gurultu tabani yukselme hizlari — sessizlikte hizli, konusmada cok yavas
+const FLOOR_RISE_SILENCE: f32 = 0.05;
+const FLOOR_RISE_SPEECH: f32 = 0.005;
+/// Yuksek sifir-gecis orani + dusuk SNR genelde fan/ugultu gurultusudur
+const NOISY_ZCR: f32 = 0.4;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VadConfig {
+    /// Konusma baslangic olasiligi (0..1) — `AppSettings::vad_threshold`
+    pub threshold: f32,
+    /// Konusmanin baslamis sayilmasi icin gereken kesintisiz sure
+    pub min_speech_ms: u32,
+    /// Esigin altina dustukten sonra konusmanin bitmis sayilmasi icin bekleme
+    pub hangover_ms: u32,
+    /// Konusmadan sonra bu kadar sessizlik olursa `SilenceTimeout` (0 = kapali)
+    pub silence_timeout_ms: u32,
+}
+
+impl Default for VadConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 0.3,
+            min_speech_ms: 90,
+            hangover_ms: 450,
+            silence_timeout_ms: 4000,
+        }
+    }
+}
+
+impl VadConfig {
+    pub fn from_settings(settings: &AppSettings) -> Self {
+        Self {
+            threshold: settings.vad_threshold.clamp(0.05, 0.95),
+            silence_timeout_ms: (settings.silence_timeout.max(0.0) * 1000.0) as u32,
+            ..Self::default()
+        }
+    }
+
+    fn release_threshold(&self) -> f32 {
+        (self.threshold - HYSTERESIS).max(0.05)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VadEvent {
+    SpeechStart,
+    SpeechEnd,
+    /// Konusmadan sonra `silence_timeout_ms` kadar sessizlik — kayit durdurulabilir
+    SilenceTimeout,
+}
+
+/// Anlik VAD durumu — frontend'e raporlanir
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct VadStatus {
+    pub is_speech: bool,
+    pub probability: f32,
+    pub heard_speech: bool,
+    pub silence_ms: u32,
+}
+
+pub struct Vad {
+    config: VadConfig,
+    frame_len: usize,
+    pending: Vec<f32>,
+    noise_floor_db: Option<f32>,
+    probability: f32,
+    in_speech: bool,
+    heard_speech: bool,
+    /// Esigi asan ardisik cerceve sayisi (konusma baslamadan once)
+    onset_frames: u32,
+    /// Konusma sirasinda bitis esiginin altindaki ardisik cerceveler
+    release_frames: u32,
+    /// Son konusma cercevesinden beri gecen cerceveler
+    silence_frames: u32,
+    timeout_fired: bool,
+}
+
+impl Vad {
+    pub fn new(config: VadConfig, sample_rate: u32) -> Self {
+        let frame_len = (sample_rate as usize * FRAME_MS as usize / 1000).max(1);
+        Self {
+            config,
+            frame_len,
+            pending: Vec::with_capacity(frame_len),
+            noise_floor_db: None,
+            probability: 0.0,
+            in_speech: false,
+            heard_speech: false,
+            onset_frames: 0,
+            release_frames: 0,
+            silence_frames: 0,
+            timeout_fired: false,
+        }
+    }
+
+    /// Ornekleri cercevelere bolup isle; olusan olaylari sirayla dondur
+    pub fn process(&mut self, samples: &[f32]) -> Vec<VadEvent> {
+        let mut events = Vec::new();
+        let mut rest = samples;
+
+        while !rest.is_empty() {
+            let take = (self.frame_len - self.pending.len()).min(rest.len());
+            self.pending.extend_from_slice(&rest[..take]);
+            rest = &rest[take..];
+
+            if self.pending.len() == self.frame_len {
+                let frame = std::mem::take(&mut self.pending);
+                events.extend(self.process_frame(&frame));
+                self.pending = frame;
+                self.pending.clear();
+            }
+        }
+
+        events
+    }
+
+    fn process_frame(&mut self, frame: &[f32]) -> Option<VadEvent> {
+        let probability = self.frame_probability(frame);
+        self.probability = probability;
+
+        let frames_for = |ms: u32| ms.div_ceil(FRAME_MS).max(1);
+
+        if self.in_speech {
+            if probability < self.config.release_threshold() {
+                self.release_frames += 1;
+                self.silence_frames += 1;
+                if self.release_frames >= frames_for(self.config.hangover_ms) {
+                    self.in_speech = false;
+                    self.release_frames = 0;
+                    return Some(VadEvent::SpeechEnd);
+                }
+            } else {
+                self.release_frames = 0;
+                self.silence_frames = 0;
+            }
+            return None;
+        }
+
+        if probability >= self.config.threshold {
+            self.onset_frames += 1;
+            if self.onset_frames >= frames_for(self.config.min_speech_ms) {
+                self.in_speech = true;
+                self.heard_speech = true;
+                self.onset_frames = 0;
+                self.silence_frames = 0;
+                self.timeout_fired = false;
+                return Some(VadEvent::SpeechStart);
+            }
+        } else {
+            self.onset_frames = 0;
+        }
+
+        self.silence_frames += 1;
+        if self.heard_speech
+            && !self.timeout_fired
+            && self.config.silence_timeout_ms > 0
+            && self.silence_frames * FRAME_MS >= self.config.silence_timeout_ms
+        {
+            self.timeout_fired = true;
+            return Some(VadEvent::SilenceTimeout);
+        }
+        None
+    }
+
+    /// Cercevenin konusma olasiligi; gurultu tabanini da gunceller
+    fn frame_probability(&mut self, frame: &[f32]) -> f32 {
+        let rms = (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt();
+        let db = 20.0 * rms.max(1e-10).log10();
+
+        let floor = *self.noise_floor_db.get_or_insert(db.max(MIN_NOISE_FLOOR_DB));
+        let snr = db - floor;
+
+        let mut probability = if db < ABSOLUTE_SILENCE_DB {
+            0.0
+        } else {
+            1.0 / (1.0 + (-(snr - SNR_MIDPOINT_DB) / SNR_SLOPE_DB).exp())
+        };
+        if zero_crossing_rate(frame) > NOISY_ZCR && snr < SNR_MIDPOINT_DB * 2.0 {
+            probability *= 0.5;
+        }
+
+        // Tabana hizli in, yavas cik — surekli gurultu zamanla konusma sayilmaz
+        let rise = if probability >= self.config.threshold { FLOOR_RISE_SPEECH } else { FLOOR_RISE_SILENCE };
+        let next = if db < floor { db } else { floor + (db - floor) * rise };
+        self.noise_floor_db = Some(next.max(MIN_NOISE_FLOOR_DB));
+
+        probability
+    }
+
+    pub fn is_speech(&self) -> bool {
+        self.in_speech
+    }
+
+    pub fn status(&self) -> VadStatus {
+        VadStatus {
+            is_speech: self.in_speech,
+            probability: self.probability,
+            heard_speech: self.heard_speech,
+            silence_ms: if self.in_speech { 0 } else { self.silence_frames * FRAME_MS },
+        }
+    }
+
+    /// Yeni kayit/segment icin durumu sifirla (gurultu tabani korunur)
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.probability = 0.0;
+        self.in_speech = false;
+        self.heard_speech = false;
+        self.onset_frames = 0;
+        self.release_frames = 0;
+        self.silence_frames = 0;
+        self.timeout_fired = false;
+    }
+}
+
+fn zero_crossing_rate(frame: &[f32]) -> f32 {
+    if frame.len() < 2 {
+        return 0.0;
+    }
+    let crossings = frame
+        .windows(2)
+        .filter(|w| (w[0] >= 0.0) != (w[1] >= 0.0))
+        .count();
+    crossings as f32 / (frame.len() - 1) as f32
+}
+
+/// Tum ses icin konusma bolgeleri (ornek indeksleri, [baslangic, bitis)).
+/// Hangover suresi bitise dahildir; acik kalan bolge sesin sonunda kapatilir.
+pub fn speech_regions(audio: &[f32], sample_rate: u32, config: &VadConfig) -> Vec<(usize, usize)> {
+    let config = VadConfig { silence_timeout_ms: 0, ..config.clone() };
+    let mut vad = Vad::new(config, sample_rate);
+    let frame_len = vad.frame_len;
+    let onset_len = vad.config.min_speech_ms.div_ceil(FRAME_MS).max(1) as usize * frame_len;
+
+    let mut regions = Vec::new();
+    let mut start = None;
+    for (i, frame) in audio.chunks(frame_len).enumerate() {
+        for event in vad.process(frame) {
+            let frame_end = i * frame_len + frame.len();
+            match event {
+                VadEvent::SpeechStart => start = Some(frame_end.saturating_sub(onset_len)),
+                VadEvent::SpeechEnd => {
+                    if let Some(s) = start.take() {
+                        regions.push((s, frame_end));
+                    }
+                }
+                VadEvent::SilenceTimeout => {}
+            }
+        }
+    }
+    if let Some(s) = start {
+        regions.push((s, audio.len()));
+    }
+    regions
+}
+
+/// Seste konusma var mi? Bos kayitlari Whisper'a gondermemek icin.
+pub fn contains_speech(audio: &[f32], sample_rate: u32, config: &VadConfig) -> bool {
+    !speech_regions(audio, sample_rate, config).is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{silence, tone, RATE};
+
+    #[test]
+    fn test_silence_has_no_speech() {
+        let mut vad = Vad::new(VadConfig::default(), RATE);
+        assert!(vad.process(&silence(2000)).is_empty());
+        assert!(!vad.is_speech());
+        assert!(vad.status().probability < 0.3);
+    }
+
+    #[test]
+    fn test_speech_start_and_end_with_hangover() {
+        let mut vad = Vad::new(VadConfig::default(), RATE);
+        vad.process(&silence(500));
+        assert_eq!(vad.process(&tone(600)), vec![VadEvent::SpeechStart]);
+
+        // Hangover'dan kisa bir duraklama konusmayi bolmez
+        assert!(vad.process(&silence(200)).is_empty());
+        assert!(vad.is_speech());
+        vad.process(&tone(300));
+
+        assert_eq!(vad.process(&silence(600)), vec![VadEvent::SpeechEnd]);
+        assert!(!vad.is_speech());
+    }
+
+    #[test]
+    fn test_silence_timeout_fires_once_after_speech() {
+        let config = VadConfig { silence_timeout_ms: 1000, ..VadConfig::default() };
+        let mut vad = Vad::new(config, RATE);
+        // Konusmadan once sessizlik zaman asimi tetiklemez
+        assert!(vad.process(&silence(1500)).is_empty());
+
+        vad.process(&tone(500));
+        let events = vad.process(&silence(3000));
+        assert_eq!(events, vec![VadEvent::SpeechEnd, VadEvent::SilenceTimeout]);
+        assert!(vad.status().silence_ms >= 1000);
+    }
+
+    #[test]
+    fn test_constant_noise_is_absorbed_into_floor() {
+        let mut vad = Vad::new(VadConfig::default(), RATE);
+        vad.process(&silence(300));
+        let hum: Vec<f32> = tone(20_000);
+        vad.process(&hum);
+        // Surekli ayni seviyedeki ses zamanla gurultu tabanina katilir
+        assert!(!vad.is_speech());
+    }
+
+    #[test]
+    fn test_speech_regions() {
+        let mut audio = silence(500);
+        audio.extend(tone(800));
+        audio.extend(silence(1000));
+        audio.extend(tone(400));
+
+        let regions = speech_regions(&audio, RATE, &VadConfig::default());
+        assert_eq!(regions.len(), 2);
+        let (s, e) = regions[0];
+        assert!((s as i64 - 8000).abs() <= 480 * 2, "baslangic: {}", s);
+        assert!(e > 8000 + 12800 && e < 8000 + 12800 + 16000);
+        assert_eq!(regions[1].1, audio.len());
+        assert!(!contains_speech(&silence(1000), RATE, &VadConfig::default()));
+    }
+}
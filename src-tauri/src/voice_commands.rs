@@ -0,0 +1,418 @@
+//! Sesli duzenleme komutlari — transkriptten "yeni satir", "son cumleyi sil",
+//! "hepsini buyuk harf yap", "mesaji gonder" gibi ifadeleri ayiklar.
+//!
+//! Komut ancak kendi cumlesi olarak soylenirse taninir: oncesinde konusma
+//! basi ya da cumle sonu, sonrasinda cumle sonu ya da konusma sonu olmali.
+//! Boylece "new line of products" ya da "yarin gonder" dikte olarak kalir.
+//!
+//! Metnin icinde uygulanabilen komutlar (satir sonu, silme, harf donusumu)
+//! dogrudan `cleaned_text` uzerinde uygulanir. Uygulanamayanlar (ornegin
+//! "mesaji gonder" ya da tek basina "son kelimeyi sil") `applied: false` ile doner;
+//! yapistirma katmani bunlari `commands::input::execute_voice_commands` ile
+//! tus vuruslarina cevirir.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VoiceCommand {
+    NewLine,
+    NewParagraph,
+    DeleteLastWord,
+    DeleteLastSentence,
+    UpperCaseAll,
+    LowerCaseAll,
+    Send,
+    Undo,
+    SelectAll,
+}
+
+impl VoiceCommand {
+    /// Onceki yapistirmayi silen tuslar — yalnizca konusmanin tamami komutsa gonderilir
+    pub(crate) fn is_destructive(self) -> bool {
+        matches!(self, VoiceCommand::DeleteLastWord | VoiceCommand::DeleteLastSentence)
+    }
+}
+
+/// Komutun transkriptte nerede taninacagi
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Placement {
+    /// Kendi cumlesi olarak herhangi bir yerde
+    Anywhere,
+    /// Yalnizca son cumle olarak
+    End,
+    /// Konusmanin tamami komut olmali ("geri al" gunluk dilde de gecer)
+    Alone,
+}
+
+/// Ifadeler ASCII'ye katlanmis ve kucuk harfli yazilir (bkz. `fold_word`).
+/// Gunluk dilde cumle olarak da gecebilen kisa ifadeler ("send", "all caps",
+/// "scratch that", tek basina "gonder") bilerek listede yok.
+const GRAMMAR: &[(VoiceCommand, Placement, &[&str])] = &[
+    (
+        VoiceCommand::NewLine,
+        Placement::Anywhere,
+        &["yeni satir", "alt satir", "alt satira gec", "new line"],
+    ),
+    (
+        VoiceCommand::NewParagraph,
+        Placement::Anywhere,
+        &["yeni paragraf", "new paragraph"],
+    ),
+    (
+        VoiceCommand::DeleteLastWord,
+        Placement::Anywhere,
+        &["son kelimeyi sil", "delete last word"],
+    ),
+    (
+        VoiceCommand::DeleteLastSentence,
+        Placement::Anywhere,
+        &["son cumleyi sil", "delete last sentence"],
+    ),
+    (
+        VoiceCommand::UpperCaseAll,
+        Placement::Anywhere,
+        &[
+            "hepsini buyuk harf yap",
+            "hepsini buyuk harfle yaz",
+            "tumunu buyuk harf yap",
+            "make it all caps",
+        ],
+    ),
+    (
+        VoiceCommand::LowerCaseAll,
+        Placement::Anywhere,
+        &[
+            "hepsini kucuk harf yap",
+            "hepsini kucuk harfle yaz",
+            "tumunu kucuk harf yap",
+            "make it all lowercase",
+        ],
+    ),
+    (
+        VoiceCommand::Send,
+        Placement::End,
+        &["mesaji gonder", "entera bas", "send message"],
+    ),
+    (VoiceCommand::Undo, Placement::Alone, &["geri al", "undo", "undo that"]),
+    (
+        VoiceCommand::SelectAll,
+        Placement::Alone,
+        &["hepsini sec", "tumunu sec", "select all"],
+    ),
+];
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DetectedCommand {
+    pub command: VoiceCommand,
+    /// Transkriptteki orijinal ifade
+    pub phrase: String,
+    /// Komut `cleaned_text` uzerinde uygulandiysa true
+    pub applied: bool,
+    /// Komutun geldigi andaki `cleaned_text` uzunlugu (karakter).
+    /// 0 ise uygulanmayan komut yapistirmadan once calistirilmalidir.
+    pub offset: usize,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct VoiceCommandResult {
+    pub cleaned_text: String,
+    pub commands: Vec<DetectedCommand>,
+}
+
+/// Karsilastirma icin kelimeyi normalize et: Turkce kucuk harf, ASCII katlama,
+/// noktalama ve kesme isareti temizligi ("Satır." -> "satir", "Enter'a" -> "entera")
+fn fold_word(word: &str) -> String {
+    word.chars()
+        .flat_map(|c| match c {
+            'I' => vec!['i'],
+            'İ' => vec!['i'],
+            _ => c.to_lowercase().collect(),
+        })
+        .filter_map(|c| match c {
+            'ı' => Some('i'),
+            'ş' => Some('s'),
+            'ğ' => Some('g'),
+            'ü' => Some('u'),
+            'ö' => Some('o'),
+            'ç' => Some('c'),
+            'â' => Some('a'),
+            'î' => Some('i'),
+            'û' => Some('u'),
+            c if c.is_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+fn turkish_upper(text: &str) -> String {
+    text.chars()
+        .flat_map(|c| match c {
+            'i' => vec!['İ'],
+            'ı' => vec!['I'],
+            _ => c.to_uppercase().collect(),
+        })
+        .collect()
+}
+
+fn turkish_lower(text: &str) -> String {
+    text.chars()
+        .flat_map(|c| match c {
+            'I' => vec!['ı'],
+            'İ' => vec!['i'],
+            _ => c.to_lowercase().collect(),
+        })
+        .collect()
+}
+
+/// Kelime cumleyi bitiriyor mu ("satır." / "Nasılsın?")
+fn ends_sentence(word: &str) -> bool {
+    word.ends_with(['.', '!', '?', '…', ':'])
+}
+
+/// `i` konumunda eslesen en uzun komut: (komut, kelime sayisi).
+/// `after_break`: `i` konusma ya da cumle basinda (veya onceki komutun hemen ardinda).
+fn match_command(
+    words: &[&str],
+    folded: &[String],
+    i: usize,
+    after_break: bool,
+) -> Option<(VoiceCommand, usize)> {
+    if !after_break {
+        return None;
+    }
+    let total = folded.len();
+    let mut best: Option<(VoiceCommand, usize)> = None;
+    for (command, placement, phrases) in GRAMMAR {
+        for phrase in phrases.iter() {
+            let phrase_words: Vec<&str> = phrase.split(' ').collect();
+            let len = phrase_words.len();
+            if i + len > total || best.is_some_and(|(_, l)| l >= len) {
+                continue;
+            }
+            let placed = match placement {
+                Placement::Anywhere => true,
+                Placement::End => i + len == total,
+                Placement::Alone => i == 0 && len == total,
+            };
+            let closed = i + len == total || ends_sentence(words[i + len - 1]);
+            if placed && closed && phrase_words.iter().zip(&folded[i..i + len]).all(|(w, f)| *w == f) {
+                best = Some((*command, len));
+            }
+        }
+    }
+    best
+}
+
+fn push_word(buffer: &mut String, word: &str) {
+    if !buffer.is_empty() && !buffer.ends_with('\n') {
+        buffer.push(' ');
+    }
+    buffer.push_str(word);
+}
+
+fn trim_trailing_spaces(buffer: &mut String) {
+    let len = buffer.trim_end_matches(' ').len();
+    buffer.truncate(len);
+}
+
+pub(crate) fn delete_last_word(buffer: &mut String) -> bool {
+    let trimmed = buffer.trim_end();
+    if trimmed.is_empty() {
+        return false;
+    }
+    let cut = trimmed.rfind(char::is_whitespace).map(|i| i + 1).unwrap_or(0);
+    buffer.truncate(cut);
+    trim_trailing_spaces(buffer);
+    true
+}
+
+pub(crate) fn delete_last_sentence(buffer: &mut String) -> bool {
+    let trimmed = buffer.trim_end();
+    if trimmed.is_empty() {
+        return false;
+    }
+    // Son cumlenin kendi noktalamasini atla, bir onceki cumle sonunu bul
+    let body = trimmed.trim_end_matches(['.', '!', '?', '…']);
+    let cut = body
+        .rfind(['.', '!', '?', '…', '\n'])
+        .map(|i| i + body[i..].chars().next().map_or(1, char::len_utf8))
+        .unwrap_or(0);
+    buffer.truncate(cut);
+    trim_trailing_spaces(buffer);
+    true
+}
+
+fn apply_command(buffer: &mut String, command: VoiceCommand) -> bool {
+    match command {
+        VoiceCommand::NewLine => {
+            trim_trailing_spaces(buffer);
+            buffer.push('\n');
+            true
+        }
+        VoiceCommand::NewParagraph => {
+            trim_trailing_spaces(buffer);
+            buffer.push_str("\n\n");
+            true
+        }
+        VoiceCommand::DeleteLastWord => delete_last_word(buffer),
+        VoiceCommand::DeleteLastSentence => delete_last_sentence(buffer),
+        VoiceCommand::UpperCaseAll | VoiceCommand::LowerCaseAll => {
+            if !buffer.chars().any(char::is_alphabetic) {
+                return false;
+            }
+            *buffer = if command == VoiceCommand::UpperCaseAll {
+                turkish_upper(buffer)
+            } else {
+                turkish_lower(buffer)
+            };
+            true
+        }
+        // Tus vurusu gerektirir — yapistirma katmani calistirir
+        VoiceCommand::Send | VoiceCommand::Undo | VoiceCommand::SelectAll => false,
+    }
+}
+
+/// Transkriptteki komutlari ayikla ve metin ici olanlari uygula
+pub fn extract(text: &str) -> VoiceCommandResult {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let folded: Vec<String> = words.iter().map(|w| fold_word(w)).collect();
+
+    let mut buffer = String::new();
+    let mut commands = Vec::new();
+    let mut i = 0;
+    let mut after_break = true;
+    while i < words.len() {
+        let matched = match_command(&words, &folded, i, after_break).filter(|&(command, len)| {
+            // Silme tusu ancak konusmanin tamami komutsa; aksi halde dikte edilmis metindir
+            !command.is_destructive() || (i == 0 && len == words.len()) || !buffer.trim().is_empty()
+        });
+        match matched {
+            Some((command, len)) => {
+                let offset = buffer.chars().count();
+                let applied = apply_command(&mut buffer, command);
+                commands.push(DetectedCommand {
+                    command,
+                    phrase: words[i..i + len].join(" "),
+                    applied,
+                    offset,
+                });
+                i += len;
+                after_break = true;
+            }
+            None => {
+                push_word(&mut buffer, words[i]);
+                after_break = ends_sentence(words[i]);
+                i += 1;
+            }
+        }
+    }
+    trim_trailing_spaces(&mut buffer);
+
+    VoiceCommandResult {
+        cleaned_text: buffer,
+        commands,
+    }
+}
+
+#[tauri::command]
+pub fn extract_voice_commands(app: tauri::AppHandle, text: String) -> VoiceCommandResult {
+    if !crate::commands::settings::get_settings(app).features.voice_commands {
+        return VoiceCommandResult {
+            cleaned_text: text,
+            commands: Vec::new(),
+        };
+    }
+    let result = extract(&text);
+    if !result.commands.is_empty() {
+        log::info!(
+            "Ses komutlari algilandi: {:?}",
+            result.commands.iter().map(|c| c.command).collect::<Vec<_>>()
+        );
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(result: &VoiceCommandResult) -> Vec<(VoiceCommand, bool)> {
+        result.commands.iter().map(|c| (c.command, c.applied)).collect()
+    }
+
+    #[test]
+    fn test_new_line_and_paragraph() {
+        let r = extract("Merhaba dünya. Yeni satır. Nasılsın? yeni paragraf. Görüşürüz");
+        assert_eq!(r.cleaned_text, "Merhaba dünya.\nNasılsın?\n\nGörüşürüz");
+        assert_eq!(
+            kinds(&r),
+            vec![(VoiceCommand::NewLine, true), (VoiceCommand::NewParagraph, true)]
+        );
+        assert_eq!(r.commands[0].phrase, "Yeni satır.");
+
+        let ascii = extract("ilk satir. yeni satir. ikinci! new line. third");
+        assert_eq!(ascii.cleaned_text, "ilk satir.\nikinci!\nthird");
+    }
+
+    #[test]
+    fn test_dictation_is_not_a_command() {
+        for text in [
+            "We launched a new line of products.",
+            "I will send it tomorrow. I will send",
+            "Raporu yarın gönder",
+            "Let me scratch that idea. All caps lock is on",
+            "bugün hava çok güzel son kelimeyi sil soğuk",
+            "Yeni satır ekledim.",
+        ] {
+            let r = extract(text);
+            assert!(r.commands.is_empty(), "{}", text);
+            assert_eq!(r.cleaned_text, text);
+        }
+    }
+
+    #[test]
+    fn test_delete_commands() {
+        let r = extract("Toplantı yarın. Saat üçte değil. Son cümleyi sil. Saat dörtte.");
+        assert_eq!(r.cleaned_text, "Toplantı yarın. Saat dörtte.");
+
+        let w = extract("bugün hava çok güzel. Son kelimeyi sil. soğuk");
+        assert_eq!(w.cleaned_text, "bugün hava çok soğuk");
+
+        // Metin yoksa onceki yapistirmaya ait — tus vurusuna birakilir
+        let pending = extract("Son cümleyi sil");
+        assert_eq!(pending.cleaned_text, "");
+        assert_eq!(kinds(&pending), vec![(VoiceCommand::DeleteLastSentence, false)]);
+        assert_eq!(pending.commands[0].offset, 0);
+
+        // Ardindan dikte gelirse onceki yapistirmaya silme tusu gonderilmez
+        let mixed = extract("Son cümleyi sil. Saat dörtte.");
+        assert!(mixed.commands.is_empty());
+        assert_eq!(mixed.cleaned_text, "Son cümleyi sil. Saat dörtte.");
+    }
+
+    #[test]
+    fn test_case_commands_use_turkish_rules() {
+        let r = extract("istanbul ılık. hepsini büyük harf yap");
+        assert_eq!(r.cleaned_text, "İSTANBUL ILIK.");
+        let l = extract("IŞIK İZMİR. Make it all lowercase.");
+        assert_eq!(l.cleaned_text, "ışık izmir.");
+    }
+
+    #[test]
+    fn test_placement_rules() {
+        // "gonder" yalnizca sonda komut
+        let r = extract("dosyayı gönder lütfen");
+        assert_eq!(r.cleaned_text, "dosyayı gönder lütfen");
+        assert!(r.commands.is_empty());
+
+        let s = extract("Yarın görüşelim. Mesajı gönder.");
+        assert_eq!(s.cleaned_text, "Yarın görüşelim.");
+        assert_eq!(kinds(&s), vec![(VoiceCommand::Send, false)]);
+        assert_eq!(s.commands[0].offset, s.cleaned_text.chars().count());
+
+        // "geri al" tek basina soylenmeli
+        assert!(extract("parayı geri al").commands.is_empty());
+        assert_eq!(kinds(&extract("Geri al.")), vec![(VoiceCommand::Undo, false)]);
+    }
+}
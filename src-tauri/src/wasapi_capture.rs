@@ -0,0 +1,457 @@
+//! WASAPI loopback yakalama — hoparlor/kulaklikta calan sesi kaydeder.
+//!
+//! Paylasimli modda `AUDCLNT_STREAMFLAGS_LOOPBACK` ile acilan istemci, cikis
+//! cihazinin karisim formatinda (genelde 48kHz float32 stereo) paketler verir.
+//! COM nesneleri thread'ler arasi tasinamadigi icin tum yakalama ayri bir
+//! thread'de yapilir; ornekler mono'ya indirilip paylasilan tampona yazilir.
+//! Format cozumleme platformdan bagimsizdir ve test edilebilir.
+
+// Format cozumleme diger platformlarda yalnizca testlerde kullanilir
+#![cfg_attr(not(target_os = "windows"), allow(dead_code))]
+
+#[cfg(target_os = "windows")]
+pub use win::{list_capture_endpoints, list_render_devices, WasapiLoopback};
+
+const WAVE_FORMAT_PCM: u16 = 1;
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 3;
+const WAVE_FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SampleKind {
+    F32,
+    I16,
+    I24,
+    I32,
+}
+
+/// Cihazin paylasimli mod karisim formati
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct MixFormat {
+    kind: SampleKind,
+    channels: usize,
+    /// Bir cerceve (tum kanallar) kac bayt
+    block_align: usize,
+    sample_rate: u32,
+}
+
+impl MixFormat {
+    /// WAVEFORMATEX alanlarindan format. `float_subformat` yalnizca
+    /// WAVE_FORMAT_EXTENSIBLE icin anlamlidir (SubFormat IEEE float mu).
+    fn from_wave_format(
+        tag: u16,
+        bits: u16,
+        channels: u16,
+        block_align: u16,
+        sample_rate: u32,
+        float_subformat: bool,
+    ) -> Result<Self, String> {
+        let is_float = match tag {
+            WAVE_FORMAT_IEEE_FLOAT => true,
+            WAVE_FORMAT_PCM => false,
+            WAVE_FORMAT_EXTENSIBLE => float_subformat,
+            other => return Err(format!("Desteklenmeyen ses formati: 0x{:04x}", other)),
+        };
+        let kind = match (is_float, bits) {
+            (true, 32) => SampleKind::F32,
+            (false, 16) => SampleKind::I16,
+            (false, 24) => SampleKind::I24,
+            (false, 32) => SampleKind::I32,
+            _ => {
+                return Err(format!(
+                    "Desteklenmeyen ornek boyutu: {} bit{}",
+                    bits,
+                    if is_float { " float" } else { "" }
+                ))
+            }
+        };
+        if channels == 0 || (block_align as usize) < channels as usize * bits as usize / 8 {
+            return Err(format!("Gecersiz ses formati: {} kanal, blok {}", channels, block_align));
+        }
+        Ok(Self {
+            kind,
+            channels: channels as usize,
+            block_align: block_align as usize,
+            sample_rate,
+        })
+    }
+
+    /// Ic ice (interleaved) cerceveleri mono f32'ye indir (kanal ortalamasi)
+    fn downmix(&self, data: &[u8]) -> Vec<f32> {
+        let width = match self.kind {
+            SampleKind::I16 => 2,
+            SampleKind::I24 => 3,
+            SampleKind::F32 | SampleKind::I32 => 4,
+        };
+        let sample = |b: &[u8]| -> f32 {
+            match self.kind {
+                SampleKind::F32 => f32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+                SampleKind::I16 => i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0,
+                SampleKind::I24 => i32::from_le_bytes([0, b[0], b[1], b[2]]) as f32 / 2_147_483_648.0,
+                SampleKind::I32 => i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f32 / 2_147_483_648.0,
+            }
+        };
+        data.chunks_exact(self.block_align)
+            .map(|frame| {
+                let sum: f32 = (0..self.channels)
+                    .map(|c| sample(&frame[c * width..(c + 1) * width]))
+                    .sum();
+                sum / self.channels as f32
+            })
+            .collect()
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod win {
+    use super::MixFormat;
+    use crate::audio::{CaptureSource, LoopbackDevice};
+    use parking_lot::Mutex;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{mpsc, Arc};
+    use std::time::Duration;
+
+    use windows::core::{GUID, HSTRING};
+    use windows::Win32::Devices::Properties::DEVPKEY_Device_FriendlyName;
+    use windows::Win32::Media::Audio::{
+        eCapture, eConsole, eRender, EDataFlow, IAudioCaptureClient, IAudioClient, IMMDevice, IMMDeviceEnumerator,
+        MMDeviceEnumerator, AUDCLNT_BUFFERFLAGS_SILENT, AUDCLNT_E_DEVICE_INVALIDATED,
+        AUDCLNT_SHAREMODE_SHARED, AUDCLNT_STREAMFLAGS_LOOPBACK, DEVICE_STATE_ACTIVE,
+        WAVEFORMATEX, WAVEFORMATEXTENSIBLE,
+    };
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CoTaskMemFree, CoUninitialize, CLSCTX_ALL,
+        COINIT_MULTITHREADED, STGM_READ,
+    };
+    use windows::Win32::UI::Shell::PropertiesSystem::PROPERTYKEY;
+
+    /// KSDATAFORMAT_SUBTYPE_IEEE_FLOAT
+    const SUBTYPE_IEEE_FLOAT: GUID = GUID::from_u128(0x00000003_0000_0010_8000_00aa00389b71);
+    /// Paylasimli tampon suresi (100ns birimi) — 200ms
+    const BUFFER_DURATION_HNS: i64 = 2_000_000;
+    /// Paketler arasi bekleme; tampon bunun birkac katini tutar
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+    /// COM'u bu thread icin baslatir, kapsam bitince kapatir
+    struct ComGuard(bool);
+
+    impl ComGuard {
+        fn new() -> Self {
+            Self(unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) }.is_ok())
+        }
+    }
+
+    impl Drop for ComGuard {
+        fn drop(&mut self) {
+            if self.0 {
+                unsafe { CoUninitialize() };
+            }
+        }
+    }
+
+    fn enumerator() -> Result<IMMDeviceEnumerator, String> {
+        unsafe { CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL) }
+            .map_err(|e| format!("Ses cihazi listesi alinamadi: {}", e))
+    }
+
+    fn device_id(device: &IMMDevice) -> Result<String, String> {
+        unsafe {
+            let id = device.GetId().map_err(|e| format!("Cihaz kimligi alinamadi: {}", e))?;
+            let text = id.to_string().unwrap_or_default();
+            CoTaskMemFree(Some(id.0 as _));
+            Ok(text)
+        }
+    }
+
+    fn friendly_name(device: &IMMDevice) -> Option<String> {
+        let key = PROPERTYKEY {
+            fmtid: DEVPKEY_Device_FriendlyName.fmtid,
+            pid: DEVPKEY_Device_FriendlyName.pid,
+        };
+        unsafe {
+            let store = device.OpenPropertyStore(STGM_READ).ok()?;
+            let value = store.GetValue(&key).ok()?;
+            Some(value.to_string()).filter(|name| !name.is_empty())
+        }
+    }
+
+    unsafe fn read_mix_format(format: *const WAVEFORMATEX) -> Result<MixFormat, String> {
+        let wf = unsafe { std::ptr::read_unaligned(format) };
+        let float_subformat = wf.wFormatTag == super::WAVE_FORMAT_EXTENSIBLE && wf.cbSize >= 22 && {
+            let ext = unsafe { std::ptr::read_unaligned(format as *const WAVEFORMATEXTENSIBLE) };
+            let sub_format = ext.SubFormat;
+            sub_format == SUBTYPE_IEEE_FLOAT
+        };
+        MixFormat::from_wave_format(
+            wf.wFormatTag,
+            wf.wBitsPerSample,
+            wf.nChannels,
+            wf.nBlockAlign,
+            wf.nSamplesPerSec,
+            float_subformat,
+        )
+    }
+
+    /// `flow` yonundeki aktif uc noktalar; kimlik WASAPI uc nokta kimligidir
+    fn list_endpoints(flow: EDataFlow) -> Result<Vec<LoopbackDevice>, String> {
+        // Cagiran thread'in COM modu (STA) ile cakismamak icin ayri thread
+        std::thread::spawn(move || {
+            let _com = ComGuard::new();
+            let enumerator = enumerator()?;
+            let default_id = unsafe { enumerator.GetDefaultAudioEndpoint(flow, eConsole) }
+                .ok()
+                .and_then(|d| device_id(&d).ok());
+            let collection = unsafe { enumerator.EnumAudioEndpoints(flow, DEVICE_STATE_ACTIVE) }
+                .map_err(|e| format!("Ses cihazlari listelenemedi: {}", e))?;
+            let count = unsafe { collection.GetCount() }.unwrap_or(0);
+
+            let mut devices = Vec::new();
+            for i in 0..count {
+                let Ok(device) = (unsafe { collection.Item(i) }) else {
+                    continue;
+                };
+                let Ok(id) = device_id(&device) else {
+                    continue;
+                };
+                devices.push(LoopbackDevice {
+                    name: friendly_name(&device).unwrap_or_else(|| id.clone()),
+                    is_default: default_id.as_deref() == Some(id.as_str()),
+                    id,
+                });
+            }
+            Ok(devices)
+        })
+        .join()
+        .map_err(|_| "Ses cihazlari listelenemedi".to_string())?
+    }
+
+    /// Aktif cikis cihazlari (loopback kaynaklari)
+    pub fn list_render_devices() -> Result<Vec<LoopbackDevice>, String> {
+        list_endpoints(eRender)
+    }
+
+    /// Aktif giris cihazlari (kimlik, ad). cpal WASAPI cihaz adi da ayni
+    /// FriendlyName ozelligidir; mikrofonlara kararli kimlik vermek icin eslenir.
+    pub fn list_capture_endpoints() -> Result<Vec<(String, String)>, String> {
+        Ok(list_endpoints(eCapture)?.into_iter().map(|d| (d.id, d.name)).collect())
+    }
+
+    /// Bir cikis cihazinin loopback yakalamasi
+    pub struct WasapiLoopback {
+        buffer: Arc<Mutex<Vec<f32>>>,
+        stop: Arc<AtomicBool>,
+        /// Yakalama thread'i bittiyse nedeni
+        ended: Arc<Mutex<Option<String>>>,
+        thread: Option<std::thread::JoinHandle<()>>,
+        sample_rate: u32,
+        name: String,
+    }
+
+    impl WasapiLoopback {
+        /// `device_id` bos ise varsayilan cikis cihazi kullanilir
+        pub fn open(device_id: Option<&str>) -> Result<Self, String> {
+            let device_id = device_id.filter(|id| !id.is_empty()).map(str::to_string);
+            let buffer = Arc::new(Mutex::new(Vec::new()));
+            let stop = Arc::new(AtomicBool::new(false));
+            let ended = Arc::new(Mutex::new(None));
+            let (ready_tx, ready_rx) = mpsc::channel::<Result<(u32, String), String>>();
+
+            let (sink, stop_flag, end_reason) = (buffer.clone(), stop.clone(), ended.clone());
+            let thread = std::thread::Builder::new()
+                .name("wasapi-loopback".into())
+                .spawn(move || {
+                    let _com = ComGuard::new();
+                    let (client, capture, format) = match open_client(device_id.as_deref()) {
+                        Ok((client, capture, format, name)) => {
+                            ready_tx.send(Ok((format.sample_rate, name))).ok();
+                            (client, capture, format)
+                        }
+                        Err(e) => {
+                            ready_tx.send(Err(e)).ok();
+                            return;
+                        }
+                    };
+
+                    let result = capture_loop(&capture, &format, &sink, &stop_flag);
+                    unsafe { client.Stop() }.ok();
+                    let reason = match result {
+                        Ok(()) => "Sistem sesi yakalama durduruldu".to_string(),
+                        Err(e) => {
+                            log::error!("WASAPI yakalama hatasi: {}", e);
+                            e
+                        }
+                    };
+                    *end_reason.lock() = Some(reason);
+                })
+                .map_err(|e| format!("Yakalama thread'i baslatilamadi: {}", e))?;
+
+            match ready_rx.recv() {
+                Ok(Ok((sample_rate, name))) => {
+                    log::info!("WASAPI loopback basladi: {} ({}Hz)", name, sample_rate);
+                    Ok(Self {
+                        buffer,
+                        stop,
+                        ended,
+                        thread: Some(thread),
+                        sample_rate,
+                        name,
+                    })
+                }
+                Ok(Err(e)) => {
+                    thread.join().ok();
+                    Err(e)
+                }
+                Err(_) => Err("Sistem sesi yakalama baslatilamadi".to_string()),
+            }
+        }
+    }
+
+    fn open_client(
+        device_id: Option<&str>,
+    ) -> Result<(IAudioClient, IAudioCaptureClient, MixFormat, String), String> {
+        unsafe {
+            let enumerator = enumerator()?;
+            let device = match device_id {
+                Some(id) => enumerator
+                    .GetDevice(&HSTRING::from(id))
+                    .map_err(|e| format!("Cihaz bulunamadi: {} ({})", id, e))?,
+                None => enumerator
+                    .GetDefaultAudioEndpoint(eRender, eConsole)
+                    .map_err(|e| format!("Varsayilan cikis cihazi bulunamadi: {}", e))?,
+            };
+            let name = friendly_name(&device).unwrap_or_else(|| "Sistem sesi".to_string());
+
+            let client: IAudioClient = device
+                .Activate(CLSCTX_ALL, None)
+                .map_err(|e| format!("Ses istemcisi acilamadi: {}", e))?;
+            let mix = client
+                .GetMixFormat()
+                .map_err(|e| format!("Karisim formati alinamadi: {}", e))?;
+            let format = read_mix_format(mix);
+            let init = format.as_ref().map_err(Clone::clone).and_then(|_| {
+                client
+                    .Initialize(
+                        AUDCLNT_SHAREMODE_SHARED,
+                        AUDCLNT_STREAMFLAGS_LOOPBACK,
+                        BUFFER_DURATION_HNS,
+                        0,
+                        mix,
+                        None,
+                    )
+                    .map_err(|e| format!("Loopback akisi baslatilamadi: {}", e))
+            });
+            CoTaskMemFree(Some(mix as _));
+            init?;
+            let format = format?;
+
+            let capture: IAudioCaptureClient = client
+                .GetService()
+                .map_err(|e| format!("Yakalama servisi alinamadi: {}", e))?;
+            client
+                .Start()
+                .map_err(|e| format!("Loopback akisi baslatilamadi: {}", e))?;
+            Ok((client, capture, format, name))
+        }
+    }
+
+    fn capture_loop(
+        capture: &IAudioCaptureClient,
+        format: &MixFormat,
+        sink: &Mutex<Vec<f32>>,
+        stop: &AtomicBool,
+    ) -> Result<(), String> {
+        let map_err = |e: windows::core::Error| {
+            if e.code() == AUDCLNT_E_DEVICE_INVALIDATED {
+                "Cikis cihazi kaldirildi veya degisti".to_string()
+            } else {
+                format!("Sistem sesi okunamadi: {}", e)
+            }
+        };
+
+        while !stop.load(Ordering::SeqCst) {
+            std::thread::sleep(POLL_INTERVAL);
+            // Hicbir sey calmiyorsa loopback paket uretmez
+            loop {
+                let frames = unsafe { capture.GetNextPacketSize() }.map_err(map_err)?;
+                if frames == 0 {
+                    break;
+                }
+                let mut data = std::ptr::null_mut();
+                let mut frames = 0u32;
+                let mut flags = 0u32;
+                unsafe { capture.GetBuffer(&mut data, &mut frames, &mut flags, None, None) }.map_err(map_err)?;
+
+                let samples = if flags & AUDCLNT_BUFFERFLAGS_SILENT.0 as u32 != 0 || data.is_null() {
+                    vec![0.0; frames as usize]
+                } else {
+                    let bytes = unsafe { std::slice::from_raw_parts(data, frames as usize * format.block_align) };
+                    format.downmix(bytes)
+                };
+                unsafe { capture.ReleaseBuffer(frames) }.map_err(map_err)?;
+                sink.lock().extend_from_slice(&samples);
+            }
+        }
+        Ok(())
+    }
+
+    impl CaptureSource for WasapiLoopback {
+        fn sample_rate(&self) -> u32 {
+            self.sample_rate
+        }
+
+        fn name(&self) -> String {
+            self.name.clone()
+        }
+
+        fn read(&mut self) -> Result<Option<Vec<f32>>, String> {
+            let samples = std::mem::take(&mut *self.buffer.lock());
+            if samples.is_empty() {
+                if let Some(reason) = self.ended.lock().clone() {
+                    return Err(reason);
+                }
+            }
+            Ok(Some(samples))
+        }
+    }
+
+    impl Drop for WasapiLoopback {
+        fn drop(&mut self) {
+            self.stop.store(true, Ordering::SeqCst);
+            if let Some(thread) = self.thread.take() {
+                thread.join().ok();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mix_format_parsing() {
+        let float = MixFormat::from_wave_format(WAVE_FORMAT_EXTENSIBLE, 32, 2, 8, 48000, true).unwrap();
+        assert_eq!(float.kind, SampleKind::F32);
+        assert_eq!((float.channels, float.block_align, float.sample_rate), (2, 8, 48000));
+        let pcm = MixFormat::from_wave_format(WAVE_FORMAT_EXTENSIBLE, 24, 2, 6, 44100, false).unwrap();
+        assert_eq!(pcm.kind, SampleKind::I24);
+        assert!(MixFormat::from_wave_format(WAVE_FORMAT_IEEE_FLOAT, 64, 2, 16, 48000, false).is_err());
+        assert!(MixFormat::from_wave_format(0x0055, 16, 2, 4, 44100, false).is_err());
+        assert!(MixFormat::from_wave_format(WAVE_FORMAT_PCM, 16, 2, 2, 44100, false).is_err());
+    }
+
+    #[test]
+    fn test_downmix() {
+        let float = MixFormat::from_wave_format(WAVE_FORMAT_IEEE_FLOAT, 32, 2, 8, 48000, false).unwrap();
+        let bytes: Vec<u8> = [0.5f32, -0.5, 1.0, 0.0].iter().flat_map(|s| s.to_le_bytes()).collect();
+        assert_eq!(float.downmix(&bytes), vec![0.0, 0.5]);
+
+        let pcm16 = MixFormat::from_wave_format(WAVE_FORMAT_PCM, 16, 1, 2, 16000, false).unwrap();
+        let bytes: Vec<u8> = [16384i16, -32768].iter().flat_map(|s| s.to_le_bytes()).collect();
+        assert_eq!(pcm16.downmix(&bytes), vec![0.5, -1.0]);
+
+        // 24 bit, 2 kanal; yarim kalan cerceve atlanir
+        let pcm24 = MixFormat::from_wave_format(WAVE_FORMAT_PCM, 24, 2, 6, 48000, false).unwrap();
+        let bytes = [0x00, 0x00, 0x40, 0x00, 0x00, 0x40, 0x00, 0x00];
+        assert_eq!(pcm24.downmix(&bytes), vec![0.5]);
+    }
+}
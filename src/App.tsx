@@ -75,6 +75,8 @@ function App() {
           notifications: saved.notifications ?? true,
           logLevel: saved.log_level ?? "info",
           ttsShortcut: saved.tts_shortcut ?? "Ctrl+Shift+R",
+          whisperLivePreview: saved.whisper_live_preview ?? true,
+          whisperTranslate: saved.whisper_translate ?? false,
           features: saved.features ? {
             voiceCommands: saved.features.voice_commands ?? true,
             sentiment: saved.features.sentiment ?? false,
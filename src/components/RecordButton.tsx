@@ -41,6 +41,7 @@ export function RecordButton() {
               engine: settings.transcriptionEngine as "web" | "whisper" | "deepgram" | "azure" | "google-cloud",
               language: settings.language,
               modelId: settings.transcriptionEngine === "web" ? "web-speech" : settings.selectedModel,
+              segments: result.segments,
             });
 
             // Otomatik yapistirma aktifse aktif uygulamaya yaz
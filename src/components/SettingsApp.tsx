@@ -21,7 +21,7 @@ import { useTranscriptionStore } from "../stores/transcriptionStore";
 import { useRecordingStore } from "../stores/recordingStore";
 import { useTTSStore } from "../stores/ttsStore";
 import { getTTSService } from "../lib/ttsService";
-import { getSettings } from "../lib/tauri-commands";
+import { getSettings, type DeviceFallback } from "../lib/tauri-commands";
 import { readText } from "@tauri-apps/plugin-clipboard-manager";
 import { listen } from "@tauri-apps/api/event";
 import { ToastContainer, showToast } from "./ToastNotification";
@@ -91,6 +91,19 @@ export function SettingsApp() {
     return () => { unlisten.then((fn) => fn()); };
   }, []);
 
+  // Kayit sirasinda mikrofon cikarildiysa varsayilana gecildigini bildir
+  useEffect(() => {
+    const unlisten = listen<DeviceFallback>("audio-device-fallback", (event) => {
+      showToast({
+        type: "info",
+        title: "Mikrofon degisti",
+        message: `${event.payload.lost} bulunamadi, ${event.payload.fallback} kullaniliyor`,
+        duration: 5000,
+      });
+    });
+    return () => { unlisten.then((fn) => fn()); };
+  }, []);
+
   // Sablon ses tetikleyici bildirimi dinle (overlay penceresinden gelir)
   useEffect(() => {
     const unlisten = listen<{ name: string; id: string; hasCustomVars?: boolean; customVars?: string[] }>(
@@ -185,6 +198,8 @@ export function SettingsApp() {
           notifications: saved.notifications ?? true,
           logLevel: saved.log_level ?? "info",
           ttsShortcut: saved.tts_shortcut ?? "Ctrl+Shift+R",
+          whisperLivePreview: saved.whisper_live_preview ?? true,
+          whisperTranslate: saved.whisper_translate ?? false,
           translateEngine: saved.translate_engine ?? "google",
           deeplApiKey: saved.deepl_api_key ?? "",
           translateTargetLang: saved.translate_target_lang ?? "en",
@@ -297,6 +312,8 @@ export function SettingsApp() {
               notifications: saved.notifications ?? true,
               logLevel: saved.log_level ?? "info",
               ttsShortcut: saved.tts_shortcut ?? "Ctrl+Shift+R",
+              whisperLivePreview: saved.whisper_live_preview ?? true,
+              whisperTranslate: saved.whisper_translate ?? false,
               translateEngine: saved.translate_engine ?? "google",
               deeplApiKey: saved.deepl_api_key ?? "",
               translateTargetLang: saved.translate_target_lang ?? "en",
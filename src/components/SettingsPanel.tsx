@@ -1,14 +1,22 @@
 import React, { useEffect, useState } from "react";
-import { emit } from "@tauri-apps/api/event";
+import { emit, listen } from "@tauri-apps/api/event";
 import { useSettingsStore, type AppSettings, type WakeWordStatus } from "../stores/settingsStore";
 import {
   listAudioDevices,
+  watchAudioDevices,
+  listModels,
+  listAudioInputConfigs,
+  getAudioInputConfig,
+  setAudioInputConfig,
   saveSettings,
   changeShortcut,
   setOverlayFollowCursor,
   type AudioDevice,
+  type InputConfigOption,
+  type WhisperModel,
 } from "../lib/tauri-commands";
 import { invoke } from "@tauri-apps/api/core";
+import { speechLanguage } from "../lib/settingsUtils";
 
 /* ════════════════════════════════════════
    Helpers — untouched business logic
@@ -16,6 +24,7 @@ import { invoke } from "@tauri-apps/api/core";
 
 const LANGUAGES = [
   { id: "tr", name: "Turkce" },
+  { id: "auto", name: "Otomatik (Whisper)" },
   { id: "en", name: "English" },
   { id: "de", name: "Deutsch" },
   { id: "fr", name: "Francais" },
@@ -45,6 +54,8 @@ function toBackend(s: AppSettings) {
     paragraph_break: s.paragraphBreak,
     notifications: s.notifications, log_level: s.logLevel,
     tts_shortcut: s.ttsShortcut ?? "Ctrl+Shift+R",
+    whisper_live_preview: s.whisperLivePreview ?? true,
+    whisper_translate: s.whisperTranslate ?? false,
   };
 }
 
@@ -324,8 +335,49 @@ function ShortcutChip({ value, onChange }: { value: string; onChange: (s: string
 export function SettingsPanel() {
   const { settings, updateSettings } = useSettingsStore();
   const [devices, setDevices] = useState<AudioDevice[]>([]);
+  const [models, setModels] = useState<WhisperModel[]>([]);
 
-  useEffect(() => { listAudioDevices().then(setDevices).catch(console.error); }, []);
+  const [inputConfigs, setInputConfigs] = useState<InputConfigOption[]>([]);
+  const [inputConfig, setInputConfig] = useState("");
+
+  useEffect(() => { listModels().then(setModels).catch(console.error); }, []);
+
+  useEffect(() => {
+    listAudioDevices().then(setDevices).catch(console.error);
+    // Mikrofon takilip cikarildiginda liste backend'den gelir; yoklama yalnizca panel gorunurken
+    const unlisten = listen<AudioDevice[]>("audio-devices-changed", (e) => setDevices(e.payload));
+    const syncWatch = () => {
+      const visible = document.visibilityState === "visible";
+      if (visible) listAudioDevices().then(setDevices).catch(console.error);
+      watchAudioDevices(visible).catch(console.error);
+    };
+    syncWatch();
+    document.addEventListener("visibilitychange", syncWatch);
+    return () => {
+      document.removeEventListener("visibilitychange", syncWatch);
+      watchAudioDevices(false).catch(console.error);
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Secili cihaz takili degilse secimi kaybetmemek icin listede goster
+  const selectedMissing = !!settings.selectedDevice && !devices.some((d) => d.id === settings.selectedDevice || d.name === settings.selectedDevice);
+
+  // Secili mikrofonun desteklenen hiz/kanal secenekleri ve kayitli tercih
+  useEffect(() => {
+    const device = settings.selectedDevice || null;
+    listAudioInputConfigs(device).then(setInputConfigs).catch(() => setInputConfigs([]));
+    getAudioInputConfig(device)
+      .then((c) => setInputConfig(c ? `${c.sample_rate}:${c.channels}` : ""))
+      .catch(console.error);
+  }, [settings.selectedDevice]);
+
+  const saveInputConfig = async (v: string) => {
+    setInputConfig(v);
+    const [rate, channels] = v.split(":").map(Number);
+    try { await setAudioInputConfig(settings.selectedDevice || null, v ? { sample_rate: rate, channels } : null); }
+    catch (e) { console.error("Input config save error:", e); }
+  };
 
   const save = async (partial: Partial<AppSettings>) => {
     updateSettings(partial);
@@ -334,6 +386,23 @@ export function SettingsPanel() {
     catch (e) { console.error("Save error:", e); }
   };
 
+  // Turbo modeller ceviri verisiyle egitilmedi; ceviri acikken backend kaydi reddeder
+  const translateSupported = models.find((m) => m.id === settings.selectedModel)?.supports_translate ?? true;
+
+  // Otomatik dil yalnizca Whisper'da var; baska motora gecerken varsayilan dile don
+  const saveEngine = (transcriptionEngine: AppSettings["transcriptionEngine"]) =>
+    save(transcriptionEngine !== "whisper" && settings.language === "auto"
+      ? { transcriptionEngine, language: speechLanguage(settings.language) }
+      : { transcriptionEngine });
+
+  // Eski ayarlar cihaz adini saklar; cihaz takiliysa kararli kimligine tasi
+  useEffect(() => {
+    const selected = settings.selectedDevice;
+    if (!selected || devices.some((d) => d.id === selected)) return;
+    const device = devices.find((d) => d.name === selected);
+    if (device) save({ selectedDevice: device.id });
+  }, [devices, settings.selectedDevice]);
+
   const engine = settings.transcriptionEngine;
 
   return (
@@ -344,30 +413,60 @@ export function SettingsPanel() {
         <div className="sp-engine-grid">
           <EngineCard active={engine === "web"} title="Google" desc="Online · Hizli"
             icon={<svg width="20" height="20" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="1.4" strokeLinecap="round" strokeLinejoin="round"><path d="M22 12h-4l-3 9L9 3l-3 9H2" /></svg>}
-            onClick={() => save({ transcriptionEngine: "web" })} />
+            onClick={() => saveEngine("web")} />
           <EngineCard active={engine === "whisper"} title="Whisper" desc="Offline · Yerel"
             icon={<svg width="20" height="20" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="1.4" strokeLinecap="round" strokeLinejoin="round"><path d="M12 1a3 3 0 0 0-3 3v8a3 3 0 0 0 6 0V4a3 3 0 0 0-3-3z" /><path d="M19 10v2a7 7 0 0 1-14 0v-2" /></svg>}
             onClick={() => save({ transcriptionEngine: "whisper" })} />
           <EngineCard active={engine === "deepgram"} title="Deepgram" desc="Bulut · Nova-3"
             icon={<svg width="20" height="20" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="1.4" strokeLinecap="round" strokeLinejoin="round"><path d="M2 12s3-7 10-7 10 7 10 7-3 7-10 7-10-7-10-7z"/><circle cx="12" cy="12" r="3"/></svg>}
-            onClick={() => save({ transcriptionEngine: "deepgram" })} />
+            onClick={() => saveEngine("deepgram")} />
           <EngineCard active={engine === "azure"} title="Azure" desc="Bulut · Speech"
             icon={<svg width="20" height="20" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="1.4" strokeLinecap="round" strokeLinejoin="round"><rect x="3" y="3" width="18" height="18" rx="3"/><path d="M8 12h8M12 8v8"/></svg>}
-            onClick={() => save({ transcriptionEngine: "azure" })} />
+            onClick={() => saveEngine("azure")} />
           <EngineCard active={engine === "google-cloud"} title="G. Cloud" desc="Bulut · Chirp"
             icon={<svg width="20" height="20" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="1.4" strokeLinecap="round" strokeLinejoin="round"><path d="M18 10h-1.26A8 8 0 1 0 9 20h9a5 5 0 0 0 0-10z"/></svg>}
-            onClick={() => save({ transcriptionEngine: "google-cloud" })} />
+            onClick={() => saveEngine("google-cloud")} />
         </div>
+        {engine === "whisper" && (
+          <Item title="Canli onizleme" desc="Kayit sirasinda metni yaz (ek islemci yuku)" onClick={() => save({ whisperLivePreview: !settings.whisperLivePreview })}>
+            <Pill checked={settings.whisperLivePreview} onChange={() => save({ whisperLivePreview: !settings.whisperLivePreview })} />
+          </Item>
+        )}
+        {engine === "whisper" && (
+          <Item title="Ingilizceye cevir"
+            desc={translateSupported ? "Konusmayi cevrimdisi Ingilizce metne cevir" : "Secili model ceviri yapamaz — Small veya Large V3 secin"}
+            onClick={() => (translateSupported || settings.whisperTranslate) && save({ whisperTranslate: !settings.whisperTranslate })}>
+            <Pill checked={settings.whisperTranslate && translateSupported}
+              onChange={() => (translateSupported || settings.whisperTranslate) && save({ whisperTranslate: !settings.whisperTranslate })} />
+          </Item>
+        )}
       </Section>
 
       {/* ── Dil ve Giris ── */}
       <Section title="Dil ve Giris" icon={icons.language}>
         <Item title="Tanima dili">
-          <Sel value={settings.language} options={LANGUAGES} onChange={(v) => save({ language: v })} />
+          <Sel value={engine === "whisper" ? settings.language : speechLanguage(settings.language)}
+            options={engine === "whisper" ? LANGUAGES : LANGUAGES.filter((l) => l.id !== "auto")}
+            onChange={(v) => save({ language: v })} />
         </Item>
         <Item title="Mikrofon">
-          <Sel value={settings.selectedDevice || ""} options={[{ id: "", name: "Varsayilan" }, ...devices]} onChange={(v) => save({ selectedDevice: v || null })} />
+          <Sel value={settings.selectedDevice || ""}
+            options={[
+              { id: "", name: "Varsayilan" },
+              ...devices,
+              ...(selectedMissing ? [{ id: settings.selectedDevice!, name: "Bagli olmayan cihaz" }] : []),
+            ]}
+            onChange={(v) => save({ selectedDevice: v || null })} />
         </Item>
+        {inputConfigs.length > 0 && (
+          <Item title="Ses formati" desc="Ornekleme hizi ve kanal sayisi">
+            <Sel value={inputConfig} onChange={saveInputConfig}
+              options={[{ id: "", name: "Varsayilan" }, ...inputConfigs.map((c) => ({
+                id: `${c.sample_rate}:${c.channels}`,
+                name: `${c.sample_rate / 1000} kHz · ${c.channels === 1 ? "Mono" : c.channels === 2 ? "Stereo" : `${c.channels} kanal`} · ${c.sample_format}${c.is_default ? " *" : ""}`,
+              }))]} />
+          </Item>
+        )}
         <Range label="Sessizlik zamani" value={settings.silenceTimeout} min={2} max={10} step={0.5} unit="sn" onChange={(v) => save({ silenceTimeout: v })} />
         <Range label="Ses algilama esigi (VAD)" value={Math.round(settings.vadThreshold * 100)} min={10} max={90} step={5} unit="%"
           onChange={(v) => save({ vadThreshold: v / 100 })} />
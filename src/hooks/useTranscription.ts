@@ -10,7 +10,14 @@ import {
   getSettings,
   processText,
   getAudioLevels,
+  startStreamingTranscription,
+  stopStreamingTranscription,
+  extractVoiceCommands,
+  executeVoiceCommands,
 } from "../lib/tauri-commands";
+import type { FinalHypothesis, PartialHypothesis, TranscriptionSegment, VoiceCommand } from "../lib/tauri-commands";
+import { speechLanguage } from "../lib/settingsUtils";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import {
   startWebSpeech,
   stopWebSpeech,
@@ -54,6 +61,7 @@ let maxDurationTimer: ReturnType<typeof setTimeout> | undefined;
 let silenceMonitorTimer: ReturnType<typeof setInterval> | undefined;
 let silenceStartTime = 0;
 let hasDetectedSpeech = false;
+let livePreviewUnlisten: UnlistenFn[] = [];
 
 function clearDurationTimer() {
   if (durationTimer) {
@@ -82,6 +90,40 @@ function clearSilenceMonitor() {
   stopSileroVad().catch(() => {});
 }
 
+/**
+ * Whisper kaydi sirasinda canli onizleme (backend streaming_stt).
+ * Basarisiz olursa sessizce devre disi — dikte normal sekilde devam eder.
+ */
+async function startWhisperLivePreview() {
+  stopWhisperLivePreview();
+  if (!useSettingsStore.getState().settings.whisperLivePreview) return;
+  let finalized = "";
+  try {
+    livePreviewUnlisten = [
+      await listen<FinalHypothesis>("stt-final", (e) => {
+        finalized = finalized ? `${finalized} ${e.payload.text}` : e.payload.text;
+        if (isActive) useTranscriptionStore.getState().setCurrentText(finalized);
+      }),
+      await listen<PartialHypothesis>("stt-partial", (e) => {
+        if (!isActive) return;
+        const live = [finalized, e.payload.stable, e.payload.unstable].filter(Boolean).join(" ");
+        useTranscriptionStore.getState().setCurrentText(live);
+      }),
+    ];
+    await startStreamingTranscription(useSettingsStore.getState().settings.selectedModel);
+  } catch (err) {
+    console.warn("[Canli onizleme] Baslatilamadi:", err);
+    stopWhisperLivePreview();
+  }
+}
+
+/** Onizleme olaylarini birak; backend son pencereyi cozmeden oturumu kapatir */
+function stopWhisperLivePreview() {
+  livePreviewUnlisten.forEach((unlisten) => unlisten());
+  livePreviewUnlisten = [];
+  stopStreamingTranscription().catch(() => {});
+}
+
 /**
  * Whisper icin sessizlik izleme
  * Backend'den ses seviyesini pollla, sessizlik suresi dolunca kaydi durdur
@@ -167,7 +209,7 @@ function getMaxRecordDurationMs(): number {
 
 type EngineType = "web" | "whisper" | "deepgram" | "azure" | "google-cloud";
 
-async function finishWithText(text: string, engineOverride?: EngineType) {
+async function finishWithText(text: string, engineOverride?: EngineType, segments?: TranscriptionSegment[]) {
   if (!text) {
     useTranscriptionStore.getState().setCurrentText("");
     return;
@@ -197,6 +239,26 @@ async function finishWithText(text: string, engineOverride?: EngineType) {
     useUsageStore.getState().addUsage(provider as "deepgram" | "azure" | "googleCloud", durationMs);
   }
 
+  // Sesli duzenleme komutlari ("yeni satir", "mesaji gonder"...) — metin ici olanlar
+  // uygulanir, kalanlar yapistirmadan once/sonra tus vurusu olarak calisir
+  let keysBefore: VoiceCommand[] = [];
+  let keysAfter: VoiceCommand[] = [];
+  try {
+    const saved = await getSettings();
+    if (saved.features?.voice_commands ?? true) {
+      const result = await extractVoiceCommands(processedText);
+      if (result.commands.length > 0) {
+        if (!originalText) originalText = processedText;
+        processedText = result.cleaned_text;
+        const pending = result.commands.filter((c) => !c.applied);
+        keysBefore = pending.filter((c) => c.offset === 0).map((c) => c.command);
+        keysAfter = pending.filter((c) => c.offset > 0).map((c) => c.command);
+      }
+    }
+  } catch {
+    // komut ayiklanamazsa metni oldugu gibi kullan
+  }
+
   const modelIdMap: Record<string, string> = {
     web: "web-speech",
     deepgram: "deepgram-nova-3",
@@ -205,6 +267,13 @@ async function finishWithText(text: string, engineOverride?: EngineType) {
   };
 
   useTranscriptionStore.getState().setCurrentText(processedText);
+  if (!processedText.trim()) {
+    // Yalnizca komut soylendi ("son cumleyi sil", "mesaji gonder")
+    if (settings.autoPaste) {
+      await executeVoiceCommands([...keysBefore, ...keysAfter]).catch(() => {});
+    }
+    return;
+  }
   useTranscriptionStore.getState().addToHistory({
     id: Date.now().toString(),
     text: processedText,
@@ -215,11 +284,14 @@ async function finishWithText(text: string, engineOverride?: EngineType) {
     language: settings.language,
     modelId: modelIdMap[engine] || settings.selectedModel,
     confidence: engine === "web" ? getResultConfidence() : undefined,
+    segments,
   });
 
   if (settings.autoPaste) {
     try {
+      await executeVoiceCommands(keysBefore);
       await pasteToActiveApp(processedText);
+      await executeVoiceCommands(keysAfter);
     } catch {
       // sessizce devam
     }
@@ -237,7 +309,7 @@ function restartWakeWordIfEnabled() {
   const settings = useSettingsStore.getState().settings;
   if (settings.voiceActivation) {
     setTimeout(() => {
-      startWakeWordListener(settings.language, handleWakeWord, settings.wakeWord, wakeWordStatusCallback);
+      startWakeWordListener(speechLanguage(settings.language), handleWakeWord, settings.wakeWord, wakeWordStatusCallback);
     }, 500); // 300ms -> 500ms: SpeechRecognition abort async — cakismayi onle
   }
 }
@@ -284,7 +356,7 @@ function doForceStop() {
       useTranscriptionStore.getState().setCurrentText("Donusturuluyor...");
       try {
         const apiKey = useSettingsStore.getState().settings.googleCloudApiKey;
-        const lang = useSettingsStore.getState().settings.language;
+        const lang = speechLanguage(useSettingsStore.getState().settings.language);
         const text = await transcribeWithGoogleCloud(apiKey, audioData, lang);
         if (text) {
           playDeactivationSound();
@@ -308,6 +380,7 @@ function doForceStop() {
     });
   } else {
     // Whisper
+    stopWhisperLivePreview();
     stopRecording().then(async (audioData) => {
       useRecordingStore.getState().setRecording(false);
       useRecordingStore.getState().setAudioData(audioData);
@@ -320,7 +393,7 @@ function doForceStop() {
         );
         if (result.text) {
           playDeactivationSound();
-          await finishWithText(result.text, "whisper");
+          await finishWithText(result.text, "whisper", result.segments);
         } else {
           playErrorSound();
           useTranscriptionStore.getState().setCurrentText("");
@@ -380,7 +453,7 @@ function handleWakeWord() {
       return;
     }
 
-    const lang = settings.language;
+    const lang = speechLanguage(settings.language);
     setTimeout(() => {
       startWebSpeech(
         lang,
@@ -420,7 +493,7 @@ function handleWakeWord() {
     }, 200);
   } else if (engine === "deepgram") {
     const apiKey = settings.deepgramApiKey;
-    const lang = settings.language;
+    const lang = speechLanguage(settings.language);
     setTimeout(() => {
       startDeepgram(apiKey, lang, {
         onInterimResult: (text) => {
@@ -453,7 +526,7 @@ function handleWakeWord() {
   } else if (engine === "azure") {
     const key = settings.azureSpeechKey;
     const region = settings.azureSpeechRegion;
-    const lang = settings.language;
+    const lang = speechLanguage(settings.language);
     setTimeout(() => {
       startAzureSpeech(key, region, lang, {
         onInterimResult: (text) => {
@@ -502,6 +575,7 @@ function handleWakeWord() {
     const device = settings.selectedDevice ?? undefined;
     startRecording(device).then(() => {
       startSilenceMonitor();
+      startWhisperLivePreview();
     }).catch((err) => {
       isActive = false;
       clearDurationTimer();
@@ -580,7 +654,7 @@ export function useTranscription() {
           throw new Error("Web Speech API desteklenmiyor");
         }
 
-        const lang = useSettingsStore.getState().settings.language;
+        const lang = speechLanguage(useSettingsStore.getState().settings.language);
         // Push-to-talk/buton modu: sessizlik izleme YOK — kullanici birakinca durur
         // noAutoRestart: oturum restart'ini engelle — kisa kelimelerin kaybolmasini onler
         startWebSpeech(lang, {
@@ -614,7 +688,7 @@ export function useTranscription() {
       } else if (engine === "deepgram") {
         const s = useSettingsStore.getState().settings;
         if (!s.deepgramApiKey) throw new Error("Deepgram API key girilmemis");
-        startDeepgram(s.deepgramApiKey, s.language, {
+        startDeepgram(s.deepgramApiKey, speechLanguage(s.language), {
           onInterimResult: (text) => {
             useTranscriptionStore.getState().setCurrentText(text);
           },
@@ -644,7 +718,7 @@ export function useTranscription() {
       } else if (engine === "azure") {
         const s = useSettingsStore.getState().settings;
         if (!s.azureSpeechKey || !s.azureSpeechRegion) throw new Error("Azure Speech key veya region girilmemis");
-        await startAzureSpeech(s.azureSpeechKey, s.azureSpeechRegion, s.language, {
+        await startAzureSpeech(s.azureSpeechKey, s.azureSpeechRegion, speechLanguage(s.language), {
           onInterimResult: (text) => {
             useTranscriptionStore.getState().setCurrentText(text);
           },
@@ -681,6 +755,7 @@ export function useTranscription() {
         const device =
           useSettingsStore.getState().settings.selectedDevice ?? undefined;
         await startRecording(device);
+        startWhisperLivePreview();
       }
     } catch (err) {
       isActive = false;
@@ -733,7 +808,7 @@ export function useTranscription() {
 
         try {
           const s = useSettingsStore.getState().settings;
-          const text = await transcribeWithGoogleCloud(s.googleCloudApiKey, audioData, s.language);
+          const text = await transcribeWithGoogleCloud(s.googleCloudApiKey, audioData, speechLanguage(s.language));
           if (text) {
             playDeactivationSound();
             await finishWithText(text, "google-cloud");
@@ -755,6 +830,7 @@ export function useTranscription() {
       restartWakeWordIfEnabled();
     } else {
       // Whisper
+      stopWhisperLivePreview();
       try {
         const audioData = await stopRecording();
         useRecordingStore.getState().setRecording(false);
@@ -770,7 +846,7 @@ export function useTranscription() {
 
           if (result.text) {
             playDeactivationSound();
-            await finishWithText(result.text, "whisper");
+            await finishWithText(result.text, "whisper", result.segments);
           } else {
             playErrorSound();
             useTranscriptionStore.getState().setCurrentText("");
@@ -795,7 +871,7 @@ export function useTranscription() {
   const startVoiceActivation = useCallback(() => {
     const settings = useSettingsStore.getState().settings;
     if (!isActive) {
-      startWakeWordListener(settings.language, handleWakeWord, settings.wakeWord, wakeWordStatusCallback);
+      startWakeWordListener(speechLanguage(settings.language), handleWakeWord, settings.wakeWord, wakeWordStatusCallback);
     }
   }, []);
 
@@ -2,6 +2,14 @@ import { emit } from "@tauri-apps/api/event";
 import { useSettingsStore, type AppSettings } from "../stores/settingsStore";
 import { saveSettings } from "./tauri-commands";
 
+/**
+ * Dil tespiti yalnizca Whisper'da var. Web Speech, bulut motorlari ve wake word
+ * "auto" yerine varsayilan dili (Turkce) kullanir — backend'deki spoken_language ile ayni.
+ */
+export function speechLanguage(language: string): string {
+  return language === "auto" ? "tr" : language;
+}
+
 /**
  * AppSettings (camelCase) -> Backend (snake_case) donusumu.
  * Tek bir kaynak (single source of truth).
@@ -38,6 +46,8 @@ export function toBackend(s: AppSettings) {
     notifications: s.notifications,
     log_level: s.logLevel,
     tts_shortcut: s.ttsShortcut ?? "Ctrl+Shift+R",
+    whisper_live_preview: s.whisperLivePreview ?? true,
+    whisper_translate: s.whisperTranslate ?? false,
     translate_engine: s.translateEngine ?? "google",
     deepl_api_key: s.deeplApiKey ?? "",
     translate_target_lang: s.translateTargetLang ?? "en",
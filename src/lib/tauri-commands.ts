@@ -2,7 +2,15 @@ import { invoke } from "@tauri-apps/api/core";
 
 export interface AudioDevice {
   name: string;
+  /** Kararli kimlik — cihaz yeniden adlandirilsa da degismez (Windows) */
   id: string;
+  is_default: boolean;
+}
+
+/** "audio-device-fallback" olayinin yuku: secili mikrofon yok, varsayilana gecildi */
+export interface DeviceFallback {
+  lost: string;
+  fallback: string;
 }
 
 export interface WhisperModel {
@@ -14,6 +22,7 @@ export interface WhisperModel {
   filename: string;
   description: string;
   downloaded: boolean;
+  supports_translate: boolean;
 }
 
 export interface DownloadProgress {
@@ -25,11 +34,48 @@ export interface DownloadProgress {
   status: string;
 }
 
+export interface TranscriptionWord {
+  text: string;
+  start_ms: number;
+  end_ms: number;
+  /** Token olasiliklarinin ortalamasi (0..1) */
+  confidence: number;
+}
+
+export interface TranscriptionSegment {
+  text: string;
+  start_ms: number;
+  end_ms: number;
+  words: TranscriptionWord[];
+  confidence: number;
+  no_speech_prob: number;
+}
+
 export interface TranscriptionResponse {
   text: string;
   duration_ms: number;
   status: string;
   original_text: string;
+  segments: TranscriptionSegment[];
+  /** Transkripsiyon dili — "auto" modunda tespit edilen dil */
+  language: string;
+  language_probability: number | null;
+  /** Whisper ceviri modu kullanildi (metin Ingilizce) */
+  translated: boolean;
+}
+
+export interface DecodingProfile {
+  name: string;
+  strategy: "beam" | "greedy";
+  beam_size: number;
+  best_of: number;
+  temperature: number;
+  temperature_inc: number;
+  entropy_thold: number;
+  logprob_thold: number;
+  no_speech_thold: number;
+  /** 0 = tum cekirdekler */
+  threads: number;
 }
 
 export interface AppSettings {
@@ -73,6 +119,16 @@ export interface AppSettings {
   groq_api_key: string;
   gemini_api_key: string;
   ollama_model: string;
+  ollama_url?: string;
+  /** "openai" saglayicisi icin OpenAI uyumlu API adresi */
+  llm_base_url?: string;
+  decoding_profile?: string;
+  decoding_profiles?: DecodingProfile[];
+  preload_model?: boolean;
+  /** 0 = modeli hic bosaltma */
+  model_idle_timeout_mins?: number;
+  whisper_live_preview?: boolean;
+  whisper_translate?: boolean;
   features: {
     voice_commands: boolean;
     sentiment: boolean;
@@ -99,12 +155,42 @@ export interface HistoryEntry {
   engine: string;
   language: string;
   model_id: string;
+  segments?: TranscriptionSegment[];
 }
 
 export async function listAudioDevices(): Promise<AudioDevice[]> {
   return invoke("list_audio_devices");
 }
 
+/** Cihaz takma/cikarma izlemesini ac/kapat (yalnizca ayarlar gorunurken acik) */
+export async function watchAudioDevices(enabled: boolean): Promise<void> {
+  return invoke("watch_audio_devices", { enabled });
+}
+
+/** Mikrofonun kayit icin kullanacagi ornekleme hizi ve kanal sayisi */
+export interface InputConfig {
+  sample_rate: number;
+  channels: number;
+}
+
+export interface InputConfigOption extends InputConfig {
+  sample_format: string;
+  is_default: boolean;
+}
+
+export async function listAudioInputConfigs(deviceName?: string | null): Promise<InputConfigOption[]> {
+  return invoke("list_audio_input_configs", { deviceName: deviceName ?? null });
+}
+
+export async function getAudioInputConfig(deviceName?: string | null): Promise<InputConfig | null> {
+  return invoke("get_audio_input_config", { deviceName: deviceName ?? null });
+}
+
+/** `config` null ise cihazin varsayilan yapilandirmasina donulur */
+export async function setAudioInputConfig(deviceName: string | null, config: InputConfig | null): Promise<void> {
+  return invoke("set_audio_input_config", { deviceName, config });
+}
+
 export async function startRecording(
   deviceName?: string,
 ): Promise<void> {
@@ -119,17 +205,117 @@ export async function getAudioLevels(): Promise<number> {
   return invoke("get_audio_levels");
 }
 
+export interface VadStatus {
+  is_speech: boolean;
+  probability: number;
+  heard_speech: boolean;
+  silence_ms: number;
+}
+
+/** Kayit sirasinda "vad-event" olayinin yuku */
+export type VadEvent = "speech_start" | "speech_end" | "silence_timeout";
+
+export async function getVadStatus(): Promise<VadStatus> {
+  return invoke("get_vad_status");
+}
+
 export async function transcribeAudio(
   audioData: number[],
   modelId: string,
+  wordTimestamps?: boolean,
+  decodingProfile?: string,
+  translate?: boolean,
+): Promise<TranscriptionResponse> {
+  return invoke("transcribe_audio", {
+    audioData,
+    modelId,
+    wordTimestamps: wordTimestamps ?? null,
+    decodingProfile: decodingProfile ?? null,
+    translate: translate ?? null,
+  });
+}
+
+export async function transcribeFile(
+  path: string,
+  modelId: string,
+  wordTimestamps?: boolean,
+  decodingProfile?: string,
+  translate?: boolean,
 ): Promise<TranscriptionResponse> {
-  return invoke("transcribe_audio", { audioData, modelId });
+  return invoke("transcribe_file", {
+    path,
+    modelId,
+    wordTimestamps: wordTimestamps ?? null,
+    decodingProfile: decodingProfile ?? null,
+    translate: translate ?? null,
+  });
 }
 
 export async function getTranscriptionStatus(): Promise<string> {
   return invoke("get_transcription_status");
 }
 
+export interface TranscriptionJobStatus {
+  job_id: string;
+  status: "calisiyor" | "tamamlandi" | "iptal_edildi" | "hata";
+  percent: number;
+  error: string | null;
+  /** Biten isin sonucu; is okununca backend listeden siler */
+  result: TranscriptionResponse | null;
+}
+
+export interface TranscriptionProgressEvent {
+  job_id: string;
+  percent: number;
+}
+
+export interface TranscriptionFinishedEvent {
+  job_id: string;
+  status: TranscriptionJobStatus["status"];
+  result: TranscriptionResponse | null;
+  error: string | null;
+}
+
+export async function startTranscriptionJob(
+  audioData: number[],
+  modelId: string,
+  wordTimestamps?: boolean,
+  decodingProfile?: string,
+  translate?: boolean,
+): Promise<string> {
+  return invoke("start_transcription_job", {
+    audioData,
+    modelId,
+    wordTimestamps: wordTimestamps ?? null,
+    decodingProfile: decodingProfile ?? null,
+    translate: translate ?? null,
+  });
+}
+
+export async function startFileTranscriptionJob(
+  path: string,
+  modelId: string,
+  wordTimestamps?: boolean,
+  decodingProfile?: string,
+  translate?: boolean,
+): Promise<string> {
+  return invoke("start_file_transcription_job", {
+    path,
+    modelId,
+    wordTimestamps: wordTimestamps ?? null,
+    decodingProfile: decodingProfile ?? null,
+    translate: translate ?? null,
+  });
+}
+
+export async function cancelTranscriptionJob(jobId: string): Promise<boolean> {
+  return invoke("cancel_transcription_job", { jobId });
+}
+
+export async function getTranscriptionJob(jobId: string): Promise<TranscriptionJobStatus | null> {
+  return invoke("get_transcription_job", { jobId });
+}
+
 export async function listModels(): Promise<WhisperModel[]> {
   return invoke("list_models");
 }
@@ -148,6 +334,18 @@ export async function deleteModel(modelId: string): Promise<void> {
   return invoke("delete_model", { modelId });
 }
 
+export async function preloadModel(modelId: string): Promise<void> {
+  return invoke("preload_model", { modelId });
+}
+
+export async function getLoadedModels(): Promise<string[]> {
+  return invoke("get_loaded_models");
+}
+
+export async function unloadModels(): Promise<number> {
+  return invoke("unload_models");
+}
+
 export async function getSettings(): Promise<AppSettings> {
   return invoke("get_settings");
 }
@@ -203,6 +401,54 @@ export async function processText(text: string): Promise<string> {
   return invoke("process_text_command", { text });
 }
 
+// ─── Disa Aktarma ───
+
+export type ExportFormat = "srt" | "vtt" | "json" | "txt";
+
+export interface ExportOptions {
+  include_timestamps?: boolean;
+  max_line_chars?: number;
+  max_lines?: number;
+}
+
+export interface ExportSegment {
+  start_ms: number;
+  end_ms: number;
+  text: string;
+  speaker?: string | null;
+}
+
+export interface TranscriptDocument {
+  title?: string | null;
+  language: string;
+  segments: ExportSegment[];
+}
+
+export async function exportTranscript(
+  document: TranscriptDocument,
+  format: ExportFormat,
+  options?: ExportOptions,
+): Promise<string> {
+  return invoke("export_transcript", { document, format, options: options ?? null });
+}
+
+export async function exportTranscriptToFile(
+  document: TranscriptDocument,
+  format: ExportFormat,
+  path: string,
+  options?: ExportOptions,
+): Promise<void> {
+  return invoke("export_transcript_to_file", { document, format, options: options ?? null, path });
+}
+
+export async function exportHistoryEntry(
+  id: string,
+  format: ExportFormat,
+  options?: ExportOptions,
+): Promise<string> {
+  return invoke("export_history_entry", { id, format, options: options ?? null });
+}
+
 // ─── Kullanici Duzeltme Sozlugu ───
 
 export interface UserCorrection {
@@ -223,6 +469,13 @@ export interface UserProfile {
   ngrams: NgramEntry[];
   total_transcriptions: number;
   total_corrections: number;
+  uncertain_words?: UncertainWord[];
+}
+
+export interface UncertainWord {
+  word: string;
+  count: number;
+  avg_confidence: number;
 }
 
 export interface DynamicPromptPreview {
@@ -340,15 +593,39 @@ export async function uiaStopReadAlong(): Promise<void> {
 
 // ─── Ses Komutlari ───
 
+export type VoiceCommand =
+  | "new_line"
+  | "new_paragraph"
+  | "delete_last_word"
+  | "delete_last_sentence"
+  | "upper_case_all"
+  | "lower_case_all"
+  | "send"
+  | "undo"
+  | "select_all";
+
+export interface DetectedVoiceCommand {
+  command: VoiceCommand;
+  phrase: string;
+  /** cleaned_text uzerinde uygulandiysa true */
+  applied: boolean;
+  /** Komutun geldigi andaki cleaned_text uzunlugu */
+  offset: number;
+}
+
 export interface VoiceCommandResult {
   cleaned_text: string;
-  commands: unknown[];
+  commands: DetectedVoiceCommand[];
 }
 
 export async function extractVoiceCommands(text: string): Promise<VoiceCommandResult> {
   return invoke("extract_voice_commands", { text });
 }
 
+export async function executeVoiceCommands(commands: VoiceCommand[]): Promise<void> {
+  return invoke("execute_voice_commands", { commands });
+}
+
 // ─── Duygu Analizi ───
 
 export interface SentimentResult {
@@ -395,6 +672,7 @@ export interface TranscriptChunk {
   start_time: number;
   end_time: number;
   chapter_id: number;
+  speaker?: string | null;
 }
 
 export interface Chapter {
@@ -420,16 +698,89 @@ export async function stopMeeting(): Promise<MeetingState> {
   return invoke("stop_meeting");
 }
 
-export async function addMeetingChunk(text: string, startTime: number, endTime: number): Promise<void> {
-  return invoke("add_meeting_chunk", { text, startTime, endTime });
+export async function addMeetingChunk(
+  text: string,
+  startTime: number,
+  endTime: number,
+  speaker?: string,
+): Promise<void> {
+  return invoke("add_meeting_chunk", { text, startTime, endTime, speaker: speaker ?? null });
 }
 
 export async function getMeetingState(): Promise<MeetingState> {
   return invoke("get_meeting_state");
 }
 
-export async function getMeetingTranscript(): Promise<string> {
-  return invoke("get_meeting_transcript");
+export async function getMeetingTranscript(meetingId?: string): Promise<string> {
+  return invoke("get_meeting_transcript", { meetingId: meetingId ?? null });
+}
+
+export interface SpeakerStats {
+  speaker: string;
+  chunk_count: number;
+  word_count: number;
+  talk_time: number;
+  /** Toplam konusma suresi icindeki pay (0..1) */
+  share: number;
+}
+
+export interface MeetingSummary {
+  id: string;
+  start_time: number;
+  total_duration: number;
+  chunk_count: number;
+  chapter_titles: string[];
+}
+
+export interface SavedMeeting {
+  id: string;
+  start_time: number;
+  end_time: number | null;
+  chunks: TranscriptChunk[];
+  chapters: Chapter[];
+  notes: string;
+}
+
+export interface MeetingChunkEvent {
+  chunk: TranscriptChunk;
+  chapter: Chapter;
+  new_chapter: boolean;
+}
+
+export async function getMeetingSpeakerStats(meetingId?: string): Promise<SpeakerStats[]> {
+  return invoke("get_meeting_speaker_stats", { meetingId: meetingId ?? null });
+}
+
+export async function updateChunkSpeaker(chunkId: number, speaker: string): Promise<void> {
+  return invoke("update_chunk_speaker", { chunkId, speaker });
+}
+
+export async function setMeetingNotes(notes: string): Promise<void> {
+  return invoke("set_meeting_notes", { notes });
+}
+
+export async function getMeetingNotes(): Promise<string> {
+  return invoke("get_meeting_notes");
+}
+
+export async function listMeetings(): Promise<MeetingSummary[]> {
+  return invoke("list_meetings");
+}
+
+export async function getSavedMeeting(meetingId: string): Promise<SavedMeeting> {
+  return invoke("get_saved_meeting", { meetingId });
+}
+
+export async function deleteSavedMeeting(meetingId: string): Promise<void> {
+  return invoke("delete_saved_meeting", { meetingId });
+}
+
+export async function exportMeeting(
+  format: ExportFormat,
+  options?: ExportOptions,
+  meetingId?: string,
+): Promise<string> {
+  return invoke("export_meeting", { meetingId: meetingId ?? null, format, options: options ?? null });
 }
 
 // ─── AI Asistan ───
@@ -440,21 +791,35 @@ export interface LLMRequest {
   provider: string;
   api_key?: string;
   model?: string;
+  base_url?: string;
 }
 
 export interface LLMResponse {
   text: string;
   provider: string;
   model: string;
+  prompt_tokens: number;
+  completion_tokens: number;
   tokens_used: number;
+  tokens_estimated: boolean;
+}
+
+export interface LLMAction {
+  id: string;
+  label: string;
 }
 
 export async function processWithLLM(request: LLMRequest): Promise<LLMResponse> {
   return invoke("process_with_llm", { request });
 }
 
+export async function getLlmActions(): Promise<LLMAction[]> {
+  return invoke("get_llm_actions");
+}
+
 // ─── Peer Discovery ───
 
+/** mDNS ile bulunan Fisilti ornegi — "peer-discovered" / "peer-lost" olaylariyla da gelir */
 export interface PeerInfo {
   name: string;
   address: string;
@@ -470,6 +835,45 @@ export async function stopPeerService(): Promise<void> {
   return invoke("stop_peer_service");
 }
 
+// ─── Canli Transkripsiyon (Whisper) ───
+
+/** "stt-partial" olayi — stable kisim art arda iki hipotezde degismedi */
+export interface PartialHypothesis {
+  session_id: number;
+  stable: string;
+  unstable: string;
+  start_ms: number;
+}
+
+/** "stt-final" olayi — kesinlesen pencere */
+export interface FinalHypothesis {
+  session_id: number;
+  text: string;
+  start_ms: number;
+  end_ms: number;
+  segments: TranscriptionSegment[];
+}
+
+/** get_streaming_status ve "stt-stopped" olayi */
+export interface StreamingStatus {
+  running: boolean;
+  session_id: number;
+  language: string;
+  text: string;
+}
+
+export async function startStreamingTranscription(modelId?: string): Promise<number> {
+  return invoke("start_streaming_transcription", { modelId: modelId ?? null });
+}
+
+export async function stopStreamingTranscription(): Promise<void> {
+  return invoke("stop_streaming_transcription");
+}
+
+export async function getStreamingStatus(): Promise<StreamingStatus> {
+  return invoke("get_streaming_status");
+}
+
 // ─── Canli Ceviri ───
 
 export interface LiveTranslationConfig {
@@ -477,7 +881,23 @@ export interface LiveTranslationConfig {
   target_lang: string;
   translate_engine: string;
   deepl_api_key: string;
+  /** "mic", "loopback", loopback cihaz kimligi veya "file:<yol>" */
   audio_source: string;
+  /** Bos ise ayarlardaki secili model */
+  model_id?: string;
+}
+
+/** "live-caption" olayi */
+export interface LiveCaption {
+  id: number;
+  original: string;
+  translated: string;
+  source_lang: string;
+  target_lang: string;
+  speaker: string;
+  start_ms: number;
+  end_ms: number;
+  latency_ms: number;
 }
 
 export interface LiveTranslationStatus {
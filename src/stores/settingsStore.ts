@@ -31,6 +31,9 @@ export interface AppSettings {
   notifications: boolean;
   logLevel: string;
   ttsShortcut: string;
+  /** Whisper kaydi sirasinda canli onizleme */
+  whisperLivePreview: boolean;
+  whisperTranslate: boolean;
 }
 
 export type WakeWordStatus =
@@ -84,6 +87,8 @@ const defaultSettings: AppSettings = {
   notifications: true,
   logLevel: "info",
   ttsShortcut: "Ctrl+Shift+R",
+  whisperLivePreview: true,
+  whisperTranslate: false,
 };
 
 export const useSettingsStore = create<SettingsState>((set) => ({
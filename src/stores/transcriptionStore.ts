@@ -1,6 +1,6 @@
 import { create } from "zustand";
 import { invoke } from "@tauri-apps/api/core";
-import { learnFromEdit, reportCorrectionRevert } from "../lib/tauri-commands";
+import { learnFromEdit, reportCorrectionRevert, type TranscriptionSegment } from "../lib/tauri-commands";
 
 export interface TranscriptionEntry {
   id: string;
@@ -14,6 +14,8 @@ export interface TranscriptionEntry {
   editedText?: string;
   originalText?: string;
   confidence?: number;
+  /** Whisper segmentleri — SRT/VTT disa aktarimi icin zaman damgalari */
+  segments?: TranscriptionSegment[];
 }
 
 interface HistoryEntryBackend {
@@ -24,6 +26,7 @@ interface HistoryEntryBackend {
   engine: string;
   language: string;
   model_id: string;
+  segments?: TranscriptionSegment[];
 }
 
 interface TranscriptionState {
@@ -58,6 +61,7 @@ export const useTranscriptionStore = create<TranscriptionState>((set) => ({
       engine: entry.engine,
       language: entry.language,
       model_id: entry.modelId,
+      segments: entry.segments ?? [],
     };
     invoke("save_history_entry", { entry: backendEntry })
       .catch((err) => {
@@ -128,6 +132,7 @@ export const useTranscriptionStore = create<TranscriptionState>((set) => ({
         engine: (e.engine as "web" | "whisper" | "deepgram" | "azure" | "google-cloud") || "web",
         language: e.language || "tr",
         modelId: e.model_id || "web-speech",
+        segments: e.segments,
       }));
       set({ history, historyLoaded: true });
     } catch (err) {